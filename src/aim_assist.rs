@@ -0,0 +1,132 @@
+// src/aim_assist.rs
+//! 辅助瞄准：远程攻击会吸附到瞄准方向锥形范围内最近的敌人；按 Tab（可改键）在附近敌人之间轮换锁定。
+//! 锁定目标优先于吸附，目标身上画一个准星。吸附的锥角随设置里的强度缩放，强度为 0 时只保留手动锁定。
+
+use bevy::prelude::*;
+
+use crate::enemy::Enemy;
+use crate::input::KeyBindings;
+use crate::movement::Player;
+use crate::state::GameState;
+
+/// 锁定 / 吸附的最远距离
+const AIM_ASSIST_RANGE: f32 = 450.0;
+/// 强度为 1 时吸附锥的半角（弧度，约 30°）
+const AIM_ASSIST_MAX_HALF_ANGLE: f32 = 0.52;
+const RETICLE_RADIUS: f32 = 14.0;
+
+/// 当前锁定的敌人
+#[derive(Resource, Default)]
+pub struct LockedTarget(pub Option<Entity>);
+
+pub struct AimAssistPlugin;
+
+impl Plugin for AimAssistPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LockedTarget>()
+            .add_systems(
+                Update,
+                (drop_invalid_target, cycle_locked_target, draw_target_reticle)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnEnter(GameState::MainMenu), clear_locked_target);
+    }
+}
+
+/// 计算辅助后的远程瞄准方向：有锁定目标就直接瞄准它，
+/// 否则在 aim 周围 strength 缩放的锥形内找最近的敌人吸附过去，找不到则保持原方向
+pub fn assisted_aim(
+    origin: Vec2,
+    aim: Vec2,
+    locked: Option<Vec2>,
+    enemies: impl Iterator<Item = Vec2>,
+    strength: f32,
+) -> Vec2 {
+    if let Some(target) = locked {
+        let dir = (target - origin).normalize_or_zero();
+        if dir != Vec2::ZERO {
+            return dir;
+        }
+    }
+
+    if strength <= 0.0 || aim == Vec2::ZERO {
+        return aim;
+    }
+
+    let half_angle = AIM_ASSIST_MAX_HALF_ANGLE * strength.clamp(0.0, 1.0);
+    enemies
+        .filter_map(|pos| {
+            let to = pos - origin;
+            let dist = to.length();
+            (dist > f32::EPSILON && dist <= AIM_ASSIST_RANGE && aim.angle_to(to).abs() <= half_angle)
+                .then_some((dist, to / dist))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, dir)| dir)
+        .unwrap_or(aim)
+}
+
+/// 目标死亡或跑出范围时解除锁定
+fn drop_invalid_target(
+    mut locked: ResMut<LockedTarget>,
+    player_q: Query<&Transform, With<Player>>,
+    enemies_q: Query<&Transform, With<Enemy>>,
+) {
+    let Some(target) = locked.0 else { return; };
+    let Ok(player_tf) = player_q.single() else { return; };
+
+    let in_range = enemies_q
+        .get(target)
+        .is_ok_and(|tf| tf.translation.truncate().distance(player_tf.translation.truncate()) <= AIM_ASSIST_RANGE);
+    if !in_range {
+        locked.0 = None;
+    }
+}
+
+/// 按距离由近到远轮换锁定范围内的敌人；轮到最后一个之后解除锁定
+fn cycle_locked_target(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut locked: ResMut<LockedTarget>,
+    player_q: Query<&Transform, With<Player>>,
+    enemies_q: Query<(Entity, &Transform), With<Enemy>>,
+) {
+    if !keyboard.just_pressed(bindings.cycle_target) {
+        return;
+    }
+    let Ok(player_tf) = player_q.single() else { return; };
+    let ppos = player_tf.translation.truncate();
+
+    let mut candidates: Vec<(f32, Entity)> = enemies_q
+        .iter()
+        .map(|(e, tf)| (tf.translation.truncate().distance(ppos), e))
+        .filter(|(dist, _)| *dist <= AIM_ASSIST_RANGE)
+        .collect();
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let next = match locked.0.and_then(|cur| candidates.iter().position(|(_, e)| *e == cur)) {
+        Some(i) => candidates.get(i + 1),
+        None => candidates.first(),
+    };
+    locked.0 = next.map(|(_, e)| *e);
+}
+
+fn draw_target_reticle(
+    locked: Res<LockedTarget>,
+    enemies_q: Query<&Transform, With<Enemy>>,
+    mut gizmos: Gizmos,
+) {
+    let Some(tf) = locked.0.and_then(|e| enemies_q.get(e).ok()) else { return; };
+    let pos = tf.translation.truncate();
+    let color = Color::srgb(1.0, 0.85, 0.2);
+
+    gizmos.circle_2d(Isometry2d::from_translation(pos), RETICLE_RADIUS, color);
+    for dir in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y] {
+        gizmos.line_2d(pos + dir * (RETICLE_RADIUS - 5.0), pos + dir * (RETICLE_RADIUS + 5.0), color);
+    }
+}
+
+fn clear_locked_target(mut locked: ResMut<LockedTarget>) {
+    locked.0 = None;
+}
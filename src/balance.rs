@@ -0,0 +1,64 @@
+// src/balance.rs
+//! 数值配置：刷怪、敌人属性、命中判定和武器倍率集中在这里，调数值不用翻代码。
+//! 默认值与原来写死在各系统里的数字一致。
+
+use bevy::prelude::*;
+
+#[derive(Resource, Clone, Debug)]
+pub struct BalanceConfig {
+    /// 普通难度下的刷怪间隔（秒），再乘难度系数
+    pub spawn_interval_secs: f32,
+    /// 每次刷怪的数量，沿屏幕边缘均匀分布
+    pub spawn_batch: u32,
+    /// 拿不到相机视野时，刷怪点到玩家的距离
+    pub spawn_radius: f32,
+    /// 敌人离玩家超过这个距离就直接移除（跟丢玩家、被甩在地图另一头的），不掉落也不算击杀
+    pub enemy_despawn_radius: f32,
+    pub enemy_base_hp: f32,
+    pub enemy_base_speed: f32,
+    /// 接触伤害基础值，再乘难度系数
+    pub enemy_base_damage: f32,
+    /// 新刷出的敌人成为精英（带词缀）的概率
+    pub elite_chance: f32,
+    /// 每个敌人释放技能的冷却（秒）
+    pub enemy_cast_interval_secs: f32,
+    /// 玩家在敌人多远以内，该敌人才会释放技能
+    pub enemy_cast_range: f32,
+    /// 远程敌人（Spitter）的出手距离
+    pub enemy_bolt_range: f32,
+    /// 子弹命中判定半径
+    pub projectile_hit_radius: f32,
+    /// 近战武器伤害倍率
+    pub melee_damage_mult: f32,
+    /// 远程武器伤害倍率
+    pub ranged_damage_mult: f32,
+}
+
+impl Default for BalanceConfig {
+    fn default() -> Self {
+        Self {
+            spawn_interval_secs: 1.0,
+            spawn_batch: 1,
+            spawn_radius: 200.0,
+            enemy_despawn_radius: 1600.0,
+            enemy_base_hp: 40.0,
+            enemy_base_speed: 60.0,
+            enemy_base_damage: 8.0,
+            elite_chance: 0.08,
+            enemy_cast_interval_secs: 1.2,
+            enemy_cast_range: 160.0,
+            enemy_bolt_range: 360.0,
+            projectile_hit_radius: 12.0,
+            melee_damage_mult: 1.5,
+            ranged_damage_mult: 1.3,
+        }
+    }
+}
+
+pub struct BalancePlugin;
+
+impl Plugin for BalancePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BalanceConfig>();
+    }
+}
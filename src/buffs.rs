@@ -0,0 +1,206 @@
+// src/buffs.rs
+//! 限时增益：捡到强化道具（"狂暴" 加伤害、"疾行" 加移速）后挂在玩家的 `Buffs` 上，倒计时结束自动移除。
+//! 同种增益再次拾取只刷新持续时间，不叠加数值。移动和攻击读取 `Buffs` 的倍率；
+//! HUD 左上角每个增益一个色块，下面显示剩余秒数。
+
+use bevy::prelude::*;
+
+use crate::i18n::{tr, Language};
+use crate::movement::Player;
+use crate::state::GameState;
+use crate::utils::despawn_with_children;
+
+const BERSERK_DAMAGE_FACTOR: f32 = 1.5;
+const HASTE_SPEED_FACTOR: f32 = 1.35;
+const BUFF_ICON_SIZE: f32 = 28.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BuffKind {
+    /// 狂暴：伤害提高
+    Berserk,
+    /// 疾行：移速提高
+    Haste,
+}
+
+impl BuffKind {
+    pub const ALL: [BuffKind; 2] = [BuffKind::Berserk, BuffKind::Haste];
+
+    pub fn display_name(self, lang: Language) -> &'static str {
+        match self {
+            BuffKind::Berserk => tr(lang, "buff.berserk"),
+            BuffKind::Haste => tr(lang, "buff.haste"),
+        }
+    }
+
+    /// 地上的道具和 HUD 图标共用的颜色
+    pub fn color(self) -> Color {
+        match self {
+            BuffKind::Berserk => Color::srgb(0.9, 0.3, 0.2),
+            BuffKind::Haste => Color::srgb(0.3, 0.85, 0.9),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Buff {
+    pub kind: BuffKind,
+    pub timer: Timer,
+}
+
+/// 玩家身上生效中的增益
+#[derive(Component, Default, Clone, Debug)]
+pub struct Buffs(pub Vec<Buff>);
+
+impl Buffs {
+    /// 添加增益；已有同种增益时只把剩余时间刷新为 duration
+    pub fn apply(&mut self, kind: BuffKind, duration: f32) {
+        let timer = Timer::from_seconds(duration, TimerMode::Once);
+        match self.0.iter_mut().find(|b| b.kind == kind) {
+            Some(existing) => existing.timer = timer,
+            None => self.0.push(Buff { kind, timer }),
+        }
+    }
+
+    pub fn has(&self, kind: BuffKind) -> bool {
+        self.0.iter().any(|b| b.kind == kind)
+    }
+
+    pub fn damage_factor(&self) -> f32 {
+        if self.has(BuffKind::Berserk) { BERSERK_DAMAGE_FACTOR } else { 1.0 }
+    }
+
+    pub fn speed_factor(&self) -> f32 {
+        if self.has(BuffKind::Haste) { HASTE_SPEED_FACTOR } else { 1.0 }
+    }
+}
+
+#[derive(Component)]
+struct BuffHudRoot;
+
+#[derive(Component)]
+struct BuffTimerText(BuffKind);
+
+pub struct BuffsPlugin;
+
+impl Plugin for BuffsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::InGame), setup_buff_hud)
+            .add_systems(OnExit(GameState::InGame), cleanup_buff_hud)
+            .add_systems(
+                Update,
+                (tick_buffs, sync_buff_hud).chain().run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// 倒计时，到期的增益移除
+fn tick_buffs(time: Res<Time>, mut q: Query<&mut Buffs, With<Player>>) {
+    for mut buffs in &mut q {
+        for buff in buffs.0.iter_mut() {
+            buff.timer.tick(time.delta());
+        }
+        buffs.0.retain(|b| !b.timer.is_finished());
+    }
+}
+
+/// 放在金币下方
+fn setup_buff_hud(mut commands: Commands) {
+    commands.spawn((
+        BuffHudRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(16.0),
+            top: Val::Px(132.0),
+            column_gap: Val::Px(6.0),
+            ..default()
+        },
+    ));
+}
+
+/// 增益种类变了就重建图标，否则只刷新倒计时
+fn sync_buff_hud(
+    mut commands: Commands,
+    player_q: Query<&Buffs, With<Player>>,
+    root_q: Query<Entity, With<BuffHudRoot>>,
+    children_q: Query<&Children>,
+    mut texts: Query<(&mut Text, &BuffTimerText)>,
+    mut shown: Local<Vec<BuffKind>>,
+) {
+    let Ok(root) = root_q.single() else { return; };
+    let active: &[Buff] = player_q.single().map(|b| b.0.as_slice()).unwrap_or(&[]);
+    let kinds: Vec<BuffKind> = active.iter().map(|b| b.kind).collect();
+
+    // HUD 刚重新生成（例如暂停回来）时没有子节点，也要重建
+    let empty_root = children_q.get(root).map_or(true, |c| c.is_empty());
+    if kinds != *shown || (empty_root && !kinds.is_empty()) {
+        if let Ok(children) = children_q.get(root) {
+            for child in children.iter() {
+                despawn_with_children(&mut commands, &children_q, child);
+            }
+        }
+        commands.entity(root).with_children(|row| {
+            for buff in active {
+                row.spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                ))
+                .with_children(|icon| {
+                    icon.spawn((
+                        Node {
+                            width: Val::Px(BUFF_ICON_SIZE),
+                            height: Val::Px(BUFF_ICON_SIZE),
+                            ..default()
+                        },
+                        BackgroundColor(buff.kind.color()),
+                    ));
+                    icon.spawn((
+                        BuffTimerText(buff.kind),
+                        Text::new(format!("{:.0}", buff.timer.remaining_secs().ceil())),
+                        TextFont { font_size: 14.0, ..default() },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+            }
+        });
+        *shown = kinds;
+        return;
+    }
+
+    for (mut text, BuffTimerText(kind)) in &mut texts {
+        if let Some(buff) = active.iter().find(|b| b.kind == *kind) {
+            let label = format!("{:.0}", buff.timer.remaining_secs().ceil());
+            if text.0 != label {
+                text.0 = label;
+            }
+        }
+    }
+}
+
+fn cleanup_buff_hud(mut commands: Commands, q: Query<Entity, With<BuffHudRoot>>) {
+    for e in &q {
+        commands.entity(e).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn reapplying_a_buff_refreshes_duration_without_stacking() {
+        let mut buffs = Buffs::default();
+        buffs.apply(BuffKind::Berserk, 10.0);
+        buffs.0[0].timer.tick(Duration::from_secs(8));
+        buffs.apply(BuffKind::Berserk, 10.0);
+
+        assert_eq!(buffs.0.len(), 1);
+        assert_eq!(buffs.0[0].timer.remaining_secs(), 10.0);
+        assert_eq!(buffs.damage_factor(), BERSERK_DAMAGE_FACTOR);
+        assert_eq!(buffs.speed_factor(), 1.0);
+    }
+}
@@ -0,0 +1,260 @@
+// src/checkpoint.rs
+//! 检查点复活：走到 LDtk 的 "Checkpoint" 实体附近或触发自动存档时记录复活点。
+//! 玩家倒下时如果有复活点且还有复活次数，就停在 InGame 弹出复活面板（暂停 Time<Virtual>），
+//! 否则照旧进入 GameOver。
+
+use bevy::prelude::*;
+use bevy::ui::Val;
+use bevy_ecs_ldtk::prelude::EntityInstance;
+
+use crate::health::{Health, Invulnerable};
+use crate::i18n::{localized, tr};
+use crate::movement::Player;
+use crate::notifications::Notifications;
+use crate::state::GameState;
+use crate::transition::TransitionRequest;
+use crate::ui::types::GameSettings;
+use crate::utils::despawn_with_children;
+
+/// 每局可复活次数（复活的代价）
+const MAX_REVIVES: u32 = 3;
+/// 玩家进入这个距离即激活检查点
+const CHECKPOINT_RADIUS: f32 = 32.0;
+/// 复活后的无敌时间（秒）
+const REVIVE_IFRAME_SECS: f32 = 1.5;
+
+/// 最近一次激活的复活点（世界坐标）
+#[derive(Resource, Default)]
+pub struct Checkpoint {
+    pub position: Option<Vec2>,
+}
+
+/// 本局剩余复活次数
+#[derive(Resource)]
+pub struct ReviveCharges(pub u32);
+
+impl Default for ReviveCharges {
+    fn default() -> Self {
+        Self(MAX_REVIVES)
+    }
+}
+
+impl ReviveCharges {
+    pub fn can_revive(&self, checkpoint: &Checkpoint) -> bool {
+        self.0 > 0 && checkpoint.position.is_some()
+    }
+}
+
+/// 玩家倒下、正在等待选择复活或返回标题
+#[derive(Component)]
+pub struct Downed;
+
+#[derive(Component)]
+struct RevivePromptRoot;
+
+#[derive(Component, Clone, Copy)]
+enum RevivePromptButton {
+    Revive,
+    BackToTitle,
+}
+
+pub struct CheckpointPlugin;
+
+impl Plugin for CheckpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Checkpoint>()
+            .init_resource::<ReviveCharges>()
+            .add_systems(
+                Update,
+                (activate_ldtk_checkpoints, spawn_revive_prompt, handle_revive_buttons)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnEnter(GameState::MainMenu), reset_checkpoint);
+    }
+}
+
+fn activate_ldtk_checkpoints(
+    mut checkpoint: ResMut<Checkpoint>,
+    mut notifications: ResMut<Notifications>,
+    settings: Res<GameSettings>,
+    player_q: Query<&Transform, (With<Player>, Without<Downed>)>,
+    points_q: Query<(&EntityInstance, &GlobalTransform)>,
+) {
+    let Ok(player_tf) = player_q.single() else { return; };
+    let ppos = player_tf.translation.truncate();
+
+    for (inst, gt) in &points_q {
+        if inst.identifier != "Checkpoint" {
+            continue;
+        }
+        let pos = gt.translation().truncate();
+        if pos.distance(ppos) > CHECKPOINT_RADIUS {
+            continue;
+        }
+        if checkpoint.position.is_some_and(|p| p.distance(pos) < 1.0) {
+            continue;
+        }
+        checkpoint.position = Some(pos);
+        notifications.notify(tr(settings.language, "notify.checkpoint"));
+    }
+}
+
+fn spawn_revive_prompt(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    charges: Res<ReviveCharges>,
+    downed_q: Query<(), Added<Downed>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if downed_q.is_empty() {
+        return;
+    }
+
+    // 面板打开期间冻结模拟，按钮交互不依赖虚拟时间
+    virtual_time.pause();
+
+    let font: Handle<Font> = asset_server.load("fonts/YuFanLixing.otf");
+    let lang = settings.language;
+
+    commands
+        .spawn((
+            RevivePromptRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.65)),
+            GlobalZIndex(5),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(480.0),
+                    padding: UiRect::all(Val::Px(26.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(14.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.12, 0.12, 0.16, 0.95)),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    localized(lang, "revive.title"),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 40.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+                panel.spawn((
+                    Text::new(format!("{}{}", tr(lang, "revive.charges"), charges.0)),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgba(1.0, 1.0, 1.0, 0.85)),
+                ));
+
+                for (key, button) in [
+                    ("revive.at_checkpoint", RevivePromptButton::Revive),
+                    ("revive.back_title", RevivePromptButton::BackToTitle),
+                ] {
+                    panel
+                        .spawn((
+                            Button,
+                            button,
+                            Node {
+                                width: Val::Px(260.0),
+                                height: Val::Px(46.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.20, 0.20, 0.40)),
+                        ))
+                        .with_children(|btn| {
+                            btn.spawn((
+                                localized(lang, key),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 20.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+                }
+            });
+        });
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn handle_revive_buttons(
+    mut commands: Commands,
+    mut interactions: Query<(&Interaction, &mut BackgroundColor, &RevivePromptButton), Changed<Interaction>>,
+    mut player_q: Query<(Entity, &mut Transform, &mut Health, Option<&mut Invulnerable>), (With<Player>, With<Downed>)>,
+    checkpoint: Res<Checkpoint>,
+    mut charges: ResMut<ReviveCharges>,
+    mut transition_tx: MessageWriter<TransitionRequest>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    roots: Query<Entity, With<RevivePromptRoot>>,
+    children_q: Query<&Children>,
+) {
+    for (interaction, mut bg, button) in &mut interactions {
+        match *interaction {
+            Interaction::Pressed => {
+                bg.0 = Color::srgb(0.8, 0.8, 1.0);
+
+                match *button {
+                    RevivePromptButton::Revive => {
+                        let Some(pos) = checkpoint.position else { continue; };
+                        let Ok((entity, mut tf, mut health, iframes)) = player_q.single_mut() else { continue; };
+
+                        charges.0 = charges.0.saturating_sub(1);
+                        tf.translation.x = pos.x;
+                        tf.translation.y = pos.y;
+                        health.current = health.max;
+                        if let Some(mut iframes) = iframes {
+                            iframes.0 = REVIVE_IFRAME_SECS;
+                        }
+                        commands.entity(entity).remove::<Downed>();
+                    }
+                    RevivePromptButton::BackToTitle => {
+                        transition_tx.write(TransitionRequest(GameState::MainMenu));
+                    }
+                }
+
+                virtual_time.unpause();
+                for root in &roots {
+                    despawn_with_children(&mut commands, &children_q, root);
+                }
+            }
+            Interaction::Hovered => bg.0 = Color::srgb(0.6, 0.6, 0.8),
+            Interaction::None => bg.0 = Color::srgb(0.20, 0.20, 0.40),
+        }
+    }
+}
+
+fn reset_checkpoint(
+    mut commands: Commands,
+    mut checkpoint: ResMut<Checkpoint>,
+    mut charges: ResMut<ReviveCharges>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    roots: Query<Entity, With<RevivePromptRoot>>,
+    children_q: Query<&Children>,
+) {
+    checkpoint.position = None;
+    *charges = ReviveCharges::default();
+    virtual_time.unpause();
+    for root in &roots {
+        despawn_with_children(&mut commands, &children_q, root);
+    }
+}
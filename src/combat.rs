@@ -3,34 +3,63 @@ use bevy::input::mouse::MouseButton;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
-use crate::combat_core::{spawn_projectile, CombatSet, ProjectilePool};
-use crate::equipment::{EquipmentSet, WeaponKind};
-use crate::enemy::Enemy;
+use crate::aim_assist::{assisted_aim, LockedTarget};
+use crate::balance::BalanceConfig;
+use crate::buffs::Buffs;
+use crate::checkpoint::Downed;
+use crate::combat_core::{
+    spawn_projectile, CollisionLayers, CombatSet, DamageDealt, EnemyDied, ProjectileAoe, ProjectilePool,
+};
+use crate::equipment::{EquipmentSet, SecondaryEquipmentSet, WeaponKind};
+use crate::elite::{Elite, EliteExploded, EliteMod};
+use crate::enemy::{Enemy, EnemyDamage};
 use crate::health::Health;
 use crate::input::MovementInput;
-use crate::movement::Player;
+use crate::ldtk_collision::WallColliders;
+use crate::meta_progress::MetaBonuses;
+use crate::movement::{cursor_world_pos, AttackClip, Player, PlayerAnimation};
 use crate::state::GameState;
+use crate::ui::types::{FaceMode, GameSettings};
 
 #[derive(Component, Default)]
 pub struct AttackState {
     pub basic_cooldown: f32,
+    pub secondary_cooldown: f32,
     pub slash_cooldown: f32,
 }
 
+/// 角色没有指定贴图尺寸时的枪口偏移（像素）
+const DEFAULT_MUZZLE_OFFSET: f32 = 12.0;
+/// 爆炸弹的范围伤害相对子弹直击伤害的比例
+const PROJECTILE_EXPLOSION_DAMAGE_FRACTION: f32 = 0.6;
+/// 瞄准线：颜色、末端准星半径
+const AIM_LINE_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.25);
+const AIM_RETICLE_RADIUS: f32 = 5.0;
+/// 主武器冷却弧：离角色边缘的距离、颜色
+const ATTACK_COOLDOWN_ARC_GAP: f32 = 6.0;
+const ATTACK_COOLDOWN_ARC_COLOR: Color = Color::srgba(1.0, 0.85, 0.4, 0.7);
+
 pub struct CombatPlugin;
 
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (ensure_attack_state, tick_attack_state, handle_basic_attack, cleanup_dead_enemies)
+            (
+                ensure_attack_state,
+                tick_attack_state,
+                handle_basic_attack,
+                cleanup_dead_enemies,
+                draw_aim_line,
+                draw_attack_cooldown,
+            )
                 .in_set(CombatSet)
                 .run_if(in_state(GameState::InGame)),
         );
     }
 }
 
-fn ensure_attack_state(mut commands: Commands, query: Query<(Entity, Option<&AttackState>), With<Player>>) {
+pub(crate) fn ensure_attack_state(mut commands: Commands, query: Query<(Entity, Option<&AttackState>), With<Player>>) {
     for (entity, state) in &query {
         if state.is_none() {
             commands.entity(entity).insert(AttackState::default());
@@ -42,10 +71,12 @@ fn tick_attack_state(time: Res<Time>, mut query: Query<&mut AttackState>) {
     let dt = time.delta_secs();
     for mut state in &mut query {
         state.basic_cooldown = (state.basic_cooldown - dt).max(0.0);
+        state.secondary_cooldown = (state.secondary_cooldown - dt).max(0.0);
         state.slash_cooldown = (state.slash_cooldown - dt).max(0.0);
     }
 }
 
+#[allow(clippy::collapsible_if, clippy::too_many_arguments, clippy::type_complexity)]
 fn handle_basic_attack(
     mouse: Res<ButtonInput<MouseButton>>,
     movement: Res<MovementInput>,
@@ -53,68 +84,220 @@ fn handle_basic_attack(
     camera: Single<(&Camera, &GlobalTransform), With<Camera2d>>,
     mut commands: Commands,
     mut proj_pool: ResMut<ProjectilePool>,
-    mut player_q: Query<(&Transform, &EquipmentSet, &mut AttackState), With<Player>>,
-    mut enemies_q: Query<(Entity, &Transform, &mut Health), With<Enemy>>,
+    mut player_q: Query<
+        (
+            &Transform,
+            &mut PlayerAnimation,
+            &EquipmentSet,
+            Option<&SecondaryEquipmentSet>,
+            &mut AttackState,
+            Option<&Sprite>,
+            Option<&MetaBonuses>,
+            Option<&Buffs>,
+        ),
+        // 倒地等复活时不能出手
+        (With<Player>, Without<Downed>),
+    >,
+    enemies_q: Query<(Entity, &Transform), With<Enemy>>,
+    mut dealt_tx: MessageWriter<DamageDealt>,
+    balance: Res<BalanceConfig>,
+    walls: Res<WallColliders>,
+    locked: Res<LockedTarget>,
+    settings: Res<GameSettings>,
 ) {
-    if !mouse.just_pressed(MouseButton::Left) {
+    // 左键 = 主武器，右键 = 副武器，各自独立冷却
+    let primary_pressed = mouse.just_pressed(MouseButton::Left);
+    let secondary_pressed = mouse.just_pressed(MouseButton::Right);
+    if !primary_pressed && !secondary_pressed {
         return;
     }
 
-    let Ok((player_tf, equip, mut state)) = player_q.single_mut() else { return; };
-    if state.basic_cooldown > 0.0 {
-        return;
+    let Ok((player_tf, mut anim, equip, secondary, mut state, sprite, bonuses, buffs)) = player_q.single_mut() else { return; };
+    let damage_mult = bonuses.map_or(1.0, |b| b.damage_factor) * buffs.map_or(1.0, Buffs::damage_factor);
+    let origin = player_tf.translation.truncate();
+    // 子弹从角色边缘（枪口）射出，而不是从角色中心
+    let muzzle_offset = sprite
+        .and_then(|s| s.custom_size)
+        .map(|size| size.max_element() * 0.5)
+        .unwrap_or(DEFAULT_MUZZLE_OFFSET);
+
+    let (cam, cam_global) = *camera;
+    let cursor_aim = cursor_world_pos(&window, cam, cam_global)
+        .map(|world_pos| (world_pos - origin).normalize_or_zero())
+        .filter(|aim| *aim != Vec2::ZERO);
+
+    // 站着不动时按角色当前朝向出手，而不是固定朝上；朝向鼠标模式下近战也朝鼠标
+    let facing = match cursor_aim {
+        Some(aim) if settings.face_mode == FaceMode::Cursor => aim,
+        _ if movement.0 != Vec2::ZERO => movement.0.normalize(),
+        _ => anim.direction.as_vec2(),
+    };
+
+    // 近战按朝向出手，远程优先瞄准鼠标，再经过辅助瞄准（锁定目标 / 锥形吸附）修正
+    let locked_pos = locked
+        .0
+        .and_then(|e| enemies_q.get(e).ok())
+        .map(|(_, tf)| tf.translation.truncate());
+    let ranged_aim = assisted_aim(
+        origin,
+        cursor_aim.unwrap_or(facing),
+        locked_pos,
+        enemies_q.iter().map(|(_, tf)| tf.translation.truncate()),
+        settings.aim_assist,
+    );
+    let aim_for = |equip: &EquipmentSet| match equip.weapon_kind {
+        WeaponKind::Melee => facing,
+        WeaponKind::Ranged => ranged_aim,
+    };
+
+    if primary_pressed && state.basic_cooldown <= 0.0 {
+        let dir = aim_for(equip);
+        fire_weapon(&mut commands, &mut proj_pool, &enemies_q, &mut dealt_tx, &balance, damage_mult, &walls, equip, origin, muzzle_offset, dir);
+        state.basic_cooldown = equip.weapon_attack_cooldown;
+        anim.play_attack(AttackClip::for_weapon(equip.weapon_kind), dir);
     }
 
-    let mut dir = if movement.0 != Vec2::ZERO { movement.0.normalize() } else { Vec2::Y };
+    if secondary_pressed && state.secondary_cooldown <= 0.0 {
+        if let Some(SecondaryEquipmentSet(equip)) = secondary {
+            let dir = aim_for(equip);
+            fire_weapon(&mut commands, &mut proj_pool, &enemies_q, &mut dealt_tx, &balance, damage_mult, &walls, equip, origin, muzzle_offset, dir);
+            state.secondary_cooldown = equip.weapon_attack_cooldown;
+            anim.play_attack(AttackClip::for_weapon(equip.weapon_kind), dir);
+        }
+    }
+}
 
+#[allow(clippy::too_many_arguments)]
+fn fire_weapon(
+    commands: &mut Commands,
+    proj_pool: &mut ProjectilePool,
+    enemies_q: &Query<(Entity, &Transform), With<Enemy>>,
+    dealt_tx: &mut MessageWriter<DamageDealt>,
+    balance: &BalanceConfig,
+    damage_mult: f32,
+    walls: &WallColliders,
+    equip: &EquipmentSet,
+    origin: Vec2,
+    muzzle_offset: f32,
+    dir: Vec2,
+) {
     match equip.weapon_kind {
         WeaponKind::Melee => {
-            let damage = equip.weapon_damage * 1.5;
+            let damage = equip.weapon_damage * balance.melee_damage_mult * damage_mult;
             perform_melee_attack(
-                player_tf.translation.truncate(),
+                origin,
                 dir,
                 equip.melee_range,
                 equip.melee_width,
                 damage,
-                &mut enemies_q,
+                equip.lifesteal,
+                enemies_q,
+                walls,
+                dealt_tx,
             );
         }
         WeaponKind::Ranged => {
-            if let Some(screen_pos) = window.cursor_position() {
-                let (cam, cam_global) = *camera;
-                if let Ok(world_pos) = cam.viewport_to_world_2d(cam_global, screen_pos) {
-                    let player_pos = player_tf.translation.truncate();
-                    let aim = (world_pos - player_pos).normalize_or_zero();
-                    if aim != Vec2::ZERO {
-                        dir = aim;
-                    }
-                }
+            let damage = equip.weapon_damage * balance.ranged_damage_mult * damage_mult;
+            let on_death_aoe = equip
+                .projectile_explosion_radius
+                .map(|radius| ProjectileAoe { radius, damage: damage * PROJECTILE_EXPLOSION_DAMAGE_FRACTION });
+            let muzzle = origin + dir.normalize_or_zero() * muzzle_offset;
+            for shot_dir in fan_directions(dir, equip.projectile_count, equip.spread_degrees) {
+                spawn_projectile(
+                    commands,
+                    Some(&mut *proj_pool),
+                    muzzle,
+                    shot_dir,
+                    equip.weapon_projectile_speed,
+                    equip.weapon_projectile_lifetime,
+                    damage,
+                    CollisionLayers::player_projectile(equip.shoots_down_projectiles),
+                    on_death_aoe,
+                    equip.damage_falloff,
+                    equip.lifesteal,
+                );
             }
-
-            let damage = equip.weapon_damage * 1.3;
-            spawn_projectile(
-                &mut commands,
-                Some(&mut proj_pool),
-                player_tf.translation.truncate(),
-                dir,
-                equip.weapon_projectile_speed,
-                equip.weapon_projectile_lifetime,
-                damage,
-                true,
-            );
         }
     }
+}
+
+/// 装备远程武器时从枪口向鼠标画一条淡线，长度不超过子弹射程；主副武器都是近战时不画
+#[allow(clippy::type_complexity)]
+fn draw_aim_line(
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<Camera2d>>,
+    player_q: Query<(&Transform, &EquipmentSet, Option<&SecondaryEquipmentSet>, Option<&Sprite>), With<Player>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok((player_tf, equip, secondary, sprite)) = player_q.single() else { return; };
+    let Some(ranged) = [Some(equip), secondary.map(|s| &s.0)]
+        .into_iter()
+        .flatten()
+        .find(|e| e.weapon_kind == WeaponKind::Ranged)
+    else {
+        return;
+    };
+
+    let (cam, cam_global) = *camera;
+    let Some(cursor) = cursor_world_pos(&window, cam, cam_global) else { return; };
+    let origin = player_tf.translation.truncate();
+    let to_cursor = cursor - origin;
+    let Some(dir) = to_cursor.try_normalize() else { return; };
+
+    let muzzle_offset = sprite
+        .and_then(|s| s.custom_size)
+        .map(|size| size.max_element() * 0.5)
+        .unwrap_or(DEFAULT_MUZZLE_OFFSET);
+    let range = ranged.weapon_projectile_speed * ranged.weapon_projectile_lifetime;
+    let length = to_cursor.length().min(range);
+    if length <= muzzle_offset {
+        return;
+    }
+
+    let end = origin + dir * length;
+    gizmos.line_2d(origin + dir * muzzle_offset, end, AIM_LINE_COLOR);
+    gizmos.circle_2d(Isometry2d::from_translation(end), AIM_RETICLE_RADIUS, AIM_LINE_COLOR);
+}
+
+/// 主武器冷却中时在角色头顶画一段弧，随 basic_cooldown / 武器冷却逐渐缩短，缩没了就能再出手
+fn draw_attack_cooldown(
+    player_q: Query<(&Transform, &AttackState, &EquipmentSet, Option<&Sprite>), With<Player>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok((player_tf, state, equip, sprite)) = player_q.single() else { return; };
+    if state.basic_cooldown <= 0.0 || equip.weapon_attack_cooldown <= 0.0 {
+        return;
+    }
+
+    let fraction = (state.basic_cooldown / equip.weapon_attack_cooldown).clamp(0.0, 1.0);
+    let radius = sprite
+        .and_then(|s| s.custom_size)
+        .map(|size| size.max_element() * 0.5)
+        .unwrap_or(DEFAULT_MUZZLE_OFFSET)
+        + ATTACK_COOLDOWN_ARC_GAP;
+    let iso = Isometry2d::from_translation(player_tf.translation.truncate());
+    gizmos.arc_2d(iso, std::f32::consts::TAU * fraction, radius, ATTACK_COOLDOWN_ARC_COLOR);
+}
 
-    state.basic_cooldown = equip.weapon_attack_cooldown;
+/// 把 count 发子弹在 aim 两侧对称地铺成 spread_degrees 度的扇形；只有一发时就是 aim 本身
+fn fan_directions(aim: Vec2, count: u32, spread_degrees: f32) -> impl Iterator<Item = Vec2> {
+    let count = count.max(1);
+    let spread = spread_degrees.to_radians();
+    let (start, step) = if count > 1 { (-spread * 0.5, spread / (count - 1) as f32) } else { (0.0, 0.0) };
+    (0..count).map(move |i| Vec2::from_angle(start + step * i as f32).rotate(aim))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn perform_melee_attack(
     origin: Vec2,
     dir: Vec2,
     length: f32,
     width: f32,
     damage: f32,
-    enemies_q: &mut Query<(Entity, &Transform, &mut Health), With<Enemy>>,
+    lifesteal: f32,
+    enemies_q: &Query<(Entity, &Transform), With<Enemy>>,
+    walls: &WallColliders,
+    dealt_tx: &mut MessageWriter<DamageDealt>,
 ) {
     let forward = dir.normalize_or_zero();
     if forward == Vec2::ZERO {
@@ -122,21 +305,59 @@ fn perform_melee_attack(
     }
     let right = Vec2::new(-forward.y, forward.x);
 
-    for (_entity, tf, mut hp) in enemies_q.iter_mut() {
-        let to_target = tf.translation.truncate() - origin;
+    for (entity, tf) in enemies_q.iter() {
+        let target = tf.translation.truncate();
+        let to_target = target - origin;
         let d_forward = to_target.dot(forward);
         let d_side = to_target.dot(right);
 
-        if d_forward >= 0.0 && d_forward <= length && d_side.abs() <= width * 0.5 {
-            hp.current -= damage;
+        // 隔着墙打不到
+        if d_forward >= 0.0
+            && d_forward <= length
+            && d_side.abs() <= width * 0.5
+            && walls.line_of_sight(origin, target)
+        {
+            dealt_tx.write(DamageDealt { target: entity, amount: damage, crit: false, lifesteal });
         }
     }
 }
 
-fn cleanup_dead_enemies(mut commands: Commands, enemies: Query<(Entity, &Health), With<Enemy>>) {
-    for (entity, hp) in &enemies {
+#[allow(clippy::type_complexity)]
+pub(crate) fn cleanup_dead_enemies(
+    mut commands: Commands,
+    enemies: Query<(Entity, &Health, &Transform, Option<&Elite>, Option<&EnemyDamage>), With<Enemy>>,
+    mut died_tx: MessageWriter<EnemyDied>,
+    mut exploded_tx: MessageWriter<EliteExploded>,
+) {
+    for (entity, hp, tf, elite, contact) in &enemies {
         if hp.current <= 0.0 {
+            let position = tf.translation.truncate();
+            died_tx.write(EnemyDied { entity, position, elite: elite.is_some() });
+            if elite.is_some_and(|e| e.has(EliteMod::Explosive)) {
+                exploded_tx.write(EliteExploded::new(position, contact.map_or(0.0, |d| d.0)));
+            }
             commands.entity(entity).try_despawn();
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_shot_follows_aim_regardless_of_spread() {
+        let dirs: Vec<Vec2> = fan_directions(Vec2::X, 1, 30.0).collect();
+        assert_eq!(dirs.len(), 1);
+        assert!(dirs[0].distance(Vec2::X) < 1e-5);
+    }
+
+    #[test]
+    fn fan_is_symmetric_around_aim() {
+        let dirs: Vec<Vec2> = fan_directions(Vec2::Y, 3, 40.0).collect();
+        assert_eq!(dirs.len(), 3);
+        assert!((Vec2::Y.angle_to(dirs[0]) + 20f32.to_radians()).abs() < 1e-4);
+        assert!(dirs[1].distance(Vec2::Y) < 1e-5);
+        assert!((Vec2::Y.angle_to(dirs[2]) - 20f32.to_radians()).abs() < 1e-4);
+    }
+}
@@ -1,10 +1,16 @@
 use bevy::prelude::*;
-use std::collections::{HashMap, HashSet};
-
-use crate::enemy::Enemy;
-use crate::health::Health;
-use crate::movement::Player;
-use crate::state::GameState;
+use std::collections::HashMap;
+
+use crate::balance::BalanceConfig;
+use crate::elite::Elite;
+use crate::equipment::DamageFalloff;
+use crate::health::{Health, PlayerDamaged};
+use crate::layers;
+use crate::ldtk_collision::WallColliders;
+use crate::enemy::{visible_world_rect, Enemy};
+use crate::movement::{Player, PlayerCamera};
+use crate::palette::Palette;
+use crate::state::{leaving_run, GameState};
 
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct CombatSet;
@@ -14,14 +20,34 @@ pub struct CombatCorePlugin;
 impl Plugin for CombatCorePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EnemyHpBarMap>()
+            .init_resource::<HpBarPool>()
             .init_resource::<ProjectilePool>()
             .init_resource::<VfxPool>()
+            .init_resource::<DamageNumberPool>()
+            .init_resource::<TrailPool>()
+            .add_message::<DamageDealt>()
+            .add_message::<EnemyDied>()
+            .add_message::<EnemyDespawned>()
             .configure_sets(Update, CombatSet.run_if(in_state(GameState::InGame)))
             .add_systems(
                 Update,
-                (update_projectiles, update_slash_vfx, sync_enemy_hp_bars, process_enemy_death)
+                (
+                    intercept_enemy_projectiles.before(update_projectiles),
+                    update_projectiles,
+                    update_projectile_trails.after(update_projectiles),
+                    apply_damage_dealt.after(update_projectiles),
+                    update_slash_vfx,
+                    draw_explosion_vfx,
+                    update_damage_numbers,
+                    update_hit_flash,
+                    sync_enemy_hp_bars,
+                    remove_hp_bars_of_dead_enemies.after(crate::combat::cleanup_dead_enemies),
+                )
                     .in_set(CombatSet),
-            );
+            )
+            // 离开这一局（回标题、失败）时清掉所有战斗实体和池子；暂停只是切到 Paused，不清
+            .add_systems(OnExit(GameState::InGame), cleanup_combat_state.run_if(leaving_run))
+            .add_systems(OnExit(GameState::Paused), cleanup_combat_state.run_if(leaving_run));
     }
 }
 
@@ -31,7 +57,75 @@ pub struct Projectile {
     pub speed: f32,
     pub lifetime: f32,
     pub damage: f32,
-    pub from_player: bool,
+    /// 子弹所在的碰撞层和能击中的层
+    pub layers: CollisionLayers,
+    /// 距离下一次留下拖尾的剩余时间（秒）
+    pub trail_cooldown: f32,
+    /// 爆炸弹：寿命结束、命中敌人或撞墙时原地爆炸
+    pub on_death_aoe: Option<ProjectileAoe>,
+    /// 已飞行的距离，配合 falloff 计算命中时的伤害
+    pub traveled: f32,
+    pub falloff: Option<DamageFalloff>,
+    /// 发射这颗子弹的武器的吸血比例，命中和爆炸都按它回血
+    pub lifesteal: f32,
+}
+
+impl Projectile {
+    /// 按已飞行距离衰减后的伤害
+    pub fn current_damage(&self) -> f32 {
+        self.damage * self.falloff_factor()
+    }
+
+    /// 爆炸伤害和直接命中一样按飞行距离衰减；不是爆炸弹时为 None
+    pub fn current_aoe(&self) -> Option<ProjectileAoe> {
+        self.on_death_aoe.map(|aoe| ProjectileAoe { damage: aoe.damage * self.falloff_factor(), ..aoe })
+    }
+
+    fn falloff_factor(&self) -> f32 {
+        self.falloff.map_or(1.0, |falloff| falloff.factor(self.traveled))
+    }
+}
+
+/// 碰撞层：layer 是自己所在的层（位标记），mask 是能击中的层。
+/// 玩家子弹打敌人，敌人子弹打玩家；武器允许时玩家子弹还能击落敌人子弹
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollisionLayers {
+    pub layer: u8,
+    pub mask: u8,
+}
+
+impl CollisionLayers {
+    pub const PLAYER: u8 = 1 << 0;
+    pub const ENEMY: u8 = 1 << 1;
+    pub const PLAYER_PROJECTILE: u8 = 1 << 2;
+    pub const ENEMY_PROJECTILE: u8 = 1 << 3;
+
+    pub const fn player_projectile(shoots_down_projectiles: bool) -> Self {
+        let mask = if shoots_down_projectiles { Self::ENEMY | Self::ENEMY_PROJECTILE } else { Self::ENEMY };
+        Self { layer: Self::PLAYER_PROJECTILE, mask }
+    }
+
+    /// 远程敌人（Spitter）吐出的飞弹：只打玩家
+    pub const fn enemy_projectile() -> Self {
+        Self { layer: Self::ENEMY_PROJECTILE, mask: Self::PLAYER }
+    }
+
+    pub fn hits(&self, layer: u8) -> bool {
+        self.mask & layer != 0
+    }
+}
+
+/// 子弹消失时的范围伤害
+#[derive(Clone, Copy, Debug)]
+pub struct ProjectileAoe {
+    pub radius: f32,
+    pub damage: f32,
+}
+
+/// 子弹拖尾：留在原地逐渐缩小、变淡，结束后放回 `TrailPool`
+#[derive(Component)]
+pub struct ProjectileTrail {
+    pub timer: Timer,
 }
 
 #[derive(Component)]
@@ -39,25 +133,121 @@ pub struct SlashVfx {
     pub timer: Timer,
 }
 
+/// 爆炸特效：用 Gizmos 画一圈向外扩散、逐渐变淡的圆环
 #[derive(Component)]
-pub struct EnemyHpBar {
-    pub owner: Entity,
-    pub ratio: f32,
+pub struct ExplosionVfx {
+    pub pos: Vec2,
+    pub radius: f32,
+    pub color: Color,
+    pub timer: Timer,
 }
 
+/// 对某个实体造成伤害。所有玩家方的伤害都只发这个消息，
+/// 由 `apply_damage_dealt` 统一扣血、飘字、受击闪白。
+#[derive(Message, Clone, Copy, Debug)]
+pub struct DamageDealt {
+    pub target: Entity,
+    pub amount: f32,
+    /// 是否暴击：暴击飘字、顿帧和震屏都看它。目前没有暴击来源，各处都发 false
+    pub crit: bool,
+    /// 打出这次伤害的武器的吸血比例；技能、荆棘这类不是武器打出的伤害为 0
+    pub lifesteal: f32,
+}
+
+/// 敌人死亡：由 `cleanup_dead_enemies` 在销毁敌人的同一帧发出，
+/// 血条、掉落等收尾逻辑都响应这个消息，而不是逐帧比对实体集合。
+#[derive(Message, Clone, Copy, Debug)]
+pub struct EnemyDied {
+    pub entity: Entity,
+    pub position: Vec2,
+    /// 死的是精英（任务统计用）
+    pub elite: bool,
+}
+
+/// 敌人不是被打死、而是被直接移除（离玩家太远等）：只做血条之类的清理，不掉落、不计击杀
+#[derive(Message, Clone, Copy, Debug)]
+pub struct EnemyDespawned {
+    pub entity: Entity,
+}
+
+/// 飘字（伤害数字）
+#[derive(Component)]
+pub struct DamageNumber {
+    pub timer: Timer,
+}
+
+/// 受击闪烁：结束后把贴图颜色恢复为 base
+#[derive(Component)]
+pub struct HitFlash {
+    pub timer: Timer,
+    pub base: Color,
+}
+
+const DAMAGE_NUMBER_SECS: f32 = 0.7;
+const DAMAGE_NUMBER_RISE_SPEED: f32 = 40.0;
+/// 飘字池最多留存的空闲实体，超出的直接销毁，避免一次大混战后常驻一堆隐藏实体
+const MAX_POOLED_DAMAGE_NUMBERS: usize = 64;
+/// 子弹每隔多久留下一个拖尾（秒）、拖尾持续多久、起始大小
+const TRAIL_EMIT_INTERVAL: f32 = 0.02;
+const TRAIL_SECS: f32 = 0.18;
+const TRAIL_SIZE: f32 = 6.0;
+/// 拖尾池最多留存的空闲实体
+const MAX_POOLED_TRAILS: usize = 128;
+const EXPLOSION_VFX_SECS: f32 = 0.3;
+const PROJECTILE_EXPLOSION_COLOR: Color = Color::srgb(1.0, 0.7, 0.25);
+/// 血条在敌人头顶的高度、字号；视野外扩一圈，避免敌人压着屏幕边时血条闪烁
+const HP_BAR_OFFSET_Y: f32 = 24.0;
+const HP_BAR_FONT_SIZE: f32 = 12.0;
+const HP_BAR_VIEW_MARGIN: f32 = 32.0;
+/// 血条池最多留存的空闲实体
+const MAX_POOLED_HP_BARS: usize = 64;
+const HIT_FLASH_SECS: f32 = 0.1;
+
+/// 敌人头顶的血量文字；回收进池子时摘掉
+#[derive(Component)]
+pub struct EnemyHpBar;
+
 #[derive(Resource, Default)]
 pub struct EnemyHpBarMap(pub HashMap<Entity, Entity>);
 
+/// 回收的血条实体（隐藏状态）：满血或跑出画面的敌人不显示血条
+#[derive(Resource, Default)]
+pub struct HpBarPool {
+    pub free: Vec<Entity>,
+}
+
+/// 回收的子弹实体（隐藏状态，保留 Sprite/Transform），复用时同一条命令里重设位置并显示
 #[derive(Resource, Default)]
 pub struct ProjectilePool {
     pub free: Vec<Entity>,
 }
 
+impl ProjectilePool {
+    /// 去掉 Projectile 并隐藏后放回池里，避免停在原地被渲染出来
+    fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        commands.entity(entity).remove::<Projectile>().insert(Visibility::Hidden);
+        self.free.push(entity);
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct VfxPool {
     pub free: Vec<Entity>,
 }
 
+/// 回收的飘字实体（隐藏状态），重新显示时直接覆盖文字和位置
+#[derive(Resource, Default)]
+pub struct DamageNumberPool {
+    pub free: Vec<Entity>,
+}
+
+/// 回收的拖尾实体（隐藏状态）
+#[derive(Resource, Default)]
+pub struct TrailPool {
+    pub free: Vec<Entity>,
+}
+
+#[allow(clippy::collapsible_if, clippy::too_many_arguments)]
 pub fn spawn_projectile(
     commands: &mut Commands,
     pool: Option<&mut ProjectilePool>,
@@ -66,43 +256,96 @@ pub fn spawn_projectile(
     speed: f32,
     lifetime: f32,
     damage: f32,
-    from_player: bool,
+    layers: CollisionLayers,
+    on_death_aoe: Option<ProjectileAoe>,
+    falloff: Option<DamageFalloff>,
+    lifesteal: f32,
 ) {
     let forward = dir.normalize_or_zero();
     if forward == Vec2::ZERO {
         return;
     }
 
-    let mut sprite = Sprite::default();
-    sprite.color = Color::srgb(1.0, 0.2, 0.2);
-    sprite.custom_size = Some(Vec2::splat(8.0));
+    let sprite = Sprite {
+        color: Color::srgb(1.0, 0.2, 0.2),
+        custom_size: Some(Vec2::splat(8.0)),
+        ..default()
+    };
 
     if let Some(pool) = pool {
         if let Some(ent) = pool.free.pop() {
             commands.entity(ent).insert((
-                Projectile { direction: forward, speed, lifetime, damage, from_player },
+                Projectile {
+                    direction: forward,
+                    speed,
+                    lifetime,
+                    damage,
+                    layers,
+                    trail_cooldown: 0.0,
+                    on_death_aoe,
+                    traveled: 0.0,
+                    falloff,
+                    lifesteal,
+                },
                 sprite,
-                Transform::from_xyz(origin.x, origin.y, 10.0),
+                Transform::from_xyz(origin.x, origin.y, layers::PROJECTILE),
+                Visibility::Inherited,
             ));
             return;
         }
     }
 
     commands.spawn((
-        Projectile { direction: forward, speed, lifetime, damage, from_player },
+        Projectile {
+            direction: forward,
+            speed,
+            lifetime,
+            damage,
+            layers,
+            trail_cooldown: 0.0,
+            on_death_aoe,
+            traveled: 0.0,
+            falloff,
+            lifesteal,
+        },
         sprite,
-        Transform::from_xyz(origin.x, origin.y, 10.0),
+        Transform::from_xyz(origin.x, origin.y, layers::PROJECTILE),
     ));
 }
 
+/// 范围伤害：对 center 周围 radius 内的每个敌人造成伤害，返回命中的敌人数
+pub fn aoe_damage_enemies(
+    center: Vec2,
+    radius: f32,
+    damage: f32,
+    lifesteal: f32,
+    enemies: impl IntoIterator<Item = (Entity, Vec2)>,
+    dealt_tx: &mut MessageWriter<DamageDealt>,
+) -> usize {
+    let mut hits = 0;
+    for (entity, pos) in enemies {
+        if pos.distance(center) <= radius {
+            dealt_tx.write(DamageDealt { target: entity, amount: damage, crit: false, lifesteal });
+            hits += 1;
+        }
+    }
+    hits
+}
+
+/// 玩家斩击的基础伤害，局外强化和增益在调用方乘上去
+pub const SKILL_SLASH_DAMAGE: f32 = 60.0;
+
+/// 玩家斩击：对范围内、视线没被墙挡住的敌人造成伤害，返回命中的敌人数
 pub fn skill_slash(
     origin: Vec2,
     dir: Vec2,
-    enemies_q: &mut Query<(Entity, &Transform, &mut Health), With<Enemy>>,
-) {
+    damage: f32,
+    enemies_q: &Query<(Entity, &Transform), With<Enemy>>,
+    walls: &WallColliders,
+    dealt_tx: &mut MessageWriter<DamageDealt>,
+) -> usize {
     let length: f32 = 260.0;
     let width: f32 = 100.0;
-    let damage: f32 = 60.0;
     const EPS: f32 = 6.0;
 
     let forward = {
@@ -111,25 +354,38 @@ pub fn skill_slash(
     };
     let right = Vec2::new(-forward.y, forward.x);
 
-    for (_entity, tf, mut hp) in enemies_q.iter_mut() {
-        let to_target = tf.translation.truncate() - origin;
+    let mut hits = 0;
+    for (entity, tf) in enemies_q.iter() {
+        let target = tf.translation.truncate();
+        let to_target = target - origin;
         let d_forward = to_target.dot(forward);
         let d_side = to_target.dot(right);
 
-        if d_forward >= -EPS && d_forward <= length + EPS && d_side.abs() <= (width * 0.5 + EPS) {
-            hp.current -= damage;
+        if d_forward >= -EPS
+            && d_forward <= length + EPS
+            && d_side.abs() <= (width * 0.5 + EPS)
+            && walls.line_of_sight(origin, target)
+        {
+            dealt_tx.write(DamageDealt { target: entity, amount: damage, crit: false, lifesteal: 0.0 });
+            hits += 1;
         }
     }
+    hits
 }
 
-pub fn skill_slash_on_player(origin: Vec2, dir: Vec2, player_pos: Vec2, player_hp: &mut Health) {
-    let length: f32 = 160.0;
-    let width: f32 = 80.0;
+/// 敌人斩击的判定范围（预警特效按同样的尺寸画）
+pub const ENEMY_SLASH_LENGTH: f32 = 160.0;
+pub const ENEMY_SLASH_WIDTH: f32 = 80.0;
+
+/// 敌人斩击：命中玩家时返回伤害值（扣血由 PlayerDamaged 统一处理）
+pub fn skill_slash_on_player(origin: Vec2, dir: Vec2, player_pos: Vec2) -> Option<f32> {
+    let length = ENEMY_SLASH_LENGTH;
+    let width = ENEMY_SLASH_WIDTH;
     let damage: f32 = 25.0;
 
     let forward = dir.normalize_or_zero();
     if forward == Vec2::ZERO {
-        return;
+        return None;
     }
     let right = Vec2::new(-forward.y, forward.x);
 
@@ -138,11 +394,17 @@ pub fn skill_slash_on_player(origin: Vec2, dir: Vec2, player_pos: Vec2, player_h
     let d_side = to_target.dot(right);
 
     if d_forward >= 0.0 && d_forward <= length && d_side.abs() <= width * 0.5 {
-        player_hp.current -= damage;
+        return Some(damage);
     }
+    None
 }
 
-pub fn spawn_slash_vfx(commands: &mut Commands, pool: Option<&mut VfxPool>, origin: Vec2, dir: Vec2) {
+/// 斩击特效的颜色：命中 / 落空
+pub const SLASH_VFX_COLOR: Color = Color::srgba(0.9, 0.9, 0.3, 0.8);
+pub const SLASH_WHIFF_VFX_COLOR: Color = Color::srgba(0.7, 0.7, 0.75, 0.35);
+
+#[allow(clippy::collapsible_if)]
+pub fn spawn_slash_vfx(commands: &mut Commands, pool: Option<&mut VfxPool>, origin: Vec2, dir: Vec2, color: Color) {
     let forward = dir.normalize_or_zero();
     if forward == Vec2::ZERO {
         return;
@@ -151,9 +413,11 @@ pub fn spawn_slash_vfx(commands: &mut Commands, pool: Option<&mut VfxPool>, orig
     let length: f32 = 260.0;
     let width: f32 = 80.0;
 
-    let mut sprite = Sprite::default();
-    sprite.color = Color::srgba(0.9, 0.9, 0.3, 0.8);
-    sprite.custom_size = Some(Vec2::new(length, width));
+    let sprite = Sprite {
+        color,
+        custom_size: Some(Vec2::new(length, width)),
+        ..default()
+    };
 
     let center = origin + forward * (length * 0.5);
     let angle = forward.y.atan2(forward.x);
@@ -163,7 +427,7 @@ pub fn spawn_slash_vfx(commands: &mut Commands, pool: Option<&mut VfxPool>, orig
             commands.entity(ent).insert((
                 sprite,
                 Transform {
-                    translation: center.extend(15.0),
+                    translation: center.extend(layers::VFX),
                     rotation: Quat::from_rotation_z(angle),
                     ..Default::default()
                 },
@@ -176,7 +440,7 @@ pub fn spawn_slash_vfx(commands: &mut Commands, pool: Option<&mut VfxPool>, orig
     commands.spawn((
         sprite,
         Transform {
-            translation: center.extend(15.0),
+            translation: center.extend(layers::VFX),
             rotation: Quat::from_rotation_z(angle),
             ..Default::default()
         },
@@ -184,6 +448,35 @@ pub fn spawn_slash_vfx(commands: &mut Commands, pool: Option<&mut VfxPool>, orig
     ));
 }
 
+pub fn spawn_explosion_vfx(commands: &mut Commands, pos: Vec2, radius: f32, color: Color) {
+    commands.spawn(ExplosionVfx {
+        pos,
+        radius,
+        color,
+        timer: Timer::from_seconds(EXPLOSION_VFX_SECS, TimerMode::Once),
+    });
+}
+
+fn draw_explosion_vfx(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut q: Query<(Entity, &mut ExplosionVfx)>,
+    mut gizmos: Gizmos,
+) {
+    for (entity, mut vfx) in &mut q {
+        vfx.timer.tick(time.delta());
+        if vfx.timer.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let t = vfx.timer.fraction();
+        let iso = Isometry2d::from_translation(vfx.pos);
+        let color = vfx.color.with_alpha(1.0 - t);
+        gizmos.circle_2d(iso, vfx.radius * (0.3 + 0.7 * t), color);
+        gizmos.circle_2d(iso, vfx.radius, color.with_alpha(0.3 * (1.0 - t)));
+    }
+}
+
 fn update_slash_vfx(time: Res<Time>, mut commands: Commands, mut q: Query<(Entity, &mut SlashVfx)>, mut vfx_pool: ResMut<VfxPool>) {
     let dt = time.delta();
     for (entity, mut vfx) in &mut q {
@@ -195,106 +488,503 @@ fn update_slash_vfx(time: Res<Time>, mut commands: Commands, mut q: Query<(Entit
     }
 }
 
+#[allow(clippy::collapsible_if, clippy::too_many_arguments, clippy::type_complexity)]
 fn update_projectiles(
     time: Res<Time>,
+    balance: Res<BalanceConfig>,
     mut commands: Commands,
     mut proj_q: Query<(Entity, &mut Projectile, &mut Transform), With<Projectile>>,
-    mut enemies_q: Query<
-        (Entity, &Transform, &mut Health),
-        (With<Enemy>, Without<Projectile>, Without<Player>),
-    >,
-    mut player_q: Query<
-        (&Transform, &mut Health),
-        (With<Player>, Without<Projectile>, Without<Enemy>),
-    >,
+    enemies_q: Query<(Entity, &Transform), (With<Enemy>, Without<Projectile>, Without<Player>)>,
+    player_q: Query<&Transform, (With<Player>, Without<Projectile>, Without<Enemy>)>,
+    walls: Res<WallColliders>,
     mut pool: ResMut<ProjectilePool>,
+    mut trail_pool: ResMut<TrailPool>,
+    mut damaged_tx: MessageWriter<PlayerDamaged>,
+    mut dealt_tx: MessageWriter<DamageDealt>,
 ) {
     let dt = time.delta_secs();
 
     for (proj_entity, mut proj, mut tf) in &mut proj_q {
         proj.lifetime -= dt;
         if proj.lifetime <= 0.0 {
-            commands.entity(proj_entity).remove::<Projectile>();
-            pool.free.push(proj_entity);
+            recycle_projectile(&mut commands, &mut pool, &enemies_q, &mut dealt_tx, proj_entity, &proj, tf.translation.truncate());
             continue;
         }
 
+        // 拖尾留在移动前的位置，紧跟在子弹后面
+        proj.trail_cooldown -= dt;
+        if proj.trail_cooldown <= 0.0 {
+            proj.trail_cooldown = TRAIL_EMIT_INTERVAL;
+            spawn_projectile_trail(&mut commands, &mut trail_pool, tf.translation.truncate());
+        }
+
+        let prev = tf.translation.truncate();
         let delta = proj.direction * proj.speed * dt;
         tf.translation.x += delta.x;
         tf.translation.y += delta.y;
+        proj.traveled += delta.length();
+        let pos = tf.translation.truncate();
 
-        let hit_radius = 12.0;
+        // 普通子弹穿墙；爆炸弹撞墙就在墙前炸开
+        if proj.on_death_aoe.is_some() && !walls.line_of_sight(prev, pos) {
+            recycle_projectile(&mut commands, &mut pool, &enemies_q, &mut dealt_tx, proj_entity, &proj, prev);
+            continue;
+        }
+
+        let hit_radius = balance.projectile_hit_radius;
 
-        if proj.from_player {
+        if proj.layers.hits(CollisionLayers::ENEMY) {
             let mut hit = false;
-            for (_enemy_entity, enemy_tf, mut hp) in &mut enemies_q {
-                let dist = enemy_tf.translation.truncate().distance(tf.translation.truncate());
+            for (enemy_entity, enemy_tf) in &enemies_q {
+                let dist = enemy_tf.translation.truncate().distance(pos);
                 if dist <= hit_radius {
-                    hp.current -= proj.damage;
+                    dealt_tx.write(DamageDealt {
+                        target: enemy_entity,
+                        amount: proj.current_damage(),
+                        crit: false,
+                        lifesteal: proj.lifesteal,
+                    });
                     hit = true;
                 }
             }
             if hit {
-                commands.entity(proj_entity).remove::<Projectile>();
-                pool.free.push(proj_entity);
+                recycle_projectile(&mut commands, &mut pool, &enemies_q, &mut dealt_tx, proj_entity, &proj, pos);
+                continue;
             }
-        } else {
-            if let Ok((player_tf, mut hp)) = player_q.single_mut() {
+        }
+        if proj.layers.hits(CollisionLayers::PLAYER) {
+            if let Ok(player_tf) = player_q.single() {
                 let dist = player_tf.translation.truncate().distance(tf.translation.truncate());
                 if dist <= hit_radius {
-                    hp.current -= proj.damage;
-                    commands.entity(proj_entity).remove::<Projectile>();
-                    pool.free.push(proj_entity);
+                    damaged_tx.write(PlayerDamaged {
+                        from: tf.translation.truncate() - proj.direction * hit_radius,
+                        amount: proj.current_damage(),
+                    });
+                    recycle_projectile(&mut commands, &mut pool, &enemies_q, &mut dealt_tx, proj_entity, &proj, pos);
                 }
             }
         }
     }
 }
 
+/// 能击落子弹的玩家子弹碰到敌人子弹时两颗一起消失；玩家的爆炸弹照常在原地炸开
+#[allow(clippy::type_complexity)]
+fn intercept_enemy_projectiles(
+    balance: Res<BalanceConfig>,
+    mut commands: Commands,
+    proj_q: Query<(Entity, &Projectile, &Transform)>,
+    enemies_q: Query<(Entity, &Transform), (With<Enemy>, Without<Projectile>, Without<Player>)>,
+    mut pool: ResMut<ProjectilePool>,
+    mut dealt_tx: MessageWriter<DamageDealt>,
+) {
+    let hit_radius = balance.projectile_hit_radius;
+    let mut gone: Vec<Entity> = Vec::new();
+
+    for (hunter, hunter_proj, hunter_tf) in &proj_q {
+        if !hunter_proj.layers.hits(CollisionLayers::ENEMY_PROJECTILE) {
+            continue;
+        }
+        let pos = hunter_tf.translation.truncate();
+        let target = proj_q.iter().find(|(e, p, tf)| {
+            p.layers.layer & CollisionLayers::ENEMY_PROJECTILE != 0
+                && !gone.contains(e)
+                && tf.translation.truncate().distance(pos) <= hit_radius
+        });
+        let Some((target, _, _)) = target else {
+            continue;
+        };
+
+        gone.extend([hunter, target]);
+        recycle_projectile(&mut commands, &mut pool, &enemies_q, &mut dealt_tx, hunter, hunter_proj, pos);
+        pool.release(&mut commands, target);
+    }
+}
+
+/// 子弹回收前先结算爆炸
+#[allow(clippy::type_complexity)]
+fn recycle_projectile(
+    commands: &mut Commands,
+    pool: &mut ProjectilePool,
+    enemies_q: &Query<(Entity, &Transform), (With<Enemy>, Without<Projectile>, Without<Player>)>,
+    dealt_tx: &mut MessageWriter<DamageDealt>,
+    entity: Entity,
+    proj: &Projectile,
+    pos: Vec2,
+) {
+    if let Some(aoe) = proj.current_aoe() {
+        let enemies = enemies_q.iter().map(|(e, tf)| (e, tf.translation.truncate()));
+        aoe_damage_enemies(pos, aoe.radius, aoe.damage, proj.lifesteal, enemies, dealt_tx);
+        spawn_explosion_vfx(commands, pos, aoe.radius, PROJECTILE_EXPLOSION_COLOR);
+    }
+    pool.release(commands, entity);
+}
+
+/// 优先复用池里隐藏的拖尾实体，池空了才新建
+fn spawn_projectile_trail(commands: &mut Commands, pool: &mut TrailPool, pos: Vec2) {
+    let bundle = (
+        ProjectileTrail { timer: Timer::from_seconds(TRAIL_SECS, TimerMode::Once) },
+        Sprite {
+            color: Color::srgba(1.0, 0.45, 0.3, 0.6),
+            custom_size: Some(Vec2::splat(TRAIL_SIZE)),
+            ..default()
+        },
+        // 压在子弹下面一点
+        Transform::from_translation(pos.extend(layers::PROJECTILE - 0.1)),
+        Visibility::Inherited,
+    );
+
+    if let Some(ent) = pool.free.pop() {
+        commands.entity(ent).insert(bundle);
+        return;
+    }
+    commands.spawn(bundle);
+}
+
+/// 拖尾逐渐缩小变淡，结束后隐藏放回池里；池满了就直接销毁
+fn update_projectile_trails(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut pool: ResMut<TrailPool>,
+    mut q: Query<(Entity, &mut ProjectileTrail, &mut Sprite, &mut Visibility)>,
+) {
+    for (entity, mut trail, mut sprite, mut visibility) in &mut q {
+        trail.timer.tick(time.delta());
+        if trail.timer.is_finished() {
+            if pool.free.len() < MAX_POOLED_TRAILS {
+                *visibility = Visibility::Hidden;
+                commands.entity(entity).remove::<ProjectileTrail>();
+                pool.free.push(entity);
+            } else {
+                commands.entity(entity).try_despawn();
+            }
+            continue;
+        }
+        let remaining = trail.timer.fraction_remaining();
+        sprite.custom_size = Some(Vec2::splat(TRAIL_SIZE * remaining));
+        sprite.color.set_alpha(0.6 * remaining);
+    }
+}
+
+#[allow(clippy::collapsible_if, clippy::type_complexity)]
+fn apply_damage_dealt(
+    mut commands: Commands,
+    mut dealt_rx: MessageReader<DamageDealt>,
+    mut targets_q: Query<
+        (&Transform, &mut Health, Option<&mut Sprite>, Option<&mut HitFlash>, Option<&Elite>),
+        Without<Player>,
+    >,
+    mut player_q: Query<(&Transform, &mut Health), With<Player>>,
+    mut number_pool: ResMut<DamageNumberPool>,
+    palette: Res<Palette>,
+) {
+    let mut healed = 0.0;
+
+    for ev in dealt_rx.read() {
+        let Ok((tf, mut hp, sprite, flash, elite)) = targets_q.get_mut(ev.target) else {
+            continue;
+        };
+        // 同一帧里已经被打死的目标不再重复结算
+        if hp.current <= 0.0 {
+            continue;
+        }
+
+        // 坚甲精英减伤
+        let amount = ev.amount * elite.map_or(1.0, Elite::damage_taken_factor);
+        healed += amount.min(hp.current) * ev.lifesteal;
+        hp.current -= amount;
+        spawn_damage_number(&mut commands, &mut number_pool, &palette, tf.translation.truncate(), amount, ev.crit);
+
+        match (sprite, flash) {
+            (_, Some(mut flash)) => flash.timer.reset(),
+            (Some(mut sprite), None) => {
+                commands.entity(ev.target).insert(HitFlash {
+                    timer: Timer::from_seconds(HIT_FLASH_SECS, TimerMode::Once),
+                    base: sprite.color,
+                });
+                sprite.color = palette.hit_flash;
+            }
+            (None, None) => {}
+        }
+    }
+
+    // 吸血：每次伤害按打出它的武器的吸血比例回血（不超过上限），绿色飘字
+    if healed > 0.0 {
+        if let Ok((player_tf, mut player_hp)) = player_q.single_mut() {
+            let before = player_hp.current;
+            player_hp.current = (player_hp.current + healed).min(player_hp.max);
+            let gained = player_hp.current - before;
+            if gained >= 0.5 {
+                spawn_floating_text(
+                    &mut commands,
+                    &mut number_pool,
+                    player_tf.translation.truncate(),
+                    format!("+{:.0}", gained),
+                    palette.heal_number,
+                    16.0,
+                );
+            }
+        }
+    }
+}
+
+fn spawn_damage_number(
+    commands: &mut Commands,
+    pool: &mut DamageNumberPool,
+    palette: &Palette,
+    pos: Vec2,
+    amount: f32,
+    crit: bool,
+) {
+    let (color, size) = if crit {
+        (palette.crit_number, 22.0)
+    } else {
+        (palette.damage_number, 16.0)
+    };
+    spawn_floating_text(commands, pool, pos, format!("{:.0}", amount), color, size);
+}
+
+/// 优先从池里取一个隐藏的飘字实体复用，池空了才新建
+fn spawn_floating_text(
+    commands: &mut Commands,
+    pool: &mut DamageNumberPool,
+    pos: Vec2,
+    text: String,
+    color: Color,
+    size: f32,
+) {
+    let bundle = (
+        DamageNumber { timer: Timer::from_seconds(DAMAGE_NUMBER_SECS, TimerMode::Once) },
+        Text2d::new(text),
+        TextFont { font_size: size, ..default() },
+        TextColor(color),
+        Transform::from_translation((pos + Vec2::new(0.0, 20.0)).extend(layers::DAMAGE_NUMBER)),
+        Visibility::Inherited,
+    );
+
+    if let Some(ent) = pool.free.pop() {
+        commands.entity(ent).insert(bundle);
+        return;
+    }
+    commands.spawn(bundle);
+}
+
+/// 飘字结束后隐藏并放回池里；池满了就直接销毁
+fn update_damage_numbers(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut pool: ResMut<DamageNumberPool>,
+    mut q: Query<(Entity, &mut DamageNumber, &mut Transform, &mut TextColor, &mut Visibility)>,
+) {
+    for (entity, mut num, mut tf, mut color, mut visibility) in &mut q {
+        num.timer.tick(time.delta());
+        if num.timer.is_finished() {
+            if pool.free.len() < MAX_POOLED_DAMAGE_NUMBERS {
+                *visibility = Visibility::Hidden;
+                commands.entity(entity).remove::<DamageNumber>();
+                pool.free.push(entity);
+            } else {
+                commands.entity(entity).try_despawn();
+            }
+            continue;
+        }
+        tf.translation.y += DAMAGE_NUMBER_RISE_SPEED * time.delta_secs();
+        color.0.set_alpha(num.timer.fraction_remaining());
+    }
+}
+
+fn update_hit_flash(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut q: Query<(Entity, &mut HitFlash, &mut Sprite)>,
+) {
+    for (entity, mut flash, mut sprite) in &mut q {
+        flash.timer.tick(time.delta());
+        if flash.timer.is_finished() {
+            sprite.color = flash.base;
+            commands.entity(entity).remove::<HitFlash>();
+        }
+    }
+}
+
+/// 只给受过伤、且在画面内的敌人显示血条，跟在敌人头顶；其余的血条隐藏放回池里
 fn sync_enemy_hp_bars(
     mut commands: Commands,
     enemies_q: Query<(Entity, &Health, &Transform), With<Enemy>>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
     mut bar_map: ResMut<EnemyHpBarMap>,
+    mut pool: ResMut<HpBarPool>,
 ) {
-    let mut seen = HashSet::new();
+    // 拿不到相机视野时（例如无窗口测试）视为都在画面内
+    let view = camera_q
+        .single()
+        .ok()
+        .and_then(|(camera, cam_tf)| visible_world_rect(camera, cam_tf))
+        .map(|rect| rect.inflate(HP_BAR_VIEW_MARGIN));
 
     for (enemy_e, health, tf) in enemies_q.iter() {
-        if health.current <= 0.0 {
+        let pos = tf.translation.truncate();
+        let damaged = health.current > 0.0 && health.current < health.max;
+        let on_screen = view.is_none_or(|rect| rect.contains(pos));
+
+        if !(damaged && on_screen) {
+            if let Some(bar_ent) = bar_map.0.remove(&enemy_e) {
+                release_hp_bar(&mut commands, &mut pool, bar_ent);
+            }
             continue;
         }
-        seen.insert(enemy_e);
-
-        if !bar_map.0.contains_key(&enemy_e) {
-            let bar_ent = commands
-                .spawn((
-                    Text::new(format!("{:.0}/{:.0}", health.current, health.max)),
-                    EnemyHpBar { owner: enemy_e, ratio: health.current / health.max },
-                    Transform::from_translation(tf.translation + Vec3::new(-20.0, 40.0, 100.0)),
-                ))
-                .id();
 
+        let bundle = (
+            Text2d::new(format!("{:.0}/{:.0}", health.current, health.max)),
+            EnemyHpBar,
+            Transform::from_translation((pos + Vec2::Y * HP_BAR_OFFSET_Y).extend(layers::HP_BAR)),
+            Visibility::Inherited,
+        );
+        if let Some(&bar_ent) = bar_map.0.get(&enemy_e) {
+            commands.entity(bar_ent).insert(bundle);
+        } else if let Some(bar_ent) = pool.free.pop() {
+            commands.entity(bar_ent).insert(bundle);
             bar_map.0.insert(enemy_e, bar_ent);
         } else {
-            if let Some(&bar_ent) = bar_map.0.get(&enemy_e) {
-                commands.entity(bar_ent).insert(Text::new(format!("{:.0}/{:.0}", health.current, health.max)));
-            }
+            let bar_ent = commands.spawn((bundle, TextFont { font_size: HP_BAR_FONT_SIZE, ..default() })).id();
+            bar_map.0.insert(enemy_e, bar_ent);
         }
     }
+}
 
-    let to_remove: Vec<(Entity, Entity)> = bar_map
-        .0
-        .iter()
-        .filter(|(enemy, _)| !seen.contains(enemy))
-        .map(|(enemy, bar)| (*enemy, *bar))
-        .collect();
-
-    for (enemy, bar_ent) in to_remove {
-        bar_map.0.remove(&enemy);
+/// 隐藏血条放回池里；池满了就直接销毁
+fn release_hp_bar(commands: &mut Commands, pool: &mut HpBarPool, bar_ent: Entity) {
+    if pool.free.len() < MAX_POOLED_HP_BARS {
+        commands.entity(bar_ent).remove::<EnemyHpBar>().insert(Visibility::Hidden);
+        pool.free.push(bar_ent);
+    } else {
         commands.entity(bar_ent).try_despawn();
     }
 }
 
-fn process_enemy_death(mut bar_map: ResMut<EnemyHpBarMap>, enemies_q: Query<Entity, With<Enemy>>) {
-    let existing: HashSet<Entity> = enemies_q.iter().collect();
-    bar_map.0.retain(|enemy, _bar| existing.contains(enemy));
+fn remove_hp_bars_of_dead_enemies(
+    mut commands: Commands,
+    mut died_rx: MessageReader<EnemyDied>,
+    mut despawned_rx: MessageReader<EnemyDespawned>,
+    mut bar_map: ResMut<EnemyHpBarMap>,
+    mut pool: ResMut<HpBarPool>,
+) {
+    let removed = died_rx.read().map(|ev| ev.entity).chain(despawned_rx.read().map(|ev| ev.entity));
+    for entity in removed {
+        if let Some(bar_ent) = bar_map.0.remove(&entity) {
+            release_hp_bar(&mut commands, &mut pool, bar_ent);
+        }
+    }
+}
+
+/// 敌人、子弹、挥砍特效、伤害飘字、血条、拖尾、爆炸特效全部销毁，池子和血条表一起清空
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn cleanup_combat_state(
+    mut commands: Commands,
+    live_q: Query<Entity, Or<(With<Enemy>, With<Projectile>, With<SlashVfx>, With<DamageNumber>)>>,
+    mut projectile_pool: ResMut<ProjectilePool>,
+    mut vfx_pool: ResMut<VfxPool>,
+    mut number_pool: ResMut<DamageNumberPool>,
+    mut bar_map: ResMut<EnemyHpBarMap>,
+    mut bar_pool: ResMut<HpBarPool>,
+    mut trail_pool: ResMut<TrailPool>,
+    vfx_q: Query<Entity, Or<(With<ProjectileTrail>, With<ExplosionVfx>)>>,
+) {
+    let pooled = projectile_pool
+        .free
+        .drain(..)
+        .chain(vfx_pool.free.drain(..))
+        .chain(number_pool.free.drain(..))
+        .chain(trail_pool.free.drain(..))
+        .chain(bar_pool.free.drain(..))
+        .chain(bar_map.0.drain().map(|(_, bar)| bar));
+    for e in live_q.iter().chain(vfx_q.iter()).chain(pooled) {
+        commands.entity(e).try_despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    fn spawn_many(app: &mut App, count: usize) {
+        app.world_mut()
+            .run_system_once(move |mut commands: Commands, mut pool: ResMut<DamageNumberPool>| {
+                for i in 0..count {
+                    spawn_damage_number(&mut commands, &mut pool, &Palette::default(), Vec2::splat(i as f32), 10.0, i % 5 == 0);
+                }
+            })
+            .unwrap();
+    }
+
+    fn live_numbers(app: &mut App) -> usize {
+        app.world_mut().query::<&DamageNumber>().iter(app.world()).count()
+    }
+
+    fn all_number_entities(app: &mut App) -> usize {
+        app.world_mut().query_filtered::<(), With<Text2d>>().iter(app.world()).count()
+    }
+
+    #[test]
+    fn expired_damage_numbers_stay_bounded_in_pool() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(0.25)))
+            .init_resource::<DamageNumberPool>()
+            .add_systems(Update, update_damage_numbers);
+        app.update();
+
+        spawn_many(&mut app, 500);
+        assert_eq!(live_numbers(&mut app), 500);
+
+        // 0.25 秒一帧，几帧之后全部到期
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(live_numbers(&mut app), 0);
+        let pooled = app.world().resource::<DamageNumberPool>().free.len();
+        assert_eq!(pooled, MAX_POOLED_DAMAGE_NUMBERS);
+        assert_eq!(all_number_entities(&mut app), MAX_POOLED_DAMAGE_NUMBERS);
+
+        // 再来一小波：全部从池里取，不新建实体
+        spawn_many(&mut app, 20);
+        assert_eq!(live_numbers(&mut app), 20);
+        assert_eq!(all_number_entities(&mut app), MAX_POOLED_DAMAGE_NUMBERS);
+        assert_eq!(app.world().resource::<DamageNumberPool>().free.len(), MAX_POOLED_DAMAGE_NUMBERS - 20);
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<DamageNumberPool>().free.len(), MAX_POOLED_DAMAGE_NUMBERS);
+        assert_eq!(all_number_entities(&mut app), MAX_POOLED_DAMAGE_NUMBERS);
+    }
+
+    #[test]
+    fn projectile_damage_falls_off_with_distance_down_to_minimum() {
+        let mut proj = Projectile {
+            direction: Vec2::X,
+            speed: 500.0,
+            lifetime: 2.0,
+            damage: 20.0,
+            layers: CollisionLayers::player_projectile(false),
+            trail_cooldown: 0.0,
+            on_death_aoe: Some(ProjectileAoe { radius: 50.0, damage: 8.0 }),
+            traveled: 0.0,
+            falloff: Some(DamageFalloff { start: 100.0, end: 300.0, min_factor: 0.5 }),
+            lifesteal: 0.0,
+        };
+        assert_eq!(proj.current_damage(), 20.0);
+        proj.traveled = 200.0;
+        assert!((proj.current_damage() - 15.0).abs() < 1e-4);
+        proj.traveled = 1000.0;
+        assert!((proj.current_damage() - 10.0).abs() < 1e-4);
+        // 爆炸伤害跟着一起衰减，半径不变
+        let aoe = proj.current_aoe().unwrap();
+        assert!((aoe.damage - 4.0).abs() < 1e-4);
+        assert_eq!(aoe.radius, 50.0);
+    }
 }
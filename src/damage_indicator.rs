@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::health::PlayerDamaged;
+use crate::movement::Player;
+use crate::palette::Palette;
+use crate::state::GameState;
+
+/// 指示条显示时长（秒）
+const INDICATOR_SECS: f32 = 0.6;
+/// 指示条长边 / 短边（像素）
+const INDICATOR_LENGTH: f32 = 90.0;
+const INDICATOR_THICKNESS: f32 = 10.0;
+/// 距屏幕边缘的留白
+const EDGE_MARGIN: f32 = 12.0;
+
+/// 屏幕边缘的红色受击方向提示
+#[derive(Component)]
+struct DamageIndicator {
+    timer: Timer,
+}
+
+pub struct DamageIndicatorPlugin;
+
+impl Plugin for DamageIndicatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            spawn_damage_indicators.run_if(in_state(GameState::InGame)),
+        )
+        .add_systems(Update, fade_damage_indicators.run_if(in_state(GameState::InGame)))
+        .add_systems(OnExit(GameState::InGame), cleanup_damage_indicators);
+    }
+}
+
+fn spawn_damage_indicators(
+    mut commands: Commands,
+    mut damaged_rx: MessageReader<PlayerDamaged>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    player_q: Query<&Transform, With<Player>>,
+    palette: Res<Palette>,
+) {
+    let Ok(player_tf) = player_q.single() else {
+        damaged_rx.clear();
+        return;
+    };
+    let ppos = player_tf.translation.truncate();
+
+    let half = Vec2::new(window.width(), window.height()) * 0.5;
+
+    for ev in damaged_rx.read() {
+        let dir = (ev.from - ppos).normalize_or_zero();
+        if dir == Vec2::ZERO {
+            continue;
+        }
+
+        // 世界坐标 y 向上，UI 坐标 y 向下
+        let screen_dir = Vec2::new(dir.x, -dir.y);
+        let reach = half - Vec2::splat(EDGE_MARGIN);
+        let tx = if screen_dir.x != 0.0 { reach.x / screen_dir.x.abs() } else { f32::MAX };
+        let ty = if screen_dir.y != 0.0 { reach.y / screen_dir.y.abs() } else { f32::MAX };
+        let edge_point = half + screen_dir * tx.min(ty);
+
+        // 撞到左右边时竖着放，撞到上下边时横着放
+        let size = if tx < ty {
+            Vec2::new(INDICATOR_THICKNESS, INDICATOR_LENGTH)
+        } else {
+            Vec2::new(INDICATOR_LENGTH, INDICATOR_THICKNESS)
+        };
+        let top_left = (edge_point - size * 0.5).clamp(Vec2::ZERO, (half * 2.0 - size).max(Vec2::ZERO));
+
+        commands.spawn((
+            DamageIndicator {
+                timer: Timer::from_seconds(INDICATOR_SECS, TimerMode::Once),
+            },
+            GlobalZIndex(150),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(top_left.x),
+                top: Val::Px(top_left.y),
+                width: Val::Px(size.x),
+                height: Val::Px(size.y),
+                ..default()
+            },
+            BackgroundColor(palette.damage_indicator),
+        ));
+    }
+}
+
+fn fade_damage_indicators(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut q: Query<(Entity, &mut DamageIndicator, &mut BackgroundColor)>,
+) {
+    for (entity, mut indicator, mut bg) in &mut q {
+        indicator.timer.tick(time.delta());
+        if indicator.timer.is_finished() {
+            commands.entity(entity).try_despawn();
+            continue;
+        }
+        bg.0.set_alpha(0.8 * indicator.timer.fraction_remaining());
+    }
+}
+
+fn cleanup_damage_indicators(mut commands: Commands, q: Query<Entity, With<DamageIndicator>>) {
+    for e in &q {
+        commands.entity(e).despawn();
+    }
+}
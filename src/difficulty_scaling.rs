@@ -0,0 +1,108 @@
+// src/difficulty_scaling.rs
+//! 随时间上升的威胁等级：与刷怪波次无关，每存活 `THREAT_LEVEL_SECS` 秒升一级。
+//! 等级越高刷怪越快、敌人血量和接触伤害越高；刷怪系统读取 `DifficultyScaling`，HUD 左上角显示当前等级。
+
+use bevy::prelude::*;
+
+use crate::i18n::tr;
+use crate::run_stats::RunStats;
+use crate::state::GameState;
+use crate::ui::types::GameSettings;
+
+/// 每隔多少秒威胁等级 +1
+const THREAT_LEVEL_SECS: f32 = 45.0;
+/// 每级刷怪间隔乘上这个系数，最低不低于 MIN_SPAWN_INTERVAL_FACTOR
+const SPAWN_INTERVAL_STEP: f32 = 0.9;
+const MIN_SPAWN_INTERVAL_FACTOR: f32 = 0.35;
+/// 每级敌人血量 / 伤害增加的比例
+const STAT_MULT_PER_LEVEL: f32 = 0.12;
+
+/// 当前威胁等级，从 0 开始；回到标题时清零
+#[derive(Resource, Default, Clone, Copy, Debug)]
+pub struct DifficultyScaling {
+    pub level: u32,
+}
+
+impl DifficultyScaling {
+    pub fn from_run_time(secs: f32) -> Self {
+        Self { level: (secs.max(0.0) / THREAT_LEVEL_SECS) as u32 }
+    }
+
+    /// 刷怪间隔倍率（越小刷得越快）
+    pub fn spawn_interval_factor(&self) -> f32 {
+        SPAWN_INTERVAL_STEP.powi(self.level as i32).max(MIN_SPAWN_INTERVAL_FACTOR)
+    }
+
+    /// 敌人血量和接触伤害倍率
+    pub fn enemy_stat_mult(&self) -> f32 {
+        1.0 + STAT_MULT_PER_LEVEL * self.level as f32
+    }
+}
+
+#[derive(Component)]
+struct ThreatLevelText;
+
+pub struct DifficultyScalingPlugin;
+
+impl Plugin for DifficultyScalingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DifficultyScaling>()
+            .add_systems(OnEnter(GameState::InGame), setup_threat_hud)
+            .add_systems(OnExit(GameState::InGame), cleanup_threat_hud)
+            .add_systems(
+                Update,
+                (update_difficulty_scaling, update_threat_hud).chain().run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnEnter(GameState::MainMenu), reset_difficulty_scaling);
+    }
+}
+
+fn update_difficulty_scaling(stats: Res<RunStats>, mut scaling: ResMut<DifficultyScaling>) {
+    let next = DifficultyScaling::from_run_time(stats.time);
+    if next.level != scaling.level {
+        *scaling = next;
+    }
+}
+
+fn reset_difficulty_scaling(mut scaling: ResMut<DifficultyScaling>) {
+    *scaling = DifficultyScaling::default();
+}
+
+/// 放在血条下方
+fn setup_threat_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        ThreatLevelText,
+        Text::new(""),
+        TextFont {
+            font: asset_server.load("fonts/YuFanLixing.otf"),
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.6, 0.3)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(16.0),
+            top: Val::Px(60.0),
+            ..default()
+        },
+    ));
+}
+
+fn update_threat_hud(
+    scaling: Res<DifficultyScaling>,
+    settings: Res<GameSettings>,
+    mut q: Query<&mut Text, With<ThreatLevelText>>,
+) {
+    for mut t in &mut q {
+        let label = format!("{} {}", tr(settings.language, "hud.threat_level"), scaling.level + 1);
+        if t.0 != label {
+            t.0 = label;
+        }
+    }
+}
+
+fn cleanup_threat_hud(mut commands: Commands, q: Query<Entity, With<ThreatLevelText>>) {
+    for e in &q {
+        commands.entity(e).despawn();
+    }
+}
@@ -0,0 +1,137 @@
+// src/elite.rs
+//! 精英词缀：刷怪时一小部分敌人带上一到两个词缀，贴图按词缀染色、体型稍大、血更厚。
+//! 迅捷 = 移动更快；坚甲 = 受到的伤害打折；爆裂 = 死亡时原地爆炸，炸到范围内的玩家。
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::combat::cleanup_dead_enemies;
+use crate::combat_core::{spawn_explosion_vfx, CombatSet};
+use crate::health::PlayerDamaged;
+use crate::movement::Player;
+use crate::palette::Palette;
+
+/// 精英的血量倍率、体型倍率
+pub const ELITE_HP_FACTOR: f32 = 2.0;
+pub const ELITE_SIZE_FACTOR: f32 = 1.25;
+/// 已经是精英时再多带一个词缀的概率
+const SECOND_MOD_CHANCE: f64 = 0.2;
+const SWIFT_SPEED_FACTOR: f32 = 1.6;
+/// 坚甲受到的伤害倍率
+const ARMORED_DAMAGE_TAKEN: f32 = 0.6;
+/// 爆裂：爆炸半径，伤害 = 该敌人接触伤害 × 倍率
+const EXPLOSION_RADIUS: f32 = 70.0;
+const EXPLOSION_DAMAGE_MULT: f32 = 2.0;
+const EXPLOSION_COLOR: Color = Color::srgb(1.0, 0.5, 0.2);
+/// 玩家身体的大致半径，爆炸贴到身体边缘就算命中
+const PLAYER_BODY_RADIUS: f32 = 8.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EliteMod {
+    /// 迅捷
+    Swift,
+    /// 坚甲
+    Armored,
+    /// 爆裂
+    Explosive,
+}
+
+impl EliteMod {
+    pub const ALL: [EliteMod; 3] = [EliteMod::Swift, EliteMod::Armored, EliteMod::Explosive];
+
+    fn tint(self, palette: &Palette) -> Color {
+        match self {
+            EliteMod::Swift => palette.elite_swift,
+            EliteMod::Armored => palette.elite_armored,
+            EliteMod::Explosive => palette.elite_explosive,
+        }
+    }
+}
+
+/// 敌人身上的精英词缀（不重复）
+#[derive(Component, Clone, Debug)]
+pub struct Elite(pub Vec<EliteMod>);
+
+impl Elite {
+    /// 按概率掷词缀；没掷中返回 None
+    pub fn roll(rng: &mut impl Rng, chance: f32) -> Option<Self> {
+        if !rng.gen_bool(chance.clamp(0.0, 1.0) as f64) {
+            return None;
+        }
+        let first = EliteMod::ALL[rng.gen_range(0..EliteMod::ALL.len())];
+        let mut mods = vec![first];
+        if rng.gen_bool(SECOND_MOD_CHANCE) {
+            let second = EliteMod::ALL[rng.gen_range(0..EliteMod::ALL.len())];
+            if second != first {
+                mods.push(second);
+            }
+        }
+        Some(Self(mods))
+    }
+
+    pub fn has(&self, m: EliteMod) -> bool {
+        self.0.contains(&m)
+    }
+
+    pub fn speed_factor(&self) -> f32 {
+        if self.has(EliteMod::Swift) { SWIFT_SPEED_FACTOR } else { 1.0 }
+    }
+
+    /// 受到伤害的倍率
+    pub fn damage_taken_factor(&self) -> f32 {
+        if self.has(EliteMod::Armored) { ARMORED_DAMAGE_TAKEN } else { 1.0 }
+    }
+
+    /// 贴图染色：多个词缀时取平均
+    pub fn tint(&self, palette: &Palette) -> Color {
+        let sum = self
+            .0
+            .iter()
+            .map(|m| m.tint(palette).to_linear().to_vec3())
+            .fold(Vec3::ZERO, |acc, c| acc + c);
+        Color::LinearRgba(LinearRgba::from_vec3(sum / self.0.len().max(1) as f32))
+    }
+}
+
+/// 爆裂精英死亡：由 `cleanup_dead_enemies` 在销毁敌人的同一帧发出
+#[derive(Message, Clone, Copy, Debug)]
+pub struct EliteExploded {
+    pub position: Vec2,
+    pub damage: f32,
+}
+
+impl EliteExploded {
+    pub fn new(position: Vec2, contact_damage: f32) -> Self {
+        Self { position, damage: contact_damage * EXPLOSION_DAMAGE_MULT }
+    }
+}
+
+pub struct ElitePlugin;
+
+impl Plugin for ElitePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<EliteExploded>().add_systems(
+            Update,
+            resolve_elite_explosions.after(cleanup_dead_enemies).in_set(CombatSet),
+        );
+    }
+}
+
+#[allow(clippy::collapsible_if)]
+fn resolve_elite_explosions(
+    mut commands: Commands,
+    mut exploded_rx: MessageReader<EliteExploded>,
+    mut damaged_tx: MessageWriter<PlayerDamaged>,
+    player_q: Query<&Transform, With<Player>>,
+) {
+    let player_pos = player_q.single().ok().map(|tf| tf.translation.truncate());
+
+    for ev in exploded_rx.read() {
+        spawn_explosion_vfx(&mut commands, ev.position, EXPLOSION_RADIUS, EXPLOSION_COLOR);
+        if let Some(ppos) = player_pos {
+            if ppos.distance(ev.position) <= EXPLOSION_RADIUS + PLAYER_BODY_RADIUS {
+                damaged_tx.write(PlayerDamaged { from: ev.position, amount: ev.damage });
+            }
+        }
+    }
+}
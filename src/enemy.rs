@@ -1,8 +1,25 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use bevy::prelude::*;
+use rand::Rng;
 
-use crate::health::Health;
-use crate::movement::Player;
-use crate::state::GameState;
+use crate::balance::BalanceConfig;
+use crate::combat_core::{DamageDealt, EnemyDespawned};
+use crate::difficulty_scaling::DifficultyScaling;
+use crate::elite::{Elite, ELITE_HP_FACTOR, ELITE_SIZE_FACTOR};
+use crate::enemy_combat::EnemyAttackCooldown;
+use crate::enemy_db::{EnemyDatabase, MAX_SIZE_FACTOR};
+use crate::health::{Health, PlayerDamaged, Thorns};
+use crate::layers;
+use crate::ldtk_collision::WallColliders;
+use crate::movement::{apply_player_movement, move_with_walls, Player, PlayerCamera, PlayerDash, PlayerHitbox};
+use crate::palette::Palette;
+use crate::run_stats::RunStats;
+use crate::sfx::{PlaySfx, Sfx};
+use crate::sprite_animation::{SpriteSheetConfig, WalkCycle};
+use crate::state::{window_has_focus, GameState};
+use crate::ui::types::GameSettings;
 
 #[derive(Component)]
 pub struct Enemy;
@@ -13,13 +30,90 @@ pub struct EnemySpeed(pub f32);
 #[derive(Component)]
 pub struct EnemyDamage(pub f32);
 
+/// 敌人的接触判定半径（按贴图尺寸计算）
+#[derive(Component)]
+pub struct EnemyHitbox {
+    pub radius: f32,
+}
+
+/// 接触伤害的冷却，避免贴身时每帧都扣血。间隔由敌人种类决定
+#[derive(Component)]
+pub struct ContactCooldown {
+    /// 剩余冷却（秒）
+    pub remaining: f32,
+    /// 每次接触伤害后重置到的间隔（秒）
+    pub interval: f32,
+}
+
+impl ContactCooldown {
+    pub fn new(interval: f32) -> Self {
+        Self { remaining: 0.0, interval }
+    }
+}
+
+/// 敌人的行走动画：位置有变化就播放行走循环，贴图左右翻转朝向玩家
+#[derive(Component, Debug)]
+pub struct EnemyAnimation {
+    walk: WalkCycle,
+    /// 上一帧的位置，用来判断这一帧有没有移动（包括被击退、被推开）
+    last_pos: Option<Vec2>,
+}
+
+impl Default for EnemyAnimation {
+    fn default() -> Self {
+        Self { walk: WalkCycle::new(0.15), last_pos: None }
+    }
+}
+
+const ENEMY_SIZE: f32 = 28.0;
+/// 敌人之间的分离半径：小于这个距离就互相推开
+const SEPARATION_RADIUS: f32 = ENEMY_SIZE * 1.1;
+/// 分离力相对追击速度的权重
+const SEPARATION_WEIGHT: f32 = 1.2;
+/// 每个敌人最多参考多少个邻居，避免扎堆时开销暴涨
+const MAX_SEPARATION_NEIGHBORS: usize = 8;
+/// 刷怪预警时长（秒）：预警圈先出现，结束时敌人才真正落地
+const SPAWN_WARNING_SECS: f32 = 0.5;
+/// 预警圈的半径和脉动频率（弧度/秒）
+const SPAWN_WARNING_RADIUS: f32 = ENEMY_SIZE * 0.7;
+const SPAWN_WARNING_PULSE_SPEED: f32 = 18.0;
+/// 刷怪点离屏幕边缘往里缩的距离范围，保证预警圈落在画面内
+const SPAWN_EDGE_INSET_MIN: f32 = ENEMY_SIZE * 0.5;
+const SPAWN_EDGE_INSET_MAX: f32 = ENEMY_SIZE * 2.0;
+/// 刷怪点离玩家的最小距离，以及找不到合法位置时的重试次数（都失败就放弃这个刷怪点）
+const SPAWN_MIN_PLAYER_DISTANCE: f32 = 120.0;
+const SPAWN_POSITION_ATTEMPTS: u32 = 6;
+/// 离玩家这么近的刷怪才有声音提示
+const SPAWN_CUE_RADIUS: f32 = 900.0;
+/// 玩家和敌人身体重叠时，玩家承担的那部分推开距离（其余推敌人）
+const BODY_PUSH_PLAYER_SHARE: f32 = 0.5;
+/// 每秒最多把玩家推开多远，避免被一群敌人瞬间挤飞或者黏住抖动
+const MAX_BODY_PUSH_SPEED: f32 = 240.0;
+/// 每帧位移小于这个值（像素）视为站着不动
+const ENEMY_MOVING_EPSILON: f32 = 0.01;
+
+/// 一个敌人落地时的属性（刷怪时按难度和批次算好）
+#[derive(Clone, Copy)]
+struct EnemySpawnStats {
+    speed: f32,
+    damage: f32,
+    hp: f32,
+}
+
+/// 即将刷出的敌人：先在刷怪点显示预警，计时结束后再生成
+#[derive(Component)]
+struct PendingSpawn {
+    pos: Vec2,
+    timer: Timer,
+    stats: EnemySpawnStats,
+}
+
 #[derive(Resource)]
-struct EnemySpawnTimer(pub Timer);
+pub(crate) struct EnemySpawnTimer(pub Timer);
 
 impl Default for EnemySpawnTimer {
     fn default() -> Self {
-        // 你也可以把 1.0 改成你想要的刷怪间隔（秒）
-        Self(Timer::from_seconds(1.0, TimerMode::Repeating))
+        Self(Timer::from_seconds(BalanceConfig::default().spawn_interval_secs, TimerMode::Repeating))
     }
 }
 
@@ -27,83 +121,499 @@ pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<EnemySpawnTimer>().add_systems(
-            Update,
-            (
-                spawn_enemies_periodically.run_if(in_state(GameState::InGame)),
-                move_enemies_towards_player.run_if(in_state(GameState::InGame)),
-                damage_player_on_contact.run_if(in_state(GameState::InGame)),
-            ),
-        );
+        app.init_resource::<EnemySpawnTimer>()
+            .init_resource::<EnemyDatabase>()
+            .add_systems(
+                Update,
+                (
+                    spawn_enemies_periodically.run_if(in_state(GameState::InGame).and(window_has_focus)),
+                    (resolve_pending_spawns, draw_spawn_warnings).chain().run_if(in_state(GameState::InGame)),
+                    move_enemies_towards_player.run_if(in_state(GameState::InGame)),
+                    despawn_far_enemies.run_if(in_state(GameState::InGame)),
+                    animate_enemies
+                        .after(push_apart_player_and_enemies)
+                        .run_if(in_state(GameState::InGame)),
+                    push_apart_player_and_enemies
+                        .after(move_enemies_towards_player)
+                        .after(apply_player_movement)
+                        .run_if(in_state(GameState::InGame)),
+                    // 接触伤害要在推开之前判定，否则贴身的敌人永远碰不到玩家
+                    damage_player_on_contact
+                        .after(move_enemies_towards_player)
+                        .before(push_apart_player_and_enemies)
+                        .run_if(in_state(GameState::InGame)),
+                ),
+            )
+            .add_systems(OnEnter(GameState::MainMenu), cleanup_pending_spawns);
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_enemies_periodically(
     mut commands: Commands,
-    time: Res<Time>,
+    time: Res<Time<Virtual>>,
     mut timer: ResMut<EnemySpawnTimer>,
     player_q: Query<&Transform, With<Player>>,
-    asset_server: Res<AssetServer>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    settings: Res<GameSettings>,
+    balance: Res<BalanceConfig>,
+    scaling: Res<DifficultyScaling>,
+    walls: Res<WallColliders>,
+    mut stats: ResMut<RunStats>,
 ) {
     let Ok(player_tf) = player_q.single() else { return; };
     let ppos = player_tf.translation.truncate();
 
+    // 难度和威胁等级共同决定刷怪间隔、血量和接触伤害
+    let difficulty = settings.difficulty;
+    let interval = Duration::from_secs_f32(
+        balance.spawn_interval_secs * difficulty.spawn_interval_factor() * scaling.spawn_interval_factor(),
+    );
+    if timer.0.duration() != interval {
+        timer.0.set_duration(interval);
+    }
+    let stat_mult = scaling.enemy_stat_mult();
+    let hp = balance.enemy_base_hp * difficulty.enemy_hp_factor() * stat_mult;
+
     timer.0.tick(time.delta());
     if !timer.0.just_finished() {
         return;
     }
 
-    // 每次刷 3 个，围着玩家一圈
-    for i in 0..1 {
-        let ang = (i as f32) * std::f32::consts::TAU / 1.0;
-        let offset = Vec2::new(ang.cos(), ang.sin()) * 200.0;
-        let pos = ppos + offset;
+    // 每次刷 spawn_batch 个，沿屏幕边缘均匀分布（起点随机）；拿不到相机视野时退回围着玩家一圈。
+    // 落在墙里或离玩家太近的点会在附近重新取几次，仍不合法就跳过。
+    // 先放预警，由 resolve_pending_spawns 真正生成
+    stats.waves += 1;
+    let view = camera_q.single().ok().and_then(|(camera, cam_tf)| visible_world_rect(camera, cam_tf));
+    let mut rng = rand::thread_rng();
+    let start: f32 = rng.gen_range(0.0..1.0);
+    let batch = balance.spawn_batch.max(1);
+    let spread = 0.5 / batch as f32;
+    for i in 0..batch {
+        let base_t = start + i as f32 / batch as f32;
+        let pos = (0..SPAWN_POSITION_ATTEMPTS).find_map(|attempt| {
+            // 第一次用均匀分布的位置，之后在它附近随机挪动
+            let t = if attempt == 0 { base_t } else { base_t + rng.gen_range(-spread..spread) }.rem_euclid(1.0);
+            let pos = match view {
+                Some(rect) => {
+                    let inset = rng.gen_range(SPAWN_EDGE_INSET_MIN..SPAWN_EDGE_INSET_MAX);
+                    point_on_rect_edge(rect.inflate(-inset), t)
+                }
+                None => ppos + Vec2::from_angle(t * std::f32::consts::TAU) * balance.spawn_radius,
+            };
+            is_valid_spawn_pos(pos, ppos, &walls).then_some(pos)
+        });
+        let Some(pos) = pos else { continue; };
+
+        commands.spawn(PendingSpawn {
+            pos,
+            timer: Timer::from_seconds(SPAWN_WARNING_SECS, TimerMode::Once),
+            stats: EnemySpawnStats {
+                speed: balance.enemy_base_speed + (i as f32) * 8.0,
+                damage: (balance.enemy_base_damage + (i as f32) * 1.5) * difficulty.enemy_damage_factor() * stat_mult,
+                hp,
+            },
+        });
+    }
+}
+
+/// 刷怪点不能和墙重叠（按最大种类的精英体型算），也不能离玩家太近
+fn is_valid_spawn_pos(pos: Vec2, player_pos: Vec2, walls: &WallColliders) -> bool {
+    let radius = ENEMY_SIZE * MAX_SIZE_FACTOR * ELITE_SIZE_FACTOR * 0.5;
+    pos.distance(player_pos) >= SPAWN_MIN_PLAYER_DISTANCE
+        && !walls.aabbs.iter().any(|(center, half)| circle_overlaps_aabb(pos, radius, *center, *half))
+}
+
+/// 相机当前能看到的世界范围
+pub(crate) fn visible_world_rect(camera: &Camera, cam_tf: &GlobalTransform) -> Option<Rect> {
+    let size = camera.logical_viewport_size()?;
+    let a = camera.viewport_to_world_2d(cam_tf, Vec2::ZERO).ok()?;
+    let b = camera.viewport_to_world_2d(cam_tf, size).ok()?;
+    Some(Rect::from_corners(a, b))
+}
+
+/// 沿矩形周长取点，t ∈ [0, 1) 从左下角开始逆时针走一圈
+fn point_on_rect_edge(rect: Rect, t: f32) -> Vec2 {
+    let size = rect.size();
+    let mut d = t.rem_euclid(1.0) * 2.0 * (size.x + size.y);
+
+    if d < size.x {
+        return Vec2::new(rect.min.x + d, rect.min.y);
+    }
+    d -= size.x;
+    if d < size.y {
+        return Vec2::new(rect.max.x, rect.min.y + d);
+    }
+    d -= size.y;
+    if d < size.x {
+        return Vec2::new(rect.max.x - d, rect.max.y);
+    }
+    d -= size.x;
+    Vec2::new(rect.min.x, rect.max.y - d.min(size.y))
+}
+
+/// 预警结束的刷怪点生成敌人。附近屏幕外刷出的敌人播一声提示，精英只要在附近就播更急促的提示
+#[allow(clippy::too_many_arguments)]
+fn resolve_pending_spawns(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    asset_server: Res<AssetServer>,
+    balance: Res<BalanceConfig>,
+    enemy_db: Res<EnemyDatabase>,
+    palette: Res<Palette>,
+    mut pending_q: Query<(Entity, &mut PendingSpawn)>,
+    player_q: Query<&Transform, With<Player>>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    mut sfx_tx: MessageWriter<PlaySfx>,
+) {
+    let ppos = player_q.single().ok().map(|tf| tf.translation.truncate());
+    let view = camera_q.single().ok().and_then(|(camera, cam_tf)| visible_world_rect(camera, cam_tf));
 
-        let texture: Handle<Image> = asset_server.load("enemy.png");
-        let mut sprite = Sprite::from_image(texture);
-        sprite.custom_size = Some(Vec2::splat(28.0));
+    for (entity, mut pending) in &mut pending_q {
+        pending.timer.tick(time.delta());
+        if !pending.timer.is_finished() {
+            continue;
+        }
+        commands.entity(entity).despawn();
+        let elite = spawn_enemy(&mut commands, &asset_server, &balance, &enemy_db, &palette, pending.pos, pending.stats);
 
-        commands.spawn((
-            sprite,
-            Transform::from_translation(pos.extend(10.0)),
-            Enemy,
-            EnemySpeed(60.0 + (i as f32) * 8.0),
-            EnemyDamage(8.0 + (i as f32) * 1.5),
-            Health { current: 40.0, max: 40.0 },
-        ));
+        let near = ppos.is_some_and(|p| p.distance(pending.pos) <= SPAWN_CUE_RADIUS);
+        let off_screen = view.is_none_or(|rect| !rect.contains(pending.pos));
+        if near && elite {
+            sfx_tx.write(PlaySfx::new(Sfx::EliteSpawn));
+        } else if near && off_screen {
+            sfx_tx.write(PlaySfx::new(Sfx::EnemySpawn));
+        }
     }
 }
 
+/// 按权重抽一种敌人并生成，种类倍率叠在难度属性上，精英再叠一层。返回生成的是否是精英
+fn spawn_enemy(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    balance: &BalanceConfig,
+    enemy_db: &EnemyDatabase,
+    palette: &Palette,
+    pos: Vec2,
+    stats: EnemySpawnStats,
+) -> bool {
+    let mut rng = rand::thread_rng();
+    let kind = enemy_db.roll(&mut rng);
+    let def = enemy_db.get(kind);
+    let base_size = ENEMY_SIZE * def.size_factor;
+    let base_speed = stats.speed * def.speed_factor;
+    let base_hp = stats.hp * def.hp_factor;
+
+    let elite = Elite::roll(&mut rng, balance.elite_chance);
+    let (size, speed, hp) = match &elite {
+        Some(elite) => (base_size * ELITE_SIZE_FACTOR, base_speed * elite.speed_factor(), base_hp * ELITE_HP_FACTOR),
+        None => (base_size, base_speed, base_hp),
+    };
+
+    let texture: Handle<Image> = asset_server.load("enemy.png");
+    let mut sprite = Sprite::from_image(texture);
+    sprite.custom_size = Some(Vec2::splat(size));
+    if let Some(elite) = &elite {
+        sprite.color = elite.tint(palette);
+    }
+
+    let mut enemy = commands.spawn((
+        sprite,
+        Transform::from_translation(pos.extend(layers::ENEMY)),
+        Enemy,
+        kind,
+        EnemySpeed(speed),
+        EnemyDamage(stats.damage * def.damage_factor),
+        EnemyHitbox { radius: size * def.contact_radius_factor },
+        EnemyAnimation::default(),
+        // enemy.png 目前只有一帧；换成多帧行走图时改这里的列数即可
+        SpriteSheetConfig {
+            columns: Some(1),
+            rows: Some(1),
+            ..default()
+        },
+        ContactCooldown::new(def.contact_cooldown),
+        EnemyAttackCooldown::new(balance.enemy_cast_interval_secs),
+        Health::new(hp),
+    ));
+    let is_elite = elite.is_some();
+    if let Some(elite) = elite {
+        enemy.insert(elite);
+    }
+    is_elite
+}
+
+/// 预警圈：从小到大收拢到敌人体型，同时闪烁
+fn draw_spawn_warnings(pending_q: Query<&PendingSpawn>, mut gizmos: Gizmos) {
+    for pending in &pending_q {
+        let t = pending.timer.fraction();
+        let pulse = 0.5 + 0.5 * (pending.timer.elapsed_secs() * SPAWN_WARNING_PULSE_SPEED).sin();
+        let color = Color::srgba(1.0, 0.25, 0.2, 0.4 + 0.5 * pulse);
+        let iso = Isometry2d::from_translation(pending.pos);
+
+        gizmos.circle_2d(iso, SPAWN_WARNING_RADIUS * (0.4 + 0.6 * t), color);
+        gizmos.circle_2d(iso, SPAWN_WARNING_RADIUS, color.with_alpha(0.25));
+    }
+}
+
+fn cleanup_pending_spawns(mut commands: Commands, q: Query<Entity, With<PendingSpawn>>) {
+    for e in &q {
+        commands.entity(e).despawn();
+    }
+}
+
+#[allow(clippy::type_complexity)]
 fn move_enemies_towards_player(
-    time: Res<Time>,
+    time: Res<Time<Virtual>>,
     player_q: Query<&Transform, (With<Player>, Without<Enemy>)>,
-    mut enemy_q: Query<(&mut Transform, &EnemySpeed), (With<Enemy>, Without<Player>)>,
+    mut enemy_q: Query<(Entity, &mut Transform, &EnemySpeed), (With<Enemy>, Without<Player>)>,
 ) {
     let Ok(player_tf) = player_q.single() else { return; };
     let ppos = player_tf.translation.truncate();
     let dt = time.delta_secs();
 
-    for (mut tf, speed) in enemy_q.iter_mut() {
+    let separation = compute_separation(&enemy_q);
+
+    for (entity, mut tf, speed) in enemy_q.iter_mut() {
         let pos = tf.translation.truncate();
         let dir = (ppos - pos).normalize_or_zero();
-        let delta = dir * speed.0 * dt;
+        let push = separation.get(&entity).copied().unwrap_or(Vec2::ZERO);
+        let delta = (dir + push * SEPARATION_WEIGHT) * speed.0 * dt;
 
         tf.translation.x += delta.x;
         tf.translation.y += delta.y;
     }
 }
 
+/// 推进敌人的行走动画，并按玩家在左还是在右翻转贴图（贴图默认朝右）
+#[allow(clippy::type_complexity)]
+fn animate_enemies(
+    time: Res<Time<Virtual>>,
+    images: Res<Assets<Image>>,
+    player_q: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    mut enemy_q: Query<
+        (&Transform, &mut Sprite, &mut EnemyAnimation, Option<&SpriteSheetConfig>),
+        (With<Enemy>, Without<Player>),
+    >,
+) {
+    let ppos = player_q.single().ok().map(|tf| tf.translation.truncate());
+
+    for (tf, mut sprite, mut anim, config) in &mut enemy_q {
+        let pos = tf.translation.truncate();
+        let moving = anim
+            .last_pos
+            .is_some_and(|last| last.distance_squared(pos) > ENEMY_MOVING_EPSILON * ENEMY_MOVING_EPSILON);
+        anim.last_pos = Some(pos);
+
+        anim.walk.init(&images, &sprite, config);
+        anim.walk.tick(time.delta(), moving);
+        anim.walk.apply(&mut sprite, 0);
+
+        if let Some(ppos) = ppos {
+            let dx = ppos.x - pos.x;
+            // 玩家几乎在正上 / 正下方时保持原朝向，避免来回抖动
+            if dx.abs() > 1.0 {
+                sprite.flip_x = dx < 0.0;
+            }
+        }
+    }
+}
+
+/// 离玩家太远的敌人直接移除，控制场上数量
+#[allow(clippy::type_complexity)]
+fn despawn_far_enemies(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    mut despawned_tx: MessageWriter<EnemyDespawned>,
+    player_q: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    enemy_q: Query<(Entity, &Transform), (With<Enemy>, Without<Player>)>,
+) {
+    let Ok(player_tf) = player_q.single() else { return; };
+    let ppos = player_tf.translation.truncate();
+    let max_dist_sq = balance.enemy_despawn_radius * balance.enemy_despawn_radius;
+
+    for (entity, tf) in &enemy_q {
+        if tf.translation.truncate().distance_squared(ppos) > max_dist_sq {
+            despawned_tx.write(EnemyDespawned { entity });
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
+
+/// boids 式分离：按网格分桶，只和相邻格子里的敌人比较，返回每个敌人被推开的方向（长度 0~1）
+#[allow(clippy::type_complexity)]
+fn compute_separation(
+    enemy_q: &Query<(Entity, &mut Transform, &EnemySpeed), (With<Enemy>, Without<Player>)>,
+) -> HashMap<Entity, Vec2> {
+    let cell_of = |pos: Vec2| (pos / SEPARATION_RADIUS).floor().as_ivec2();
+
+    let mut grid: HashMap<IVec2, Vec<(Entity, Vec2)>> = HashMap::new();
+    for (entity, tf, _) in enemy_q.iter() {
+        let pos = tf.translation.truncate();
+        grid.entry(cell_of(pos)).or_default().push((entity, pos));
+    }
+
+    let mut result = HashMap::new();
+    for (entity, tf, _) in enemy_q.iter() {
+        let pos = tf.translation.truncate();
+        let cell = cell_of(pos);
+        let mut push = Vec2::ZERO;
+        let mut neighbors = 0;
+
+        'scan: for dx in -1..=1 {
+            for dy in -1..=1 {
+                let Some(bucket) = grid.get(&(cell + IVec2::new(dx, dy))) else { continue; };
+                for &(other, other_pos) in bucket {
+                    if other == entity {
+                        continue;
+                    }
+                    let offset = pos - other_pos;
+                    let dist = offset.length();
+                    if dist >= SEPARATION_RADIUS {
+                        continue;
+                    }
+                    // 完全重叠时按实体序号给一个固定方向，避免除零
+                    let away = if dist > f32::EPSILON {
+                        offset / dist
+                    } else {
+                        Vec2::from_angle(entity.index() as f32)
+                    };
+                    push += away * (1.0 - dist / SEPARATION_RADIUS);
+
+                    neighbors += 1;
+                    if neighbors >= MAX_SEPARATION_NEIGHBORS {
+                        break 'scan;
+                    }
+                }
+            }
+        }
+
+        if push != Vec2::ZERO {
+            result.insert(entity, push.clamp_length_max(1.0));
+        }
+    }
+    result
+}
+
+/// 软碰撞：敌人（圆）和玩家（碰撞箱）重叠时把双方推开，玩家那份受上限限制并且不穿墙。
+/// 冲刺中不处理，冲刺可以穿过敌群
+#[allow(clippy::type_complexity)]
+fn push_apart_player_and_enemies(
+    time: Res<Time<Virtual>>,
+    walls: Res<WallColliders>,
+    mut player_q: Query<(&mut Transform, &PlayerHitbox, &PlayerDash), (With<Player>, Without<Enemy>)>,
+    mut enemies_q: Query<(&mut Transform, &EnemyHitbox), (With<Enemy>, Without<Player>)>,
+) {
+    let Ok((mut player_tf, player_box, dash)) = player_q.single_mut() else { return; };
+    if dash.is_dashing {
+        return;
+    }
+    let ppos = player_tf.translation.truncate();
+
+    let mut player_push = Vec2::ZERO;
+    for (mut tf, hitbox) in enemies_q.iter_mut() {
+        let epos = tf.translation.truncate();
+        let Some((normal, depth)) = circle_aabb_penetration(epos, hitbox.radius, ppos, player_box.half) else {
+            continue;
+        };
+        // normal 从玩家指向敌人
+        player_push -= normal * depth * BODY_PUSH_PLAYER_SHARE;
+        let enemy_push = normal * depth * (1.0 - BODY_PUSH_PLAYER_SHARE);
+        tf.translation.x += enemy_push.x;
+        tf.translation.y += enemy_push.y;
+    }
+
+    if player_push == Vec2::ZERO {
+        return;
+    }
+    let push = player_push.clamp_length_max(MAX_BODY_PUSH_SPEED * time.delta_secs());
+    let pos = move_with_walls(ppos, push, player_box.half, &walls.aabbs);
+    player_tf.translation.x = pos.x;
+    player_tf.translation.y = pos.y;
+}
+
+/// 圆与 AABB 的穿透：返回（从 AABB 指向圆心的方向，穿透深度），不重叠返回 None
+fn circle_aabb_penetration(center: Vec2, radius: f32, box_center: Vec2, box_half: Vec2) -> Option<(Vec2, f32)> {
+    let closest = center.clamp(box_center - box_half, box_center + box_half);
+    let offset = center - closest;
+    let dist = offset.length();
+
+    if dist > f32::EPSILON {
+        return (dist < radius).then(|| (offset / dist, radius - dist));
+    }
+
+    // 圆心已经进了碰撞箱：沿离箱边最近的轴推出去
+    let local = center - box_center;
+    let gap = box_half - local.abs();
+    if gap.x < gap.y {
+        let sign = if local.x >= 0.0 { 1.0 } else { -1.0 };
+        Some((Vec2::new(sign, 0.0), gap.x + radius))
+    } else {
+        let sign = if local.y >= 0.0 { 1.0 } else { -1.0 };
+        Some((Vec2::new(0.0, sign), gap.y + radius))
+    }
+}
+
+#[allow(clippy::type_complexity)]
 fn damage_player_on_contact(
-    mut player_q: Query<(&mut Health, &Transform), (With<Player>, Without<Enemy>)>,
-    enemies_q: Query<(&Transform, &EnemyDamage), (With<Enemy>, Without<Player>)>,
+    time: Res<Time<Virtual>>,
+    mut damaged_tx: MessageWriter<PlayerDamaged>,
+    mut dealt_tx: MessageWriter<DamageDealt>,
+    player_q: Query<(&Transform, &PlayerHitbox, Option<&Thorns>), (With<Player>, Without<Enemy>)>,
+    mut enemies_q: Query<
+        (Entity, &Transform, &EnemyDamage, &EnemyHitbox, &mut ContactCooldown),
+        (With<Enemy>, Without<Player>),
+    >,
 ) {
-    let Ok((mut player_hp, player_tf)) = player_q.single_mut() else { return; };
+    let Ok((player_tf, player_box, thorns)) = player_q.single() else { return; };
     let ppos = player_tf.translation.truncate();
+    let dt = time.delta_secs();
+
+    for (enemy, tf, dmg, hitbox, mut cooldown) in enemies_q.iter_mut() {
+        cooldown.remaining = (cooldown.remaining - dt).max(0.0);
+        if cooldown.remaining > 0.0 {
+            continue;
+        }
 
-    for (tf, dmg) in enemies_q.iter() {
-        let dist = tf.translation.truncate().distance(ppos);
-        if dist <= 1.0 {
-            player_hp.current -= dmg.0;
+        let epos = tf.translation.truncate();
+        if circle_overlaps_aabb(epos, hitbox.radius, ppos, player_box.half) {
+            damaged_tx.write(PlayerDamaged { from: epos, amount: dmg.0 });
+            cooldown.remaining = cooldown.interval;
+            // 荆棘按敌人的原始接触伤害反弹，不看玩家的护甲、护盾和无敌帧
+            if let Some(Thorns(fraction)) = thorns {
+                dealt_tx.write(DamageDealt { target: enemy, amount: dmg.0 * fraction, crit: false, lifesteal: 0.0 });
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+/// 圆（敌人）与 AABB（玩家碰撞箱）是否重叠
+fn circle_overlaps_aabb(center: Vec2, radius: f32, box_center: Vec2, box_half: Vec2) -> bool {
+    let closest = center.clamp(box_center - box_half, box_center + box_half);
+    closest.distance_squared(center) <= radius * radius
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walls_with(aabbs: Vec<(Vec2, Vec2)>) -> WallColliders {
+        WallColliders { aabbs, ..default() }
+    }
+
+    #[test]
+    fn spawn_pos_inside_wall_is_rejected() {
+        let walls = walls_with(vec![(Vec2::new(300.0, 0.0), Vec2::splat(8.0))]);
+        assert!(!is_valid_spawn_pos(Vec2::new(300.0, 0.0), Vec2::ZERO, &walls));
+        // 贴着墙边、身体会压进墙里也不行
+        assert!(!is_valid_spawn_pos(Vec2::new(315.0, 0.0), Vec2::ZERO, &walls));
+        assert!(is_valid_spawn_pos(Vec2::new(360.0, 0.0), Vec2::ZERO, &walls));
+    }
+
+    #[test]
+    fn spawn_pos_near_player_is_rejected() {
+        let walls = walls_with(Vec::new());
+        assert!(!is_valid_spawn_pos(Vec2::new(50.0, 0.0), Vec2::ZERO, &walls));
+        assert!(is_valid_spawn_pos(Vec2::new(SPAWN_MIN_PLAYER_DISTANCE, 0.0), Vec2::ZERO, &walls));
+    }
+}
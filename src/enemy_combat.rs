@@ -1,65 +1,283 @@
 use bevy::prelude::*;
+use rand::Rng;
 
-use crate::combat_core::{spawn_slash_vfx, skill_slash_on_player, CombatSet, VfxPool};
+use crate::balance::BalanceConfig;
+use crate::combat_core::{
+    spawn_projectile, spawn_slash_vfx, skill_slash_on_player, CollisionLayers, CombatSet, ProjectilePool, VfxPool,
+    ENEMY_SLASH_LENGTH, ENEMY_SLASH_WIDTH, SLASH_VFX_COLOR,
+};
 use crate::enemy::Enemy;
-use crate::health::Health;
+use crate::enemy_db::{EnemyDatabase, EnemyKind};
+use crate::health::PlayerDamaged;
+use crate::layers;
+use crate::ldtk_collision::WallColliders;
 use crate::movement::Player;
 use crate::skills_pool::{SkillId, SkillPool};
 use crate::state::GameState;
 
-#[derive(Resource)]
-struct EnemyCastTimer(Timer);
+/// 每个敌人各自的出手冷却：remaining 归零且玩家在范围内时开始预警
+#[derive(Component)]
+pub struct EnemyAttackCooldown {
+    pub remaining: f32,
+    pub cooldown: f32,
+}
+
+impl EnemyAttackCooldown {
+    /// 刚生成的敌人随机错开第一次出手，避免一起刷出来的敌人同时攻击
+    pub fn new(cooldown: f32) -> Self {
+        Self { remaining: cooldown * rand::thread_rng().gen_range(0.5..1.0), cooldown }
+    }
+}
+
+/// 预警时长（秒）：这段时间内只显示预警，结束时才真正出手
+const TELEGRAPH_SECS: f32 = 0.4;
+
+/// 远程敌人的飞弹：飞得比玩家子弹慢，留出躲闪和拦截的余地
+const ENEMY_BOLT_SPEED: f32 = 260.0;
+const ENEMY_BOLT_LIFETIME: f32 = 1.6;
+const ENEMY_BOLT_DAMAGE: f32 = 12.0;
+/// 飞弹预警是一条细线，只提示方向
+const BOLT_TELEGRAPH_LENGTH: f32 = 120.0;
+const BOLT_TELEGRAPH_WIDTH: f32 = 6.0;
+
+/// 预警结束后要执行的攻击
+#[derive(Clone, Copy)]
+enum PendingAttack {
+    Slash { origin: Vec2, dir: Vec2 },
+    Bolt { origin: Vec2, dir: Vec2 },
+}
+
+impl PendingAttack {
+    fn origin_dir(&self) -> (Vec2, Vec2) {
+        match *self {
+            PendingAttack::Slash { origin, dir } | PendingAttack::Bolt { origin, dir } => (origin, dir),
+        }
+    }
+
+    /// 预警区域完全展开后的长和宽
+    fn telegraph_size(&self) -> Vec2 {
+        match self {
+            PendingAttack::Slash { .. } => Vec2::new(ENEMY_SLASH_LENGTH, ENEMY_SLASH_WIDTH),
+            PendingAttack::Bolt { .. } => Vec2::new(BOLT_TELEGRAPH_LENGTH, BOLT_TELEGRAPH_WIDTH),
+        }
+    }
+}
+
+/// 出手前的预警：一条沿攻击方向逐渐伸长的警示区域
+#[derive(Component)]
+struct Telegraph {
+    caster: Entity,
+    timer: Timer,
+    then: PendingAttack,
+}
 
 pub struct EnemyCombatPlugin;
 
 impl Plugin for EnemyCombatPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(EnemyCastTimer(Timer::from_seconds(1.2, TimerMode::Repeating)))
-            .add_systems(Update, enemy_cast_skill.in_set(CombatSet).run_if(in_state(GameState::InGame)));
+        app.add_systems(
+                Update,
+                (enemy_cast_skill, update_telegraphs)
+                    .chain()
+                    .in_set(CombatSet)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnEnter(GameState::MainMenu), cleanup_telegraphs);
     }
 }
 
+/// 玩家是否在施法范围内且中间没有墙挡着
+fn can_cast_at(enemy_pos: Vec2, player_pos: Vec2, range: f32, walls: &WallColliders) -> bool {
+    enemy_pos.distance(player_pos) <= range && walls.line_of_sight(enemy_pos, player_pos)
+}
+
+/// 每个敌人独立计算冷却，范围内冷却好了就各自预警出手。
+/// 隔着墙不出手，冷却停在 0，继续追击绕过墙后视线一通就立刻出手。
+/// 远程种类在更远的距离就出手，吐飞弹而不是挥砍
+#[allow(clippy::too_many_arguments)]
 fn enemy_cast_skill(
-    time: Res<Time>,
-    mut timer: ResMut<EnemyCastTimer>,
+    time: Res<Time<Virtual>>,
+    balance: Res<BalanceConfig>,
+    walls: Res<WallColliders>,
+    db: Res<EnemyDatabase>,
     mut pool: ResMut<SkillPool>,
     mut commands: Commands,
-    enemies_q: Query<&Transform, With<Enemy>>,
-    mut player_q: Query<(&Transform, &mut Health), With<Player>>,
-    mut vfx_pool: ResMut<VfxPool>,
+    mut enemies_q: Query<(Entity, &Transform, &EnemyKind, &mut EnemyAttackCooldown), With<Enemy>>,
+    player_q: Query<&Transform, With<Player>>,
 ) {
-    timer.0.tick(time.delta());
-    if !timer.0.just_finished() {
-        return;
+    let dt = time.delta_secs();
+    let player_pos = player_q.single().ok().map(|tf| tf.translation.truncate());
+
+    for (caster, tf, kind, mut cooldown) in &mut enemies_q {
+        cooldown.remaining = (cooldown.remaining - dt).max(0.0);
+        if cooldown.remaining > 0.0 {
+            continue;
+        }
+
+        let Some(player_pos) = player_pos else { continue; };
+        let enemy_pos = tf.translation.truncate();
+        let ranged = db.get(*kind).ranged;
+        let range = if ranged { balance.enemy_bolt_range } else { balance.enemy_cast_range };
+        if !can_cast_at(enemy_pos, player_pos, range, &walls) {
+            continue;
+        }
+
+        cooldown.remaining = cooldown.cooldown;
+
+        if ranged {
+            let dir = (player_pos - enemy_pos).normalize_or_zero();
+            if dir != Vec2::ZERO {
+                spawn_telegraph(&mut commands, caster, PendingAttack::Bolt { origin: enemy_pos, dir });
+            }
+            continue;
+        }
+
+        match pool.next_enemy_skill() {
+            SkillId::Slash => {
+                let dir = (player_pos - enemy_pos).normalize_or_zero();
+                if dir != Vec2::ZERO {
+                    spawn_telegraph(&mut commands, caster, PendingAttack::Slash { origin: enemy_pos, dir });
+                }
+            }
+            SkillId::Barrier => {}
+        }
     }
+}
+
+fn spawn_telegraph(commands: &mut Commands, caster: Entity, attack: PendingAttack) {
+    let (origin, dir) = attack.origin_dir();
+    let angle = dir.y.atan2(dir.x);
+
+    commands.spawn((
+        Sprite {
+            color: Color::srgba(1.0, 0.2, 0.2, 0.35),
+            custom_size: Some(Vec2::new(0.0, attack.telegraph_size().y)),
+            ..default()
+        },
+        Transform {
+            translation: origin.extend(layers::VFX),
+            rotation: Quat::from_rotation_z(angle),
+            ..default()
+        },
+        Telegraph {
+            caster,
+            timer: Timer::from_seconds(TELEGRAPH_SECS, TimerMode::Once),
+            then: attack,
+        },
+    ));
+}
+
+/// 预警区域从施法者脚下向前伸长到完整判定范围；结束时出手，施法者已死则作废
+#[allow(clippy::too_many_arguments)]
+fn update_telegraphs(
+    time: Res<Time<Virtual>>,
+    mut commands: Commands,
+    mut telegraphs: Query<(Entity, &mut Telegraph, &mut Sprite, &mut Transform)>,
+    casters_q: Query<(), With<Enemy>>,
+    player_q: Query<&Transform, (With<Player>, Without<Telegraph>)>,
+    walls: Res<WallColliders>,
+    mut vfx_pool: ResMut<VfxPool>,
+    mut projectile_pool: ResMut<ProjectilePool>,
+    mut damaged_tx: MessageWriter<PlayerDamaged>,
+) {
+    for (entity, mut telegraph, mut sprite, mut tf) in &mut telegraphs {
+        telegraph.timer.tick(time.delta());
+        let (origin, dir) = telegraph.then.origin_dir();
+        let full = telegraph.then.telegraph_size();
+
+        let length = full.x * telegraph.timer.fraction();
+        sprite.custom_size = Some(Vec2::new(length, full.y));
+        let center = origin + dir * (length * 0.5);
+        tf.translation.x = center.x;
+        tf.translation.y = center.y;
+
+        if !telegraph.timer.is_finished() {
+            continue;
+        }
+        commands.entity(entity).despawn();
 
-    let Ok((player_tf, mut player_hp)) = player_q.single_mut() else { return; };
-    let player_pos = player_tf.translation.truncate();
+        if casters_q.get(telegraph.caster).is_err() {
+            continue;
+        }
 
-    let mut best_enemy_pos = None;
-    let mut best_dist = f32::MAX;
+        // 飞弹交给弹道系统，撞墙、命中玩家和被拦截都在那边处理
+        if let PendingAttack::Bolt { .. } = telegraph.then {
+            spawn_projectile(
+                &mut commands,
+                Some(&mut projectile_pool),
+                origin,
+                dir,
+                ENEMY_BOLT_SPEED,
+                ENEMY_BOLT_LIFETIME,
+                ENEMY_BOLT_DAMAGE,
+                CollisionLayers::enemy_projectile(),
+                None,
+                None,
+                0.0,
+            );
+            continue;
+        }
 
-    for tf in enemies_q.iter() {
-        let pos = tf.translation.truncate();
-        let dist = pos.distance(player_pos);
-        if dist < best_dist {
-            best_dist = dist;
-            best_enemy_pos = Some(pos);
+        spawn_slash_vfx(&mut commands, Some(&mut vfx_pool), origin, dir, SLASH_VFX_COLOR);
+        if let Ok(player_tf) = player_q.single() {
+            let player_pos = player_tf.translation.truncate();
+            // 预警期间玩家躲到墙后面也算躲开
+            if !walls.line_of_sight(origin, player_pos) {
+                continue;
+            }
+            if let Some(amount) = skill_slash_on_player(origin, dir, player_pos) {
+                damaged_tx.write(PlayerDamaged { from: origin, amount });
+            }
         }
     }
+}
 
-    let Some(enemy_pos) = best_enemy_pos else { return; };
-    if best_dist > 160.0 {
-        return;
+fn cleanup_telegraphs(mut commands: Commands, q: Query<Entity, With<Telegraph>>) {
+    for e in &q {
+        commands.entity(e).despawn();
     }
+}
 
-    let skill = pool.next_non_dash();
-    match skill {
-        SkillId::Slash => {
-            let dir = (player_pos - enemy_pos).normalize_or_zero();
-            spawn_slash_vfx(&mut commands, Some(&mut vfx_pool), enemy_pos, dir);
-            skill_slash_on_player(enemy_pos, dir, player_pos, &mut player_hp);
-        }
-        SkillId::Dash => {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat_core::Projectile;
+    use crate::health::Health;
+    use crate::test_support::{headless_app, spawn_player, step};
+
+    #[test]
+    fn wall_between_enemy_and_player_blocks_cast() {
+        let mut walls = WallColliders { aabbs: Vec::new(), ..default() };
+        let (enemy, player) = (Vec2::ZERO, Vec2::new(100.0, 0.0));
+        assert!(can_cast_at(enemy, player, 160.0, &walls));
+        assert!(!can_cast_at(enemy, player, 80.0, &walls));
+
+        walls.aabbs.push((Vec2::new(50.0, 0.0), Vec2::splat(8.0)));
+        assert!(!can_cast_at(enemy, player, 160.0, &walls));
+        // 墙不在连线上就不影响
+        assert!(can_cast_at(enemy, Vec2::new(0.0, 100.0), 160.0, &walls));
+    }
+
+    #[test]
+    fn spitter_fires_a_bolt_that_hits_the_player() {
+        let mut app = headless_app();
+        app.init_resource::<SkillPool>().add_plugins(EnemyCombatPlugin);
+        let player = spawn_player(&mut app, Vec2::ZERO);
+        app.world_mut().spawn((
+            Enemy,
+            EnemyKind::Spitter,
+            Transform::from_xyz(300.0, 0.0, 0.0),
+            Health::new(10.0),
+            EnemyAttackCooldown { remaining: 0.0, cooldown: 10.0 },
+        ));
+
+        // 预警结束后飞弹出膛，走的是敌方弹道层
+        step(&mut app, 30);
+        let mut bolts = app.world_mut().query::<&Projectile>();
+        let layers: Vec<_> = bolts.iter(app.world()).map(|p| p.layers.layer).collect();
+        assert_eq!(layers, vec![CollisionLayers::ENEMY_PROJECTILE]);
+
+        step(&mut app, 90);
+        assert!(app.world().get::<Health>(player).unwrap().current < 100.0);
     }
 }
@@ -0,0 +1,151 @@
+// src/enemy_db.rs
+//! 敌人种类表：每种敌人的体型、属性倍率和接触判定都在这里配置。
+//! 刷怪时按权重抽一种，再乘到难度算好的基础属性上。
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+/// 体型最大的种类（Brute）的体型倍率，刷怪点避墙按它算
+pub const MAX_SIZE_FACTOR: f32 = 1.4;
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EnemyKind {
+    /// 普通小怪
+    Grunt,
+    /// 又小又快，贴身后咬得勤但每口不疼
+    Biter,
+    /// 又大又慢，很久才撞一下但撞得狠
+    Brute,
+    /// 远程：隔着一段距离朝玩家吐飞弹，飞弹能被拦截弹击落
+    Spitter,
+}
+
+/// 一种敌人的配置。倍率都相对于刷怪时按难度算好的基础属性
+#[derive(Clone, Debug)]
+pub struct EnemyDef {
+    /// 刷出这种敌人的相对权重
+    pub weight: f32,
+    pub size_factor: f32,
+    pub speed_factor: f32,
+    pub hp_factor: f32,
+    pub damage_factor: f32,
+    /// 两次接触伤害之间的间隔（秒）
+    pub contact_cooldown: f32,
+    /// 接触判定半径占体型的比例
+    pub contact_radius_factor: f32,
+    /// 出手时吐飞弹而不是近身挥砍
+    pub ranged: bool,
+}
+
+#[derive(Resource)]
+pub struct EnemyDatabase {
+    defs: HashMap<EnemyKind, EnemyDef>,
+}
+
+impl Default for EnemyDatabase {
+    fn default() -> Self {
+        let mut defs = HashMap::new();
+        defs.insert(
+            EnemyKind::Grunt,
+            EnemyDef {
+                weight: 6.0,
+                size_factor: 1.0,
+                speed_factor: 1.0,
+                hp_factor: 1.0,
+                damage_factor: 1.0,
+                contact_cooldown: 0.8,
+                contact_radius_factor: 0.5,
+                ranged: false,
+            },
+        );
+        defs.insert(
+            EnemyKind::Biter,
+            EnemyDef {
+                weight: 2.0,
+                size_factor: 0.8,
+                speed_factor: 1.35,
+                hp_factor: 0.6,
+                damage_factor: 0.5,
+                contact_cooldown: 0.35,
+                contact_radius_factor: 0.45,
+                ranged: false,
+            },
+        );
+        defs.insert(
+            EnemyKind::Brute,
+            EnemyDef {
+                weight: 1.0,
+                size_factor: MAX_SIZE_FACTOR,
+                speed_factor: 0.65,
+                hp_factor: 2.0,
+                damage_factor: 1.8,
+                contact_cooldown: 1.6,
+                contact_radius_factor: 0.55,
+                ranged: false,
+            },
+        );
+        defs.insert(
+            EnemyKind::Spitter,
+            EnemyDef {
+                weight: 1.5,
+                size_factor: 0.9,
+                speed_factor: 0.8,
+                hp_factor: 0.7,
+                damage_factor: 0.6,
+                contact_cooldown: 0.8,
+                contact_radius_factor: 0.5,
+                ranged: true,
+            },
+        );
+        Self { defs }
+    }
+}
+
+impl EnemyDatabase {
+    pub fn get(&self, kind: EnemyKind) -> &EnemyDef {
+        // 表里每种都有配置，缺了就是写漏了
+        self.defs.get(&kind).expect("enemy kind missing from EnemyDatabase")
+    }
+
+    /// 按权重随机抽一种敌人
+    pub fn roll(&self, rng: &mut impl Rng) -> EnemyKind {
+        let mut kinds: Vec<_> = self.defs.iter().map(|(k, def)| (*k, def.weight)).collect();
+        // HashMap 顺序不固定，排一下保证同一个随机数抽到同一种
+        kinds.sort_by_key(|(k, _)| *k as u8);
+        let total: f32 = kinds.iter().map(|(_, w)| w.max(0.0)).sum();
+        let mut pick = rng.gen_range(0.0..total.max(f32::EPSILON));
+        for (kind, weight) in &kinds {
+            if pick < weight.max(0.0) {
+                return *kind;
+            }
+            pick -= weight.max(0.0);
+        }
+        EnemyKind::Grunt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn biter_bites_more_often_than_brute() {
+        let db = EnemyDatabase::default();
+        assert!(db.get(EnemyKind::Biter).contact_cooldown < db.get(EnemyKind::Grunt).contact_cooldown);
+        assert!(db.get(EnemyKind::Grunt).contact_cooldown < db.get(EnemyKind::Brute).contact_cooldown);
+    }
+
+    #[test]
+    fn roll_only_returns_weighted_kinds() {
+        let mut db = EnemyDatabase::default();
+        db.defs.get_mut(&EnemyKind::Grunt).unwrap().weight = 0.0;
+        db.defs.get_mut(&EnemyKind::Brute).unwrap().weight = 0.0;
+        db.defs.get_mut(&EnemyKind::Spitter).unwrap().weight = 0.0;
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            assert_eq!(db.roll(&mut rng), EnemyKind::Biter);
+        }
+    }
+}
@@ -1,28 +1,30 @@
 use bevy::image::Image;
+use bevy::input::mouse::MouseWheel;
+use bevy::picking::events::{Drag, DragDrop, DragEnd, DragStart, Pointer};
+use bevy::picking::pointer::PointerButton;
+use bevy::picking::Pickable;
 use bevy::prelude::*;
 use bevy::ui::{
     AlignItems, Display, FlexDirection, GridAutoFlow, JustifyContent, PositionType,
-    RepeatedGridTrack, UiRect,
+    RelativeCursorPosition, RepeatedGridTrack, UiRect,
 };
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::inventory::{Inventory, ItemStack};
+use crate::combat_core::CombatSet;
+use crate::health::Armor;
+use crate::i18n::{tr, Language};
+use crate::input::KeyBindings;
+use crate::inventory::{Inventory, InventoryConfig, ItemStack};
 use crate::movement::Player;
+use crate::notifications::Notifications;
+use crate::pickup::PickupRadius;
 use crate::state::GameState;
+use crate::ui::types::GameSettings;
+use crate::utils::PressLatch;
 
-#[derive(Resource)]
-pub struct EquipmentUiConfig {
-    pub toggle_key: KeyCode,
-}
-impl Default for EquipmentUiConfig {
-    fn default() -> Self {
-        Self {
-            toggle_key: KeyCode::KeyB,
-        }
-    }
-}
-
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum WeaponKind {
     Melee,
     Ranged,
@@ -37,6 +39,17 @@ pub struct EquipmentSet {
     pub weapon_projectile_lifetime: f32,
     pub melee_range: f32,
     pub melee_width: f32,
+    /// 吸血比例：对敌人造成伤害的该比例会回复给玩家
+    pub lifesteal: f32,
+    /// 子弹消失时的爆炸半径，None 为普通子弹
+    pub projectile_explosion_radius: Option<f32>,
+    /// 每次射出的子弹数和扇形张角（度），子弹在瞄准方向两侧对称分布
+    pub projectile_count: u32,
+    pub spread_degrees: f32,
+    /// 子弹飞得越远伤害越低，None 为不衰减
+    pub damage_falloff: Option<DamageFalloff>,
+    /// 子弹能否击落敌人的子弹
+    pub shoots_down_projectiles: bool,
 }
 
 impl Default for EquipmentSet {
@@ -49,43 +62,144 @@ impl Default for EquipmentSet {
             weapon_projectile_lifetime: 1.0,
             melee_range: 80.0,
             melee_width: 40.0,
+            lifesteal: 0.0,
+            projectile_explosion_radius: None,
+            projectile_count: 1,
+            spread_degrees: 0.0,
+            damage_falloff: None,
+            shoots_down_projectiles: false,
         }
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// 远程伤害随飞行距离衰减：start 以内满伤害，之后线性降到 end 处的 min_factor 并保持
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DamageFalloff {
+    pub start: f32,
+    pub end: f32,
+    pub min_factor: f32,
+}
+
+impl DamageFalloff {
+    /// 飞行 distance 后的伤害倍率
+    pub fn factor(&self, distance: f32) -> f32 {
+        if distance <= self.start {
+            return 1.0;
+        }
+        let span = (self.end - self.start).max(f32::EPSILON);
+        let t = ((distance - self.start) / span).min(1.0);
+        1.0 + (self.min_factor - 1.0) * t
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
 pub enum ItemId {
+    #[default]
     RustySword,
     MagicWand,
     HunterBow,
+    HealthPotion,
+    LeatherArmor,
 }
 
-impl Default for ItemId {
-    fn default() -> Self {
-        ItemId::RustySword
-    }
+/// 物品大类：决定在背包里点击时是装备武器、使用还是穿到护甲槽
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ItemCategory {
+    Weapon,
+    Consumable,
+    Armor,
 }
 
 impl ItemId {
-    pub fn display_name(self) -> &'static str {
+    /// 会随机掉落的武器
+    pub const WEAPONS: [ItemId; 3] = [ItemId::RustySword, ItemId::MagicWand, ItemId::HunterBow];
+
+    pub fn category(self) -> ItemCategory {
         match self {
-            ItemId::RustySword => "生锈短剑",
-            ItemId::MagicWand => "法杖",
-            ItemId::HunterBow => "猎弓",
+            ItemId::RustySword | ItemId::MagicWand | ItemId::HunterBow => ItemCategory::Weapon,
+            ItemId::HealthPotion => ItemCategory::Consumable,
+            ItemId::LeatherArmor => ItemCategory::Armor,
         }
     }
 
+    /// 背包里一格最多叠几个；武器带随机属性，永远单独占一格
+    pub fn max_stack(self) -> u32 {
+        match self.category() {
+            ItemCategory::Weapon | ItemCategory::Armor => 1,
+            ItemCategory::Consumable => 10,
+        }
+    }
+
+    pub fn display_name(self, lang: Language) -> &'static str {
+        let key = match self {
+            ItemId::RustySword => "item.rusty_sword",
+            ItemId::MagicWand => "item.magic_wand",
+            ItemId::HunterBow => "item.hunter_bow",
+            ItemId::HealthPotion => "item.health_potion",
+            ItemId::LeatherArmor => "item.leather_armor",
+        };
+        tr(lang, key)
+    }
+
     pub fn icon_path(self) -> &'static str {
         match self {
             ItemId::RustySword => "items/rusty_sword.png",
             ItemId::MagicWand => "items/magic_wand.png",
             ItemId::HunterBow => "items/hunter_bow.png",
+            ItemId::HealthPotion => "items/health_potion.png",
+            ItemId::LeatherArmor => "items/leather_armor.png",
+        }
+    }
+}
+
+/// 物品品质：决定掉落时属性的随机范围，以及背包格子的边框颜色
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Rarity {
+    #[default]
+    Common,
+    Rare,
+    Epic,
+}
+
+impl Rarity {
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Rarity::Common => "rarity.common",
+            Rarity::Rare => "rarity.rare",
+            Rarity::Epic => "rarity.epic",
+        }
+    }
+
+    pub fn border_color(self) -> Color {
+        match self {
+            Rarity::Common => Color::srgb(0.45, 0.45, 0.50),
+            Rarity::Rare => Color::srgb(0.25, 0.50, 0.95),
+            Rarity::Epic => Color::srgb(0.70, 0.30, 0.90),
+        }
+    }
+
+    /// 掉落时属性倍率的随机区间
+    fn stat_roll_range(self) -> (f32, f32) {
+        match self {
+            Rarity::Common => (0.90, 1.00),
+            Rarity::Rare => (1.00, 1.15),
+            Rarity::Epic => (1.15, 1.30),
+        }
+    }
+
+    /// 掉落时有概率比基础品质高一档
+    fn roll_upgrade(self, rng: &mut impl Rng) -> Self {
+        match self {
+            Rarity::Common if rng.gen_bool(0.2) => Rarity::Rare,
+            Rarity::Rare if rng.gen_bool(0.1) => Rarity::Epic,
+            other => other,
         }
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct WeaponDef {
+    pub rarity: Rarity,
     pub kind: WeaponKind,
     pub damage: f32,
     pub cooldown: f32,
@@ -93,11 +207,43 @@ pub struct WeaponDef {
     pub projectile_lifetime: f32,
     pub melee_range: f32,
     pub melee_width: f32,
+    pub lifesteal: f32,
+    /// 远程武器的子弹消失时爆炸的半径；None 为普通子弹（旧存档里没有这个字段）
+    #[serde(default)]
+    pub explosion_radius: Option<f32>,
+    /// 远程武器每次射出的子弹数，以及整把扇形的张角（度）
+    #[serde(default = "default_projectile_count")]
+    pub projectile_count: u32,
+    #[serde(default)]
+    pub spread_degrees: f32,
+    #[serde(default)]
+    pub damage_falloff: Option<DamageFalloff>,
+    /// 远程武器的子弹能否击落敌人的子弹
+    #[serde(default)]
+    pub shoots_down_projectiles: bool,
+}
+
+fn default_projectile_count() -> u32 {
+    1
+}
+
+/// 消耗品：使用后回复生命
+#[derive(Clone, Debug)]
+pub struct ConsumableDef {
+    pub heal: f32,
+}
+
+/// 护甲：每次受伤固定减免
+#[derive(Clone, Debug)]
+pub struct ArmorDef {
+    pub damage_reduction: f32,
 }
 
 #[derive(Resource)]
 pub struct ItemDatabase {
     weapons: HashMap<ItemId, WeaponDef>,
+    consumables: HashMap<ItemId, ConsumableDef>,
+    armors: HashMap<ItemId, ArmorDef>,
 }
 
 impl Default for ItemDatabase {
@@ -107,6 +253,7 @@ impl Default for ItemDatabase {
         weapons.insert(
             ItemId::RustySword,
             WeaponDef {
+                rarity: Rarity::Common,
                 kind: WeaponKind::Melee,
                 damage: 20.0,
                 cooldown: 0.6,
@@ -114,12 +261,19 @@ impl Default for ItemDatabase {
                 projectile_lifetime: 1.0,
                 melee_range: 80.0,
                 melee_width: 40.0,
+                lifesteal: 0.0,
+                explosion_radius: None,
+                projectile_count: 1,
+                spread_degrees: 0.0,
+                damage_falloff: None,
+                shoots_down_projectiles: false,
             },
         );
 
         weapons.insert(
             ItemId::MagicWand,
             WeaponDef {
+                rarity: Rarity::Common,
                 kind: WeaponKind::Ranged,
                 damage: 14.0,
                 cooldown: 0.35,
@@ -127,12 +281,19 @@ impl Default for ItemDatabase {
                 projectile_lifetime: 1.2,
                 melee_range: 60.0,
                 melee_width: 30.0,
+                lifesteal: 0.1,
+                explosion_radius: Some(48.0),
+                projectile_count: 1,
+                spread_degrees: 0.0,
+                damage_falloff: Some(DamageFalloff { start: 180.0, end: 480.0, min_factor: 0.5 }),
+                shoots_down_projectiles: false,
             },
         );
 
         weapons.insert(
             ItemId::HunterBow,
             WeaponDef {
+                rarity: Rarity::Common,
                 kind: WeaponKind::Ranged,
                 damage: 18.0,
                 cooldown: 0.55,
@@ -140,10 +301,26 @@ impl Default for ItemDatabase {
                 projectile_lifetime: 1.0,
                 melee_range: 60.0,
                 melee_width: 30.0,
+                lifesteal: 0.0,
+                explosion_radius: None,
+                projectile_count: 1,
+                spread_degrees: 0.0,
+                damage_falloff: None,
+                shoots_down_projectiles: true,
             },
         );
 
-        Self { weapons }
+        let mut consumables = HashMap::new();
+        consumables.insert(ItemId::HealthPotion, ConsumableDef { heal: 30.0 });
+
+        let mut armors = HashMap::new();
+        armors.insert(ItemId::LeatherArmor, ArmorDef { damage_reduction: 2.0 });
+
+        Self {
+            weapons,
+            consumables,
+            armors,
+        }
     }
 }
 
@@ -151,6 +328,49 @@ impl ItemDatabase {
     pub fn weapon(&self, id: ItemId) -> Option<&WeaponDef> {
         self.weapons.get(&id)
     }
+
+    pub fn consumable(&self, id: ItemId) -> Option<&ConsumableDef> {
+        self.consumables.get(&id)
+    }
+
+    pub fn armor(&self, id: ItemId) -> Option<&ArmorDef> {
+        self.armors.get(&id)
+    }
+
+    /// 按基础属性生成一件武器（初始装备用，不随机）
+    pub fn base_instance(&self, id: ItemId) -> Option<ItemInstance> {
+        let def = self.weapon(id)?;
+        Some(ItemInstance {
+            id,
+            rarity: def.rarity,
+            rolled: def.clone(),
+        })
+    }
+
+    /// 掉落一件武器：先决定品质，再在品质对应的区间里随机各项属性，所以同名武器属性也会不同
+    pub fn roll(&self, id: ItemId) -> Option<ItemInstance> {
+        let def = self.weapon(id)?;
+        let mut rng = rand::thread_rng();
+        let rarity = def.rarity.roll_upgrade(&mut rng);
+        let (lo, hi) = rarity.stat_roll_range();
+        let mut roll = || rng.gen_range(lo..=hi);
+
+        let mut rolled = def.clone();
+        rolled.rarity = rarity;
+        rolled.damage *= roll();
+        rolled.cooldown /= roll();
+        rolled.projectile_speed *= roll();
+        rolled.melee_range *= roll();
+        Some(ItemInstance { id, rarity, rolled })
+    }
+}
+
+/// 一件具体的物品：同一种 ItemId 的不同掉落可以有不同的品质和属性
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ItemInstance {
+    pub id: ItemId,
+    pub rarity: Rarity,
+    pub rolled: WeaponDef,
 }
 
 impl EquipmentSet {
@@ -163,19 +383,39 @@ impl EquipmentSet {
             weapon_projectile_lifetime: def.projectile_lifetime,
             melee_range: def.melee_range,
             melee_width: def.melee_width,
+            lifesteal: def.lifesteal,
+            projectile_explosion_radius: def.explosion_radius,
+            projectile_count: def.projectile_count,
+            spread_degrees: def.spread_degrees,
+            damage_falloff: def.damage_falloff,
+            shoots_down_projectiles: def.shoots_down_projectiles,
         }
     }
 }
 
-#[derive(Component)]
+/// 副武器的属性（右键使用）；没有装备副武器时玩家身上不存在该组件
+#[derive(Component, Clone)]
+pub struct SecondaryEquipmentSet(pub EquipmentSet);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum WeaponSlot {
+    #[default]
+    Primary,
+    Secondary,
+}
+
+#[derive(Component, Clone)]
 pub struct EquippedItems {
-    pub weapon: ItemId,
+    pub primary: ItemInstance,
+    pub secondary: Option<ItemInstance>,
+    pub armor: Option<ItemId>,
 }
 
-impl Default for EquippedItems {
-    fn default() -> Self {
-        Self {
-            weapon: ItemId::default(),
+impl EquippedItems {
+    pub fn in_slot(&self, slot: WeaponSlot) -> Option<&ItemInstance> {
+        match slot {
+            WeaponSlot::Primary => Some(&self.primary),
+            WeaponSlot::Secondary => self.secondary.as_ref(),
         }
     }
 }
@@ -184,26 +424,91 @@ impl Default for EquippedItems {
 pub struct EquipmentUiRoot;
 
 #[derive(Component)]
+#[require(PressLatch)]
 struct EquipmentSlotButton;
 
+/// 背包格子按钮，index 为它在 `Inventory::slots` 里的位置
 #[derive(Component)]
 struct InventoryItemButton {
+    pub index: usize,
     pub item_id: ItemId,
 }
 
 #[derive(Component)]
+#[require(PressLatch)]
 struct CloseButton;
 
+/// 右侧面板中的武器槽按钮：点击后把它设为背包点击装备的目标槽
+#[derive(Component)]
+#[require(PressLatch)]
+struct WeaponSlotButton {
+    slot: WeaponSlot,
+}
+
+/// 点击 / 拖拽了背包第 index 格的物品：武器装备到 weapon_slot，消耗品直接使用，护甲穿到护甲槽
 #[derive(Message, Clone, Copy, Debug)]
-struct EquipWeaponMsg {
-    item_id: ItemId,
+struct UseInventoryItemMsg {
+    index: usize,
+    weapon_slot: WeaponSlot,
+}
+
+/// 把一件掉落的武器放进玩家背包
+#[derive(Message, Clone, Debug)]
+pub struct LootItemMsg {
+    pub item: ItemInstance,
+}
+
+/// 给玩家背包扩容（背包扩展道具等）
+#[derive(Message, Clone, Copy, Debug)]
+pub struct ExpandInventoryMsg {
+    pub extra: usize,
 }
 
 #[derive(Resource, Default)]
 struct EquipmentUiDirty(pub bool);
 
+/// 背包中点击武器时装备到哪个槽
+#[derive(Resource, Default)]
+struct ActiveWeaponSlot(pub WeaponSlot);
+
+/// 鼠标悬停的背包格子序号
+#[derive(Resource, Default)]
+struct HoveredItem(pub Option<usize>);
+
+/// 拖拽背包物品时跟随指针的图标
+#[derive(Component)]
+struct DragGhost;
+
+/// 背包每页显示的格子数（10 列 x 4 行）
+const INVENTORY_PAGE_SIZE: usize = 40;
+const INVENTORY_COLUMNS: u16 = 10;
+
+/// 背包当前显示第几页（从 0 开始）
 #[derive(Resource, Default)]
-struct HoveredItem(pub Option<ItemId>);
+struct InventoryPage(pub usize);
+
+/// 背包翻页：delta 为正往后翻，为负往前翻（翻页按钮和滚轮都走这里）
+#[derive(Message, Clone, Copy, Debug)]
+struct InventoryPageMsg {
+    delta: i32,
+}
+
+#[derive(Component)]
+#[require(PressLatch)]
+struct InventoryPageButton {
+    delta: i32,
+}
+
+/// 背包格子区域，鼠标悬停在上面时滚轮翻页
+#[derive(Component)]
+struct InventoryGrid;
+
+/// 背包总页数，空背包也算一页
+fn inventory_page_count(slot_count: usize) -> usize {
+    slot_count.div_ceil(INVENTORY_PAGE_SIZE).max(1)
+}
+
+const DRAG_GHOST_SIZE: f32 = 32.0;
 
 #[derive(Component)]
 struct PlayerAttrText;
@@ -218,81 +523,144 @@ pub struct EquipmentPlugin;
 
 impl Plugin for EquipmentPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<EquipmentUiConfig>()
-            .init_resource::<ItemDatabase>()
+        app.init_resource::<ItemDatabase>()
+            .init_resource::<InventoryConfig>()
             .init_resource::<EquipmentUiDirty>()
             .init_resource::<HoveredItem>()
-            .add_message::<EquipWeaponMsg>()
+            .init_resource::<ActiveWeaponSlot>()
+            .init_resource::<InventoryPage>()
+            .add_message::<UseInventoryItemMsg>()
+            .add_message::<InventoryPageMsg>()
+            .add_message::<ExpandInventoryMsg>()
+            .add_message::<LootItemMsg>()
             .add_systems(
                 Update,
                 ensure_player_inventory_and_equipment.run_if(in_state(GameState::InGame)),
             )
             .add_systems(Update, toggle_equipment_ui.run_if(in_state(GameState::InGame)))
             .add_systems(Update, handle_slot_buttons.run_if(in_state(GameState::InGame)))
+            .add_systems(
+                Update,
+                quick_use_on_right_click
+                    .after(update_hovered_item)
+                    .before(apply_inventory_item_messages)
+                    .before(CombatSet)
+                    .run_if(in_state(GameState::InGame)),
+            )
             .add_systems(Update, handle_close_button.run_if(in_state(GameState::InGame)))
             .add_systems(
                 Update,
-                apply_equip_weapon_messages.run_if(in_state(GameState::InGame)),
+                handle_weapon_slot_buttons.run_if(in_state(GameState::InGame)),
             )
             .add_systems(
                 Update,
-                rebuild_equipment_ui_when_dirty.run_if(in_state(GameState::InGame)),
+                (apply_inventory_item_messages, apply_inventory_expansion, apply_loot_messages)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (handle_inventory_page_buttons, scroll_inventory_pages, apply_inventory_page_messages)
+                    .chain()
+                    .before(rebuild_equipment_ui_when_dirty)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (mark_equipment_ui_dirty_on_language_change, rebuild_equipment_ui_when_dirty)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
             )
             .add_systems(
                 Update,
                 (update_hovered_item, update_detail_panel).run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (start_item_drag, move_drag_ghost, drop_item_on_weapon_slot, end_item_drag)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
             );
     }
 }
 
-fn ensure_player_inventory_and_equipment(
+#[allow(clippy::collapsible_if, clippy::type_complexity)]
+pub(crate) fn ensure_player_inventory_and_equipment(
     mut commands: Commands,
     db: Res<ItemDatabase>,
+    config: Res<InventoryConfig>,
     q: Query<
         (
             Entity,
             Option<&Inventory>,
             Option<&EquippedItems>,
             Option<&EquipmentSet>,
+            Option<&SecondaryEquipmentSet>,
         ),
         With<Player>,
     >,
 ) {
-    for (e, inv, equipped, equip_set) in &q {
+    for (e, inv, equipped, equip_set, secondary_set) in &q {
         if inv.is_none() {
-            let mut inv = Inventory::new(120);
-            inv.try_add(ItemId::MagicWand, 1, 99);
-            inv.try_add(ItemId::HunterBow, 1, 99);
+            let mut inv = Inventory::new(config.base_slots);
+            for id in [ItemId::MagicWand, ItemId::HunterBow] {
+                if let Some(item) = db.base_instance(id) {
+                    inv.try_add_instance(item);
+                }
+            }
+            inv.try_add(ItemId::HealthPotion, 3, ItemId::HealthPotion.max_stack());
+            inv.try_add(ItemId::LeatherArmor, 1, ItemId::LeatherArmor.max_stack());
             commands.entity(e).insert(inv);
         }
 
-        let weapon_id = equipped.map(|x| x.weapon).unwrap_or_default();
+        let equipped = match equipped {
+            Some(x) => x.clone(),
+            None => {
+                let Some(primary) = db.base_instance(ItemId::default()) else {
+                    continue;
+                };
+                let new = EquippedItems {
+                    primary,
+                    secondary: None,
+                    armor: None,
+                };
+                commands.entity(e).insert(new.clone());
+                new
+            }
+        };
 
-        if equipped.is_none() {
-            commands.entity(e).insert(EquippedItems { weapon: weapon_id });
+        if equip_set.is_none() {
+            commands
+                .entity(e)
+                .insert(EquipmentSet::from_weapon(&equipped.primary.rolled));
         }
 
-        if equip_set.is_none() {
-            if let Some(def) = db.weapon(weapon_id) {
-                commands.entity(e).insert(EquipmentSet::from_weapon(def));
-            } else {
-                commands.entity(e).insert(EquipmentSet::default());
+        if secondary_set.is_none() {
+            if let Some(item) = &equipped.secondary {
+                commands
+                    .entity(e)
+                    .insert(SecondaryEquipmentSet(EquipmentSet::from_weapon(&item.rolled)));
             }
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn toggle_equipment_ui(
     keyboard: Res<ButtonInput<KeyCode>>,
-    cfg: Res<EquipmentUiConfig>,
+    bindings: Res<KeyBindings>,
     mut commands: Commands,
     ui_root_q: Query<Entity, With<EquipmentUiRoot>>,
     asset_server: Res<AssetServer>,
-    db: Res<ItemDatabase>,
-    player_q: Query<(&EquipmentSet, &EquippedItems, &Inventory), With<Player>>,
+    settings: Res<GameSettings>,
+    active_slot: Res<ActiveWeaponSlot>,
+    page: Res<InventoryPage>,
+    player_q: Query<
+        (&EquipmentSet, Option<&SecondaryEquipmentSet>, &EquippedItems, &Inventory),
+        With<Player>,
+    >,
     mut dirty: ResMut<EquipmentUiDirty>,
 ) {
-    if !keyboard.just_pressed(cfg.toggle_key) {
+    if !keyboard.just_pressed(bindings.toggle_equipment) {
         return;
     }
 
@@ -301,23 +669,39 @@ fn toggle_equipment_ui(
         return;
     }
 
-    let Ok((equip, equipped, inv)) = player_q.single() else {
+    let Ok((equip, secondary, equipped, inv)) = player_q.single() else {
         return;
     };
 
     dirty.0 = false;
-    spawn_player_info_ui(&mut commands, &asset_server, &db, equip, equipped, inv);
+    spawn_player_info_ui(
+        &mut commands,
+        &asset_server,
+        settings.language,
+        equip,
+        secondary,
+        equipped,
+        inv,
+        active_slot.0,
+        page.0,
+    );
 }
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_player_info_ui(
     commands: &mut Commands,
     asset_server: &AssetServer,
-    _db: &ItemDatabase,
+    lang: Language,
     equip: &EquipmentSet,
+    secondary: Option<&SecondaryEquipmentSet>,
     equipped: &EquippedItems,
     inv: &Inventory,
+    active_slot: WeaponSlot,
+    page: usize,
 ) {
     let font: Handle<Font> = asset_server.load("fonts/YuFanLixing.otf");
+    let page_count = inventory_page_count(inv.slot_count());
+    let page = page.min(page_count - 1);
     let portrait: Handle<Image> = asset_server.load("character.png");
 
     let root = commands
@@ -343,6 +727,7 @@ fn spawn_player_info_ui(
         ui.spawn((
             Node {
                 width: Val::Percent(92.0),
+                max_width: Val::Px(1400.0),
                 height: Val::Percent(90.0),
                 flex_direction: FlexDirection::Row,
                 column_gap: Val::Px(12.0),
@@ -353,8 +738,10 @@ fn spawn_player_info_ui(
         .with_children(|panel| {
             panel
                 .spawn((
+                    // 三栏在 1280x720 下按比例收缩，1080p 及以上不超过设计宽度
                     Node {
-                        width: Val::Px(320.0),
+                        width: Val::Percent(24.0),
+                        max_width: Val::Px(320.0),
                         height: Val::Percent(100.0),
                         flex_direction: FlexDirection::Column,
                         align_items: AlignItems::Center,
@@ -370,8 +757,9 @@ fn spawn_player_info_ui(
                             ..default()
                         },
                         Node {
-                            width: Val::Px(280.0),
-                            height: Val::Px(420.0),
+                            width: Val::Percent(100.0),
+                            max_width: Val::Px(280.0),
+                            aspect_ratio: Some(2.0 / 3.0),
                             margin: UiRect::all(Val::Px(8.0)),
                             ..default()
                         },
@@ -392,7 +780,7 @@ fn spawn_player_info_ui(
                 ))
                 .with_children(|mid| {
                     mid.spawn((
-                        Text::new("Inventory"),
+                        Text::new(tr(lang, "equip.inventory")),
                         TextFont {
                             font: font.clone(),
                             font_size: 22.0,
@@ -401,14 +789,67 @@ fn spawn_player_info_ui(
                         TextColor(Color::WHITE),
                     ));
 
-                    let cols: u16 = 10;
-                    let rows: u16 = ((inv.slot_count() + cols as usize - 1) / cols as usize) as u16;
+                    // 翻页栏：上一页 / 页码 / 下一页，滚轮在格子上也能翻
+                    mid.spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(10.0),
+                        ..default()
+                    })
+                    .with_children(|bar| {
+                        for (delta, label) in [(-1, "<"), (1, ">")] {
+                            if delta > 0 {
+                                bar.spawn((
+                                    Text::new(format!("{} {}/{page_count}", tr(lang, "equip.page"), page + 1)),
+                                    TextFont {
+                                        font: font.clone(),
+                                        font_size: 16.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                ));
+                            }
+                            bar.spawn((
+                                Button,
+                                InventoryPageButton { delta },
+                                Node {
+                                    width: Val::Px(36.0),
+                                    height: Val::Px(28.0),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                BackgroundColor(Color::srgb(0.20, 0.20, 0.28)),
+                            ))
+                            .with_children(|b| {
+                                b.spawn((
+                                    Text::new(label),
+                                    TextFont {
+                                        font: font.clone(),
+                                        font_size: 16.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                ));
+                            });
+                        }
+                    });
+
+                    let cols = INVENTORY_COLUMNS;
+                    let page_start = page * INVENTORY_PAGE_SIZE;
+                    let page_slots = inv.slot_count().saturating_sub(page_start).min(INVENTORY_PAGE_SIZE);
+                    let rows: u16 = page_slots.div_ceil(cols as usize) as u16;
                     let cell = 36.0;
 
                     mid.spawn((
+                        InventoryGrid,
+                        RelativeCursorPosition::default(),
                         Node {
                             width: Val::Percent(100.0),
-                            height: Val::Px(520.0),
+                            // 占满中栏剩余高度，窗口矮时格子可滚动而不是溢出面板
+                            flex_grow: 1.0,
+                            min_height: Val::Px(0.0),
+                            overflow: Overflow::scroll_y(),
                             display: Display::Grid,
                             grid_auto_flow: GridAutoFlow::Row,
                             grid_template_columns: RepeatedGridTrack::px(cols, cell),
@@ -420,21 +861,24 @@ fn spawn_player_info_ui(
                         BackgroundColor(Color::srgba(0.02, 0.02, 0.03, 0.2)),
                     ))
                     .with_children(|grid| {
-                        for idx in 0..inv.slot_count() {
-                            let maybe = inv.slots[idx];
+                        for (idx, maybe) in inv.slots.iter().enumerate().skip(page_start).take(INVENTORY_PAGE_SIZE) {
                             match maybe {
-                                Some(ItemStack { id, .. }) => {
+                                Some(ItemStack { id, count, instance }) => {
+                                    let (id, count) = (*id, *count);
+                                    let rarity = instance.as_ref().map(|x| x.rarity).unwrap_or_default();
                                     grid.spawn((
                                         Button,
                                         EquipmentSlotButton,
-                                        InventoryItemButton { item_id: id },
+                                        InventoryItemButton { index: idx, item_id: id },
                                         Node {
                                             width: Val::Px(cell),
                                             height: Val::Px(cell),
                                             justify_content: JustifyContent::Center,
                                             align_items: AlignItems::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
                                             ..default()
                                         },
+                                        BorderColor::all(rarity.border_color()),
                                         BackgroundColor(Color::srgb(0.25, 0.25, 0.35)),
                                     ))
                                     .with_children(|btn| {
@@ -446,7 +890,27 @@ fn spawn_player_info_ui(
                                                 height: Val::Px(32.0),
                                                 ..default()
                                             },
+                                            Pickable::IGNORE,
                                         ));
+                                        // 可叠加物品在右下角显示数量
+                                        if count > 1 {
+                                            btn.spawn((
+                                                Text::new(count.to_string()),
+                                                TextFont {
+                                                    font: font.clone(),
+                                                    font_size: 12.0,
+                                                    ..default()
+                                                },
+                                                TextColor(Color::WHITE),
+                                                Node {
+                                                    position_type: PositionType::Absolute,
+                                                    right: Val::Px(1.0),
+                                                    bottom: Val::Px(0.0),
+                                                    ..default()
+                                                },
+                                                Pickable::IGNORE,
+                                            ));
+                                        }
                                     });
                                 }
                                 None => {
@@ -467,7 +931,9 @@ fn spawn_player_info_ui(
             panel
                 .spawn((
                     Node {
-                        width: Val::Px(380.0),
+                        width: Val::Percent(30.0),
+                        max_width: Val::Px(380.0),
+                        min_width: Val::Px(260.0),
                         height: Val::Percent(100.0),
                         flex_direction: FlexDirection::Column,
                         row_gap: Val::Px(10.0),
@@ -478,7 +944,7 @@ fn spawn_player_info_ui(
                 ))
                 .with_children(|right| {
                     right.spawn((
-                        Text::new("Player"),
+                        Text::new(tr(lang, "equip.player")),
                         TextFont {
                             font: font.clone(),
                             font_size: 20.0,
@@ -489,7 +955,7 @@ fn spawn_player_info_ui(
 
                     right.spawn((
                         PlayerAttrText,
-                        Text::new("HP: --/--   ATK: --"),
+                        Text::new("HP: --/--   ATK: --   PICKUP: --"),
                         TextFont {
                             font: font.clone(),
                             font_size: 16.0,
@@ -500,13 +966,7 @@ fn spawn_player_info_ui(
 
                     right.spawn((
                         WeaponDataText,
-                        Text::new(format!(
-                            "Weapon: {}\nDMG: {:.0}\nCD: {:.2}\nRange: {:.0}",
-                            equipped.weapon.display_name(),
-                            equip.weapon_damage,
-                            equip.weapon_attack_cooldown,
-                            equip.melee_range
-                        )),
+                        Text::new(weapon_data_text(lang, equipped, equip, secondary)),
                         TextFont {
                             font: font.clone(),
                             font_size: 16.0,
@@ -515,8 +975,66 @@ fn spawn_player_info_ui(
                         TextColor(Color::WHITE),
                     ));
 
+                    right
+                        .spawn(Node {
+                            width: Val::Percent(100.0),
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(8.0),
+                            ..default()
+                        })
+                        .with_children(|slots| {
+                            for slot in [WeaponSlot::Primary, WeaponSlot::Secondary] {
+                                let item = equipped.in_slot(slot);
+                                let name = item.map(|x| x.id.display_name(lang)).unwrap_or("--");
+                                let border = item.map(|x| x.rarity).unwrap_or_default().border_color();
+                                slots
+                                    .spawn((
+                                        Button,
+                                        WeaponSlotButton { slot },
+                                        Node {
+                                            width: Val::Percent(50.0),
+                                            height: Val::Px(40.0),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            column_gap: Val::Px(6.0),
+                                            border: UiRect::all(Val::Px(2.0)),
+                                            ..default()
+                                        },
+                                        BorderColor::all(border),
+                                        BackgroundColor(weapon_slot_color(slot == active_slot)),
+                                    ))
+                                    .with_children(|b| {
+                                        // 子节点不参与拾取，拖放的目标始终是槽位按钮本身
+                                        if let Some(item) = item {
+                                            b.spawn((
+                                                ImageNode {
+                                                    image: asset_server.load(item.id.icon_path()),
+                                                    ..default()
+                                                },
+                                                Node {
+                                                    width: Val::Px(28.0),
+                                                    height: Val::Px(28.0),
+                                                    ..default()
+                                                },
+                                                Pickable::IGNORE,
+                                            ));
+                                        }
+                                        b.spawn((
+                                            Text::new(format!("{}: {}", slot_label(lang, slot), name)),
+                                            TextFont {
+                                                font: font.clone(),
+                                                font_size: 14.0,
+                                                ..default()
+                                            },
+                                            TextColor(Color::WHITE),
+                                            Pickable::IGNORE,
+                                        ));
+                                    });
+                            }
+                        });
+
                     right.spawn((
-                        Text::new("Item Details"),
+                        Text::new(tr(lang, "equip.details")),
                         TextFont {
                             font: font.clone(),
                             font_size: 20.0,
@@ -527,7 +1045,7 @@ fn spawn_player_info_ui(
 
                     right.spawn((
                         ItemDetailText,
-                        Text::new("Hover an item to see details."),
+                        Text::new(tr(lang, "equip.hover_hint")),
                         TextFont {
                             font: font.clone(),
                             font_size: 16.0,
@@ -550,7 +1068,7 @@ fn spawn_player_info_ui(
                     ))
                     .with_children(|b| {
                         b.spawn((
-                            Text::new("Close"),
+                            Text::new(tr(lang, "equip.close")),
                             TextFont {
                                 font: font.clone(),
                                 font_size: 16.0,
@@ -564,23 +1082,75 @@ fn spawn_player_info_ui(
     });
 }
 
+fn slot_label(lang: Language, slot: WeaponSlot) -> &'static str {
+    match slot {
+        WeaponSlot::Primary => tr(lang, "equip.primary"),
+        WeaponSlot::Secondary => tr(lang, "equip.secondary"),
+    }
+}
+
+fn weapon_slot_color(active: bool) -> Color {
+    if active {
+        Color::srgb(0.35, 0.40, 0.55)
+    } else {
+        Color::srgb(0.20, 0.20, 0.28)
+    }
+}
+
+fn weapon_data_text(
+    lang: Language,
+    equipped: &EquippedItems,
+    equip: &EquipmentSet,
+    secondary: Option<&SecondaryEquipmentSet>,
+) -> String {
+    let mut s = format!(
+        "{}: {}\nDMG: {:.0}\nCD: {:.2}\nRange: {:.0}",
+        slot_label(lang, WeaponSlot::Primary),
+        equipped.primary.id.display_name(lang),
+        equip.weapon_damage,
+        equip.weapon_attack_cooldown,
+        equip.melee_range
+    );
+    match (&equipped.secondary, secondary) {
+        (Some(item), Some(SecondaryEquipmentSet(sec))) => s.push_str(&format!(
+            "\n\n{}: {}\nDMG: {:.0}\nCD: {:.2}\nRange: {:.0}",
+            slot_label(lang, WeaponSlot::Secondary),
+            item.id.display_name(lang),
+            sec.weapon_damage,
+            sec.weapon_attack_cooldown,
+            sec.melee_range
+        )),
+        _ => s.push_str(&format!("\n\n{}: --", slot_label(lang, WeaponSlot::Secondary))),
+    }
+    let armor = equipped.armor.map(|id| id.display_name(lang)).unwrap_or("--");
+    s.push_str(&format!("\n\n{}: {}", tr(lang, "equip.armor"), armor));
+    s
+}
+
+#[allow(clippy::type_complexity)]
 fn handle_slot_buttons(
     mut interactions: Query<
-        (&Interaction, &mut BackgroundColor, Option<&InventoryItemButton>),
+        (&Interaction, &mut BackgroundColor, &mut PressLatch, Option<&InventoryItemButton>),
         (Changed<Interaction>, With<Button>, With<EquipmentSlotButton>),
     >,
-    mut writer: MessageWriter<EquipWeaponMsg>,
+    active_slot: Res<ActiveWeaponSlot>,
+    mut writer: MessageWriter<UseInventoryItemMsg>,
 ) {
-    for (interaction, mut bg, item_btn) in &mut interactions {
+    for (interaction, mut bg, mut latch, item_btn) in &mut interactions {
+        // 在按钮上松开才算一次点击：只装备一次，按下后拖走（拖到武器槽）也不会误触发
+        let clicked = latch.released_over(*interaction);
         match *interaction {
             Interaction::Pressed => {
                 bg.0 = Color::srgb(0.8, 0.8, 1.0);
-                if let Some(btn) = item_btn {
-                    writer.write(EquipWeaponMsg { item_id: btn.item_id });
-                }
             }
             Interaction::Hovered => {
                 bg.0 = Color::srgb(0.6, 0.6, 0.8);
+                if let Some(btn) = item_btn.filter(|_| clicked) {
+                    writer.write(UseInventoryItemMsg {
+                        index: btn.index,
+                        weapon_slot: active_slot.0,
+                    });
+                }
             }
             Interaction::None => {
                 bg.0 = Color::srgb(0.25, 0.25, 0.35);
@@ -589,15 +1159,23 @@ fn handle_slot_buttons(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn handle_close_button(
     mut commands: Commands,
     root_q: Query<Entity, With<EquipmentUiRoot>>,
-    mut q: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>, With<CloseButton>)>,
+    mut q: Query<
+        (&Interaction, &mut BackgroundColor, &mut PressLatch),
+        (Changed<Interaction>, With<Button>, With<CloseButton>),
+    >,
 ) {
-    for (interaction, mut bg) in &mut q {
+    for (interaction, mut bg, mut latch) in &mut q {
+        let just_pressed = latch.just_pressed(*interaction);
         match *interaction {
             Interaction::Pressed => {
                 bg.0 = Color::srgb(0.35, 0.35, 0.45);
+                if !just_pressed {
+                    continue;
+                }
                 if let Ok(root) = root_q.single() {
                     commands.entity(root).try_despawn();
                 }
@@ -608,47 +1186,361 @@ fn handle_close_button(
     }
 }
 
-fn apply_equip_weapon_messages(
-    mut reader: MessageReader<EquipWeaponMsg>,
+/// 右键背包格子快捷使用：Button 的 Interaction 只认左键，所以用悬停的格子配合鼠标右键判断。
+/// 武器优先放进空着的副武器槽，副武器槽有东西时装到当前选中的目标槽；消耗品和护甲与左键相同。
+/// 用掉这次右键，免得同一下又打出副武器
+fn quick_use_on_right_click(
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+    hovered: Res<HoveredItem>,
+    active_slot: Res<ActiveWeaponSlot>,
+    ui_root_q: Query<(), With<EquipmentUiRoot>>,
+    player_q: Query<&EquippedItems, With<Player>>,
+    mut writer: MessageWriter<UseInventoryItemMsg>,
+) {
+    if !mouse.just_pressed(MouseButton::Right) || ui_root_q.is_empty() {
+        return;
+    }
+    let Some(index) = hovered.0 else { return; };
+    let Ok(equipped) = player_q.single() else { return; };
+
+    mouse.clear_just_pressed(MouseButton::Right);
+    let weapon_slot = if equipped.secondary.is_none() { WeaponSlot::Secondary } else { active_slot.0 };
+    writer.write(UseInventoryItemMsg { index, weapon_slot });
+}
+
+#[allow(clippy::type_complexity)]
+fn handle_inventory_page_buttons(
+    mut q: Query<
+        (&Interaction, &mut BackgroundColor, &mut PressLatch, &InventoryPageButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut page_tx: MessageWriter<InventoryPageMsg>,
+) {
+    for (interaction, mut bg, mut latch, btn) in &mut q {
+        let just_pressed = latch.just_pressed(*interaction);
+        match *interaction {
+            Interaction::Pressed => {
+                bg.0 = Color::srgb(0.35, 0.35, 0.45);
+                if just_pressed {
+                    page_tx.write(InventoryPageMsg { delta: btn.delta });
+                }
+            }
+            Interaction::Hovered => bg.0 = Color::srgb(0.28, 0.28, 0.40),
+            Interaction::None => bg.0 = Color::srgb(0.20, 0.20, 0.28),
+        }
+    }
+}
+
+/// 背包打开且鼠标在格子区域上时，滚轮向下翻到下一页、向上翻回上一页
+fn scroll_inventory_pages(
+    mut wheel_rx: MessageReader<MouseWheel>,
+    grid_q: Query<&RelativeCursorPosition, With<InventoryGrid>>,
+    mut page_tx: MessageWriter<InventoryPageMsg>,
+) {
+    let dy: f32 = wheel_rx.read().map(|ev| ev.y).sum();
+    if dy == 0.0 {
+        return;
+    }
+    if grid_q.iter().any(|cursor| cursor.cursor_over()) {
+        page_tx.write(InventoryPageMsg { delta: if dy < 0.0 { 1 } else { -1 } });
+    }
+}
+
+fn apply_inventory_page_messages(
+    mut page_rx: MessageReader<InventoryPageMsg>,
+    mut page: ResMut<InventoryPage>,
+    mut dirty: ResMut<EquipmentUiDirty>,
+    player_q: Query<&Inventory, With<Player>>,
+) {
+    let delta: i32 = page_rx.read().map(|ev| ev.delta).sum();
+    if delta == 0 {
+        return;
+    }
+    let Ok(inv) = player_q.single() else { return; };
+    let last = inventory_page_count(inv.slot_count()) as i32 - 1;
+    let next = (page.0 as i32 + delta).clamp(0, last) as usize;
+    if next != page.0 {
+        page.0 = next;
+        dirty.0 = true;
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn handle_weapon_slot_buttons(
+    mut interactions: Query<
+        (&Interaction, &mut BackgroundColor, &mut PressLatch, &WeaponSlotButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut active_slot: ResMut<ActiveWeaponSlot>,
+    mut dirty: ResMut<EquipmentUiDirty>,
+) {
+    for (interaction, mut bg, mut latch, btn) in &mut interactions {
+        let just_pressed = latch.just_pressed(*interaction);
+        match *interaction {
+            Interaction::Pressed => {
+                if just_pressed && active_slot.0 != btn.slot {
+                    active_slot.0 = btn.slot;
+                    dirty.0 = true;
+                }
+            }
+            Interaction::Hovered => bg.0 = Color::srgb(0.28, 0.28, 0.40),
+            Interaction::None => bg.0 = weapon_slot_color(active_slot.0 == btn.slot),
+        }
+    }
+}
+
+fn apply_inventory_item_messages(
+    mut commands: Commands,
+    mut reader: MessageReader<UseInventoryItemMsg>,
     db: Res<ItemDatabase>,
     mut dirty: ResMut<EquipmentUiDirty>,
-    mut q: Query<(&mut Inventory, &mut EquippedItems, &mut EquipmentSet), With<Player>>,
+    mut notifications: ResMut<Notifications>,
+    settings: Res<GameSettings>,
+    mut q: Query<
+        (Entity, &mut Inventory, &mut EquippedItems, &mut EquipmentSet, &mut crate::health::Health),
+        With<Player>,
+    >,
 ) {
-    let Ok((mut inv, mut equipped, mut equip_set)) = q.single_mut() else {
+    let Ok((player, mut inv, mut equipped, mut equip_set, mut health)) = q.single_mut() else {
         return;
     };
+    let lang = settings.language;
 
     for m in reader.read() {
-        let new_id = m.item_id;
-        if new_id == equipped.weapon {
+        let Some(id) = inv.slots.get(m.index).and_then(|s| s.as_ref()).map(|s| s.id) else {
             continue;
-        }
+        };
 
-        if inv.try_remove_one(new_id) {
-            let old = equipped.weapon;
-            inv.try_add(old, 1, 99);
-            equipped.weapon = new_id;
-            if let Some(def) = db.weapon(new_id) {
-                *equip_set = EquipmentSet::from_weapon(def);
+        match id.category() {
+            ItemCategory::Weapon => {
+                equip_weapon_from_slot(&mut commands, player, &mut inv, &mut equipped, &mut equip_set, m);
+            }
+            ItemCategory::Consumable => {
+                let Some(def) = db.consumable(id) else {
+                    continue;
+                };
+                // 满血时不浪费
+                if health.current >= health.max {
+                    continue;
+                }
+                inv.remove_one_at(m.index);
+                health.current = (health.current + def.heal).min(health.max);
+                notifications.notify(format!("{}{}", tr(lang, "notify.used"), id.display_name(lang)));
+                dirty.0 = true;
+                continue;
             }
+            ItemCategory::Armor => {
+                let Some(def) = db.armor(id) else {
+                    continue;
+                };
+                inv.remove_one_at(m.index);
+                // 换下来的护甲放回背包：刚腾出的那一格一定放得下
+                if let Some(old) = equipped.armor.replace(id) {
+                    inv.try_add(old, 1, old.max_stack());
+                }
+                commands.entity(player).insert(Armor(def.damage_reduction));
+            }
+        }
+
+        notifications.notify(format!("{}{}", tr(lang, "notify.equipped"), id.display_name(lang)));
+        dirty.0 = true;
+    }
+}
+
+/// 把背包某一格的武器装备到指定武器槽，换下来的武器放回被拿走的那一格
+fn equip_weapon_from_slot(
+    commands: &mut Commands,
+    player: Entity,
+    inv: &mut Inventory,
+    equipped: &mut EquippedItems,
+    equip_set: &mut EquipmentSet,
+    m: &UseInventoryItemMsg,
+) {
+    let Some(slot) = inv.slots.get_mut(m.index) else {
+        return;
+    };
+    let Some(new_item) = slot.as_ref().and_then(|s| s.instance.clone()) else {
+        return;
+    };
+
+    let old = match m.weapon_slot {
+        WeaponSlot::Primary => {
+            *equip_set = EquipmentSet::from_weapon(&new_item.rolled);
+            Some(std::mem::replace(&mut equipped.primary, new_item))
+        }
+        WeaponSlot::Secondary => {
+            commands
+                .entity(player)
+                .insert(SecondaryEquipmentSet(EquipmentSet::from_weapon(&new_item.rolled)));
+            equipped.secondary.replace(new_item)
+        }
+    };
+    *slot = old.map(|item| ItemStack {
+        id: item.id,
+        count: 1,
+        instance: Some(item),
+    });
+}
+
+fn apply_loot_messages(
+    mut reader: MessageReader<LootItemMsg>,
+    mut dirty: ResMut<EquipmentUiDirty>,
+    mut notifications: ResMut<Notifications>,
+    settings: Res<GameSettings>,
+    mut q: Query<&mut Inventory, With<Player>>,
+) {
+    let Ok(mut inv) = q.single_mut() else {
+        return;
+    };
+
+    for m in reader.read() {
+        let name = m.item.id.display_name(settings.language);
+        if inv.try_add_instance(m.item.clone()) {
+            notifications.notify(format!("{}{}", tr(settings.language, "notify.picked_up"), name));
             dirty.0 = true;
         }
     }
 }
 
+/// 从背包物品上开始拖拽时生成跟随指针的图标
+fn start_item_drag(
+    mut commands: Commands,
+    mut drag_start_rx: MessageReader<Pointer<DragStart>>,
+    asset_server: Res<AssetServer>,
+    ui_scale: Res<UiScale>,
+    items_q: Query<&InventoryItemButton>,
+) {
+    for ev in drag_start_rx.read() {
+        if ev.event.button != PointerButton::Primary {
+            continue;
+        }
+        let Ok(item) = items_q.get(ev.entity) else {
+            continue;
+        };
+
+        let pos = ev.pointer_location.position / ui_scale.0 - Vec2::splat(DRAG_GHOST_SIZE * 0.5);
+        commands.spawn((
+            DragGhost,
+            ImageNode {
+                image: asset_server.load(item.item_id.icon_path()),
+                ..default()
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(pos.x),
+                top: Val::Px(pos.y),
+                width: Val::Px(DRAG_GHOST_SIZE),
+                height: Val::Px(DRAG_GHOST_SIZE),
+                ..default()
+            },
+            GlobalZIndex(101),
+            Pickable::IGNORE,
+        ));
+    }
+}
+
+fn move_drag_ghost(
+    mut drag_rx: MessageReader<Pointer<Drag>>,
+    ui_scale: Res<UiScale>,
+    mut ghost_q: Query<&mut Node, With<DragGhost>>,
+) {
+    let Some(ev) = drag_rx.read().last() else {
+        return;
+    };
+    let pos = ev.pointer_location.position / ui_scale.0 - Vec2::splat(DRAG_GHOST_SIZE * 0.5);
+    for mut node in &mut ghost_q {
+        node.left = Val::Px(pos.x);
+        node.top = Val::Px(pos.y);
+    }
+}
+
+/// 背包物品拖到武器槽上松开：装备到该槽，原来的武器由 UseInventoryItemMsg 放回背包
+fn drop_item_on_weapon_slot(
+    mut drop_rx: MessageReader<Pointer<DragDrop>>,
+    items_q: Query<&InventoryItemButton>,
+    slots_q: Query<&WeaponSlotButton>,
+    mut writer: MessageWriter<UseInventoryItemMsg>,
+) {
+    for ev in drop_rx.read() {
+        let (Ok(slot), Ok(item)) = (slots_q.get(ev.entity), items_q.get(ev.event.dropped)) else {
+            continue;
+        };
+        // 武器槽只接受武器
+        if item.item_id.category() != ItemCategory::Weapon {
+            continue;
+        }
+        writer.write(UseInventoryItemMsg {
+            index: item.index,
+            weapon_slot: slot.slot,
+        });
+    }
+}
+
+fn end_item_drag(
+    mut commands: Commands,
+    mut end_rx: MessageReader<Pointer<DragEnd>>,
+    ui_root_q: Query<(), With<EquipmentUiRoot>>,
+    ghost_q: Query<Entity, With<DragGhost>>,
+) {
+    // 面板在拖拽途中被关掉时也一并清理
+    if end_rx.read().count() == 0 && !ui_root_q.is_empty() {
+        return;
+    }
+    for e in &ghost_q {
+        commands.entity(e).despawn();
+    }
+}
+
+fn apply_inventory_expansion(
+    mut reader: MessageReader<ExpandInventoryMsg>,
+    mut dirty: ResMut<EquipmentUiDirty>,
+    mut q: Query<&mut Inventory, With<Player>>,
+) {
+    let Ok(mut inv) = q.single_mut() else {
+        return;
+    };
+
+    for m in reader.read() {
+        inv.grow(m.extra);
+        dirty.0 = true;
+    }
+}
+
+/// 切换语言后重建装备面板，让物品名和标题换成新语言
+fn mark_equipment_ui_dirty_on_language_change(
+    settings: Res<GameSettings>,
+    mut last: Local<Option<Language>>,
+    ui_root_q: Query<(), With<EquipmentUiRoot>>,
+    mut dirty: ResMut<EquipmentUiDirty>,
+) {
+    let lang = settings.language;
+    let changed = last.is_some_and(|prev| prev != lang);
+    *last = Some(lang);
+
+    if changed && !ui_root_q.is_empty() {
+        dirty.0 = true;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn rebuild_equipment_ui_when_dirty(
     dirty: Res<EquipmentUiDirty>,
     ui_root_q: Query<Entity, With<EquipmentUiRoot>>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    db: Res<ItemDatabase>,
-    player_q: Query<(&EquipmentSet, &EquippedItems, &Inventory), With<Player>>,
+    settings: Res<GameSettings>,
+    active_slot: Res<ActiveWeaponSlot>,
+    page: Res<InventoryPage>,
+    player_q: Query<
+        (&EquipmentSet, Option<&SecondaryEquipmentSet>, &EquippedItems, &Inventory),
+        With<Player>,
+    >,
 ) {
     if !dirty.is_changed() || !dirty.0 {
         return;
     }
 
-    let Ok((equip, equipped, inv)) = player_q.single() else {
+    let Ok((equip, secondary, equipped, inv)) = player_q.single() else {
         return;
     };
 
@@ -656,7 +1548,17 @@ fn rebuild_equipment_ui_when_dirty(
         commands.entity(root).try_despawn();
     }
 
-    spawn_player_info_ui(&mut commands, &asset_server, &db, equip, equipped, inv);
+    spawn_player_info_ui(
+        &mut commands,
+        &asset_server,
+        settings.language,
+        equip,
+        secondary,
+        equipped,
+        inv,
+        active_slot.0,
+        page.0,
+    );
 }
 
 fn update_hovered_item(
@@ -666,33 +1568,72 @@ fn update_hovered_item(
     let mut found = None;
     for (interaction, btn) in &q {
         if *interaction == Interaction::Hovered {
-            found = Some(btn.item_id);
+            found = Some(btn.index);
             break;
         }
     }
     hovered.0 = found;
 }
 
+/// 武器的有效射程：近战看挥砍距离，远程看子弹能飞多远
+fn weapon_reach(w: &WeaponDef) -> f32 {
+    match w.kind {
+        WeaponKind::Melee => w.melee_range,
+        WeaponKind::Ranged => w.projectile_speed * w.projectile_lifetime,
+    }
+}
+
+/// 一项数值的差值，后面跟 ^（更好）或 v（更差）；冷却越低越好
+fn stat_delta(hovered: f32, equipped: f32, lower_is_better: bool, precision: usize) -> String {
+    let delta = hovered - equipped;
+    if delta.abs() < 0.005 {
+        return "=".to_string();
+    }
+    let better = (delta < 0.0) == lower_is_better;
+    format!("{delta:+.precision$} {}", if better { "^" } else { "v" })
+}
+
+/// 悬停武器对比目标槽当前武器：伤害、冷却、射程的差值
+fn weapon_comparison(lang: Language, hovered: &WeaponDef, equipped: &WeaponDef) -> String {
+    format!(
+        "{}\nDMG: {}\nCD: {}\nRange: {}",
+        tr(lang, "equip.compare"),
+        stat_delta(hovered.damage, equipped.damage, false, 0),
+        stat_delta(hovered.cooldown, equipped.cooldown, true, 2),
+        stat_delta(weapon_reach(hovered), weapon_reach(equipped), false, 0),
+    )
+}
+
+#[allow(clippy::collapsible_if, clippy::too_many_arguments, clippy::type_complexity)]
 fn update_detail_panel(
     hovered: Res<HoveredItem>,
+    active_slot: Res<ActiveWeaponSlot>,
     db: Res<ItemDatabase>,
+    settings: Res<GameSettings>,
     mut texts: ParamSet<(
         Query<&mut Text, With<ItemDetailText>>,
         Query<&mut Text, With<PlayerAttrText>>,
         Query<&mut Text, With<WeaponDataText>>,
     )>,
-    hp_q: Query<&crate::health::Health, With<Player>>,
-    equip_q: Query<&EquipmentSet, With<Player>>,
+    hp_q: Query<(&crate::health::Health, Option<&PickupRadius>), With<Player>>,
+    equip_q: Query<(&EquipmentSet, Option<&SecondaryEquipmentSet>), With<Player>>,
     equipped_q: Query<&EquippedItems, With<Player>>,
+    inv_q: Query<&Inventory, With<Player>>,
 ) {
+    let lang = settings.language;
     {
         let mut item_q = texts.p0();
         if let Ok(mut t) = item_q.single_mut() {
-            if let Some(item_id) = hovered.0 {
+            let stack = hovered
+                .0
+                .and_then(|idx| inv_q.single().ok().and_then(|inv| inv.slots.get(idx).cloned().flatten()));
+            if let Some(stack) = stack {
                 let mut s = String::new();
-                s.push_str(item_id.display_name());
+                s.push_str(stack.id.display_name(lang));
                 s.push_str("\n\n");
-                if let Some(w) = db.weapon(item_id) {
+                if let Some(item) = &stack.instance {
+                    let w = &item.rolled;
+                    s.push_str(&format!("{}\n", tr(lang, item.rarity.label_key())));
                     s.push_str(&format!(
                         "Type: Weapon\nKind: {:?}\nDMG: {:.0}\nCD: {:.2}\nProjSpd: {:.0}\nProjLife: {:.2}\nMeleeRange: {:.0}\nMeleeWidth: {:.0}",
                         w.kind,
@@ -703,12 +1644,28 @@ fn update_detail_panel(
                         w.melee_range,
                         w.melee_width
                     ));
+                    if w.lifesteal > 0.0 {
+                        s.push_str(&format!("\nLifesteal: {:.0}%", w.lifesteal * 100.0));
+                    }
+                    // 和点击后会被替换掉的那把（当前目标槽）比较；目标槽空着就不比
+                    let current = equipped_q.single().ok().and_then(|eq| match active_slot.0 {
+                        WeaponSlot::Primary => Some(&eq.primary),
+                        WeaponSlot::Secondary => eq.secondary.as_ref(),
+                    });
+                    if let Some(current) = current {
+                        s.push_str("\n\n");
+                        s.push_str(&weapon_comparison(lang, w, &current.rolled));
+                    }
+                } else if let Some(c) = db.consumable(stack.id) {
+                    s.push_str(&format!("Type: Consumable\nHeal: {:.0}\nCount: {}", c.heal, stack.count));
+                } else if let Some(a) = db.armor(stack.id) {
+                    s.push_str(&format!("Type: Armor\nArmor: {:.0}", a.damage_reduction));
                 } else {
-                    s.push_str("No detailed data.");
+                    s.push_str(tr(lang, "equip.no_data"));
                 }
                 t.0 = s;
             } else {
-                t.0 = "Hover an item to see details.".to_string();
+                t.0 = tr(lang, "equip.hover_hint").to_string();
             }
         }
     }
@@ -716,8 +1673,12 @@ fn update_detail_panel(
     {
         let mut attr_q = texts.p1();
         if let Ok(mut t) = attr_q.single_mut() {
-            if let (Ok(hp), Ok(equip)) = (hp_q.single(), equip_q.single()) {
-                t.0 = format!("HP: {:.0}/{:.0}   ATK: {:.0}", hp.current, hp.max, equip.weapon_damage);
+            if let (Ok((hp, radius)), Ok((equip, _))) = (hp_q.single(), equip_q.single()) {
+                let radius = radius.copied().unwrap_or_default().0;
+                t.0 = format!(
+                    "HP: {:.0}/{:.0}   ATK: {:.0}   PICKUP: {:.0}",
+                    hp.current, hp.max, equip.weapon_damage, radius
+                );
             }
         }
     }
@@ -725,14 +1686,8 @@ fn update_detail_panel(
     {
         let mut weapon_q = texts.p2();
         if let Ok(mut t) = weapon_q.single_mut() {
-            if let (Ok(equip), Ok(eq)) = (equip_q.single(), equipped_q.single()) {
-                t.0 = format!(
-                    "Weapon: {}\nDMG: {:.0}\nCD: {:.2}\nRange: {:.0}",
-                    eq.weapon.display_name(),
-                    equip.weapon_damage,
-                    equip.weapon_attack_cooldown,
-                    equip.melee_range
-                );
+            if let (Ok((equip, secondary)), Ok(eq)) = (equip_q.single(), equipped_q.single()) {
+                t.0 = weapon_data_text(lang, eq, equip, secondary);
             }
         }
     }
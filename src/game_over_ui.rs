@@ -1,8 +1,14 @@
 use bevy::prelude::*;
 use bevy::ui::Val;
 
-use crate::save::{refresh_save_slots_from_disk, CurrentSlot, LoadSlotEvent, PendingLoad, SaveSlots};
+use crate::save::{refresh_save_slots_from_disk, CurrentSlot, LoadSlotEvent, PendingLoad, SaveDir, SaveSlots};
 use crate::state::GameState;
+use crate::transition::TransitionRequest;
+use crate::i18n::{localized, tr, Language};
+use crate::meta_progress::{award_meta_currency, LastRunShards};
+use crate::new_run::ReloadPending;
+use crate::run_stats::{format_run_time, record_high_score, HighScores, LastRunRank, RunStats};
+use crate::ui::types::GameSettings;
 
 use crate::enemy::Enemy;
 
@@ -28,7 +34,10 @@ impl Plugin for GameOverUiPlugin {
             OnEnter(GameState::GameOver),
             (
                 reset_after_game_over,
-                setup_game_over_ui.after(reset_after_game_over),
+                setup_game_over_ui
+                    .after(reset_after_game_over)
+                    .after(record_high_score)
+                    .after(award_meta_currency),
             ),
         )
         .add_systems(OnExit(GameState::GameOver), cleanup_game_over_ui)
@@ -46,6 +55,7 @@ impl Plugin for GameOverUiPlugin {
 fn reset_after_game_over(
     mut commands: Commands,
     mut slots: ResMut<SaveSlots>,
+    save_dir: Res<SaveDir>,
     mut pending: ResMut<PendingLoad>,
     mut current: ResMut<CurrentSlot>,
     enemies: Query<Entity, With<Enemy>>,
@@ -59,16 +69,26 @@ fn reset_after_game_over(
     pending.file_name = None;
     current.file_name = None;
 
-    // 刷新存档列表（从 ./saves 扫描）
-    refresh_save_slots_from_disk(&mut slots);
+    // 刷新存档列表（从存档目录扫描）
+    refresh_save_slots_from_disk(&mut slots, &save_dir);
 }
 
-fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, slots: Res<SaveSlots>) {
+#[allow(clippy::too_many_arguments)]
+fn setup_game_over_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    slots: Res<SaveSlots>,
+    settings: Res<GameSettings>,
+    stats: Res<RunStats>,
+    scores: Res<HighScores>,
+    rank: Res<LastRunRank>,
+    shards: Res<LastRunShards>,
+) {
     let font: Handle<Font> = asset_server.load("fonts/YuFanLixing.otf");
+    let lang = settings.language;
 
-    // 只显示手动存档
+    // 只显示手动存档（SaveSlots 已按时间从新到旧排好）
     let mut manual_slots: Vec<_> = slots.slots.iter().filter(|s| !s.is_auto).collect();
-    manual_slots.reverse();
     manual_slots.truncate(8);
 
     commands
@@ -91,7 +111,10 @@ fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, sl
             parent
                 .spawn((
                     Node {
-                        width: Val::Px(720.0),
+                        width: Val::Percent(90.0),
+                        max_width: Val::Px(720.0),
+                        max_height: Val::Percent(92.0),
+                        overflow: Overflow::scroll_y(),
                         padding: UiRect::all(Val::Px(26.0)),
                         flex_direction: FlexDirection::Column,
                         row_gap: Val::Px(14.0),
@@ -103,7 +126,7 @@ fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, sl
                 ))
                 .with_children(|panel| {
                     panel.spawn((
-                        Text::new("游戏失败"),
+                        localized(lang, "game_over.title"),
                         TextFont {
                             font: font.clone(),
                             font_size: 40.0,
@@ -113,7 +136,7 @@ fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, sl
                     ));
 
                     panel.spawn((
-                        Text::new("请选择一个【手动存档】重新开始（不会使用自动存档）"),
+                        localized(lang, "game_over.hint"),
                         TextFont {
                             font: font.clone(),
                             font_size: 18.0,
@@ -122,6 +145,78 @@ fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, sl
                         TextColor(Color::srgba(1.0, 1.0, 1.0, 0.85)),
                     ));
 
+                    // 本局成绩
+                    panel.spawn((
+                        Text::new(stats_line(lang, stats.time, stats.kills, stats.waves, stats.score())),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 0.85, 0.4)),
+                    ));
+                    panel.spawn((
+                        Text::new(format!("{}{}", tr(lang, "game_over.shards_earned"), shards.0)),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.6, 0.85, 1.0)),
+                    ));
+                    if rank.0.is_some() {
+                        panel.spawn((
+                            localized(lang, "game_over.new_record"),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 18.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.5, 1.0, 0.5)),
+                        ));
+                    }
+
+                    // 排行榜，本局那一行高亮
+                    panel
+                        .spawn(Node {
+                            width: Val::Px(640.0),
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(4.0),
+                            ..default()
+                        })
+                        .with_children(|board| {
+                            board.spawn((
+                                localized(lang, "game_over.high_scores"),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 20.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                            for (i, entry) in scores.entries.iter().enumerate() {
+                                let color = if rank.0 == Some(i) {
+                                    Color::srgb(0.5, 1.0, 0.5)
+                                } else {
+                                    Color::srgba(1.0, 1.0, 1.0, 0.8)
+                                };
+                                board.spawn((
+                                    Text::new(format!(
+                                        "{}. {}  ({})",
+                                        i + 1,
+                                        stats_line(lang, entry.time, entry.kills, entry.waves, entry.score),
+                                        entry.date
+                                    )),
+                                    TextFont {
+                                        font: font.clone(),
+                                        font_size: 16.0,
+                                        ..default()
+                                    },
+                                    TextColor(color),
+                                ));
+                            }
+                        });
+
                     // 存档列表容器
                     panel
                         .spawn((
@@ -136,7 +231,7 @@ fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, sl
                         .with_children(|list| {
                             if manual_slots.is_empty() {
                                 list.spawn((
-                                    Text::new("暂无手动存档：请先在游戏内打开“存档面板”进行手动保存。"),
+                                    localized(lang, "game_over.no_saves"),
                                     TextFont {
                                         font: font.clone(),
                                         font_size: 18.0,
@@ -163,7 +258,7 @@ fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, sl
                                     ))
                                     .with_children(|btn| {
                                         btn.spawn((
-                                            Text::new(format!("存档：{}", s.display_name)),
+                                            Text::new(format!("{}{}", tr(lang, "game_over.save_prefix"), s.display_name)),
                                             TextFont {
                                                 font: font.clone(),
                                                 font_size: 18.0,
@@ -172,7 +267,7 @@ fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, sl
                                             TextColor(Color::WHITE),
                                         ));
                                         btn.spawn((
-                                            Text::new("加载并重新开始"),
+                                            localized(lang, "game_over.load_restart"),
                                             TextFont {
                                                 font: font.clone(),
                                                 font_size: 16.0,
@@ -210,7 +305,7 @@ fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, sl
                             ))
                             .with_children(|btn| {
                                 btn.spawn((
-                                    Text::new("返回标题界面"),
+                                    localized(lang, "game_over.back_title"),
                                     TextFont {
                                         font: font.clone(),
                                         font_size: 20.0,
@@ -224,14 +319,31 @@ fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, sl
         });
 }
 
-fn handle_manual_save_slot_buttons(
+fn stats_line(lang: Language, time: f32, kills: u32, waves: u32, score: u32) -> String {
+    format!(
+        "{} {}   {} {}   {} {}   {} {}",
+        tr(lang, "stats.time"),
+        format_run_time(time),
+        tr(lang, "stats.kills"),
+        kills,
+        tr(lang, "stats.waves"),
+        waves,
+        tr(lang, "stats.score"),
+        score
+    )
+}
+
+/// 失败界面读档：和暂停中读档一样打上 `ReloadPending`，回到 InGame 时重置本局的金币、任务、难度和复活点
+#[allow(clippy::type_complexity)]
+pub(crate) fn handle_manual_save_slot_buttons(
     mut commands: Commands,
     mut q: Query<
         (&Interaction, &mut BackgroundColor, &ManualSaveSlotButton),
         (Changed<Interaction>, With<Button>),
     >,
     mut load_tx: MessageWriter<LoadSlotEvent>,
-    mut next_state: ResMut<NextState<GameState>>,
+    mut transition_tx: MessageWriter<TransitionRequest>,
+    mut reload: ResMut<ReloadPending>,
     enemies: Query<Entity, With<Enemy>>,
 ) {
     for (interaction, mut bg, btn) in &mut q {
@@ -246,7 +358,8 @@ fn handle_manual_save_slot_buttons(
                 load_tx.write(LoadSlotEvent {
                     file_name: btn.file_name.clone(),
                 });
-                next_state.set(GameState::InGame);
+                reload.0 = true;
+                transition_tx.write(TransitionRequest(GameState::InGame));
             }
             Interaction::Hovered => bg.0 = Color::srgb(0.6, 0.6, 0.8),
             Interaction::None => bg.0 = Color::srgb(0.25, 0.25, 0.35),
@@ -254,8 +367,9 @@ fn handle_manual_save_slot_buttons(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn handle_game_over_buttons(
-    mut next_state: ResMut<NextState<GameState>>,
+    mut transition_tx: MessageWriter<TransitionRequest>,
     mut q: Query<(&Interaction, &GameOverButton), (Changed<Interaction>, With<Button>)>,
 ) {
     for (interaction, button) in &mut q {
@@ -263,7 +377,9 @@ fn handle_game_over_buttons(
             continue;
         }
         match button {
-            GameOverButton::BackToMainMenu => next_state.set(GameState::MainMenu),
+            GameOverButton::BackToMainMenu => {
+                transition_tx.write(TransitionRequest(GameState::MainMenu));
+            }
         }
     }
 }
@@ -1,5 +1,7 @@
+use crate::checkpoint::{Checkpoint, Downed, ReviveCharges};
 use crate::movement::Player;
 use crate::state::GameState;
+use crate::transition::TransitionRequest;
 use bevy::prelude::*;
 
 #[derive(Component)]
@@ -14,26 +16,136 @@ impl Health {
     }
 }
 
+/// 玩家受到伤害：from 为伤害来源的世界坐标，amount 为伤害值。
+/// 所有对玩家的伤害都只发这个消息，由 `apply_player_damage` 统一结算。
+#[derive(Message, Clone, Copy, Debug)]
+pub struct PlayerDamaged {
+    pub from: Vec2,
+    pub amount: f32,
+}
+
+/// 护甲：每次受到的伤害减去固定值（至少保留 1 点）
+#[derive(Component, Default)]
+pub struct Armor(pub f32);
+
+/// 荆棘：被敌人接触伤害时，把该比例的伤害反弹给这个敌人；由荆棘遗物提供
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Thorns(pub f32);
+
+/// 护盾：受伤时先扣护盾再扣血，随时间衰减，耗尽后移除
+#[derive(Component)]
+pub struct Shield {
+    pub amount: f32,
+}
+
+/// 护盾每秒衰减量
+const SHIELD_DECAY_PER_SEC: f32 = 4.0;
+/// 护盾叠加上限
+pub const SHIELD_MAX: f32 = 60.0;
+
+/// 受击后的无敌时间（剩余秒数）
+#[derive(Component, Default)]
+pub struct Invulnerable(pub f32);
+
+/// 每次受伤后获得的无敌时间（秒）
+const PLAYER_IFRAME_SECS: f32 = 0.25;
+
 pub struct HealthPlugin;
 
 impl Plugin for HealthPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.add_message::<PlayerDamaged>().add_systems(
             Update,
-            check_player_death.run_if(in_state(GameState::InGame)),
+            (decay_shield, apply_player_damage, check_player_death)
+                .chain()
+                .run_if(in_state(GameState::InGame)),
         );
     }
 }
 
+/// 给玩家加护盾：已有护盾时叠加（不超过上限）
+pub fn grant_shield(commands: &mut Commands, player: Entity, existing: Option<&mut Shield>, amount: f32) {
+    match existing {
+        Some(shield) => shield.amount = (shield.amount + amount).min(SHIELD_MAX),
+        None => {
+            commands.entity(player).insert(Shield { amount: amount.min(SHIELD_MAX) });
+        }
+    }
+}
+
+fn decay_shield(time: Res<Time>, mut commands: Commands, mut query: Query<(Entity, &mut Shield)>) {
+    for (entity, mut shield) in &mut query {
+        shield.amount -= SHIELD_DECAY_PER_SEC * time.delta_secs();
+        if shield.amount <= 0.0 {
+            commands.entity(entity).remove::<Shield>();
+        }
+    }
+}
+
+#[allow(clippy::collapsible_if, clippy::type_complexity)]
+fn apply_player_damage(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut damaged_rx: MessageReader<PlayerDamaged>,
+    mut query: Query<
+        (Entity, &mut Health, Option<&Armor>, Option<&mut Invulnerable>, Option<&mut Shield>),
+        With<Player>,
+    >,
+) {
+    let Ok((entity, mut health, armor, mut iframes, mut shield)) = query.single_mut() else {
+        damaged_rx.clear();
+        return;
+    };
+
+    if let Some(iframes) = iframes.as_mut() {
+        iframes.0 = (iframes.0 - time.delta_secs()).max(0.0);
+    }
+
+    for ev in damaged_rx.read() {
+        if iframes.as_ref().is_some_and(|i| i.0 > 0.0) {
+            continue;
+        }
+
+        let reduction = armor.map(|a| a.0).unwrap_or(0.0);
+        let mut amount = (ev.amount - reduction).max(1.0);
+
+        // 先由护盾吸收，剩下的才扣血
+        if let Some(shield) = shield.as_mut() {
+            if shield.amount > 0.0 {
+                let absorbed = amount.min(shield.amount);
+                shield.amount -= absorbed;
+                amount -= absorbed;
+                if shield.amount <= 0.0 {
+                    commands.entity(entity).remove::<Shield>();
+                }
+            }
+        }
+
+        health.current = (health.current - amount).max(0.0);
+
+        if let Some(iframes) = iframes.as_mut() {
+            iframes.0 = PLAYER_IFRAME_SECS;
+        }
+    }
+}
+
+/// 倒下时有复活点就进入复活面板，否则直接 GameOver
+#[allow(clippy::collapsible_if, clippy::type_complexity)]
 fn check_player_death(
     mut commands: Commands,
-    mut next_state: ResMut<NextState<GameState>>,
-    query: Query<(Entity, &Health), With<Player>>,
+    mut transition_tx: MessageWriter<TransitionRequest>,
+    checkpoint: Res<Checkpoint>,
+    charges: Res<ReviveCharges>,
+    query: Query<(Entity, &Health), (With<Player>, Without<Downed>)>,
 ) {
     if let Some((entity, health)) = query.iter().next() {
         if health.current <= 0.0 {
-            commands.entity(entity).despawn();
-            next_state.set(GameState::GameOver);
+            if charges.can_revive(&checkpoint) {
+                commands.entity(entity).insert(Downed);
+            } else {
+                commands.entity(entity).despawn();
+                transition_tx.write(TransitionRequest(GameState::GameOver));
+            }
         }
     }
 }
@@ -0,0 +1,141 @@
+// src/hit_stop.rs
+//! 顿帧：暴击、斩击命中时把 Time<Virtual> 的速度压到接近 0，持续几帧后恢复。
+//! 倒计时用 Time<Real>，所以顿帧期间输入和 UI（提示用 Real 计时）不受影响。
+//! 同一处也负责击杀慢动作：场上最后一个敌人倒下时先压低时间流速，再在约 1 秒内缓回正常。
+//! 设置里的 "游戏速度" 是基础倍率，顿帧和慢动作都乘在它上面。
+
+use bevy::prelude::*;
+
+use crate::combat::cleanup_dead_enemies;
+use crate::combat_core::{DamageDealt, EnemyDied};
+use crate::enemy::Enemy;
+use crate::health::Health;
+use crate::state::GameState;
+use crate::ui::types::{GameSettings, GAME_SPEED_MAX, GAME_SPEED_MIN};
+
+/// 顿帧期间的虚拟时间倍率
+const HIT_STOP_SPEED: f32 = 0.05;
+/// 暴击顿帧时长（秒，真实时间）
+pub const CRIT_HIT_STOP_SECS: f32 = 0.05;
+/// 斩击技能顿帧时长
+pub const SLASH_HIT_STOP_SECS: f32 = 0.07;
+/// 慢动作最低的虚拟时间倍率
+const SLOW_MO_SPEED: f32 = 0.25;
+/// 保持最低速度的时长，之后在 SLOW_MO_RAMP_SECS 内线性恢复（均为真实时间）
+const SLOW_MO_HOLD_SECS: f32 = 0.35;
+const SLOW_MO_RAMP_SECS: f32 = 1.0;
+
+#[derive(Resource, Default)]
+pub struct HitStop {
+    pub remaining: f32,
+}
+
+impl HitStop {
+    /// 触发顿帧；已在顿帧中时取较长的那个，不叠加
+    pub fn trigger(&mut self, secs: f32) {
+        self.remaining = self.remaining.max(secs);
+    }
+}
+
+/// 击杀慢动作的剩余时间（真实时间秒），0 表示未触发
+#[derive(Resource, Default)]
+pub struct KillSlowMo {
+    pub remaining: f32,
+}
+
+impl KillSlowMo {
+    fn trigger(&mut self) {
+        self.remaining = SLOW_MO_HOLD_SECS + SLOW_MO_RAMP_SECS;
+    }
+
+    /// 当前应有的时间倍率：先保持最低速度，再线性回到 1
+    fn speed(&self) -> f32 {
+        if self.remaining <= 0.0 {
+            return 1.0;
+        }
+        let ramp = (self.remaining / SLOW_MO_RAMP_SECS).min(1.0);
+        SLOW_MO_SPEED + (1.0 - SLOW_MO_SPEED) * (1.0 - ramp)
+    }
+}
+
+pub struct HitStopPlugin;
+
+impl Plugin for HitStopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HitStop>()
+            .init_resource::<KillSlowMo>()
+            .add_systems(PreUpdate, tick_hit_stop)
+            .add_systems(
+                Update,
+                (
+                    trigger_hit_stop_on_crit,
+                    trigger_slow_mo_on_last_kill.after(cleanup_dead_enemies),
+                )
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnExit(GameState::InGame), clear_hit_stop);
+    }
+}
+
+fn trigger_hit_stop_on_crit(mut hit_stop: ResMut<HitStop>, mut dealt_rx: MessageReader<DamageDealt>) {
+    if dealt_rx.read().any(|ev| ev.crit) {
+        hit_stop.trigger(CRIT_HIT_STOP_SECS);
+    }
+}
+
+/// 这一帧的击杀让场上一个活着的敌人都不剩时，触发慢动作
+fn trigger_slow_mo_on_last_kill(
+    settings: Res<GameSettings>,
+    mut slow_mo: ResMut<KillSlowMo>,
+    mut died_rx: MessageReader<EnemyDied>,
+    enemies_q: Query<(Entity, &Health), With<Enemy>>,
+) {
+    let died: Vec<Entity> = died_rx.read().map(|ev| ev.entity).collect();
+    if died.is_empty() || !settings.kill_slow_mo {
+        return;
+    }
+
+    let any_left = enemies_q.iter().any(|(e, hp)| hp.current > 0.0 && !died.contains(&e));
+    if !any_left {
+        slow_mo.trigger();
+    }
+}
+
+/// 最终速度 = 设置里的游戏速度（只在 InGame 生效）× 顿帧 / 慢动作倍率
+fn tick_hit_stop(
+    real: Res<Time<Real>>,
+    settings: Res<GameSettings>,
+    state: Res<State<GameState>>,
+    mut hit_stop: ResMut<HitStop>,
+    mut slow_mo: ResMut<KillSlowMo>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if hit_stop.remaining > 0.0 {
+        hit_stop.remaining = (hit_stop.remaining - real.delta_secs()).max(0.0);
+    }
+    if slow_mo.remaining > 0.0 {
+        slow_mo.remaining = (slow_mo.remaining - real.delta_secs()).max(0.0);
+    }
+
+    let base = if *state.get() == GameState::InGame {
+        settings.game_speed.clamp(GAME_SPEED_MIN, GAME_SPEED_MAX)
+    } else {
+        1.0
+    };
+    // 顿帧优先于慢动作
+    let effect = if hit_stop.remaining > 0.0 { HIT_STOP_SPEED } else { slow_mo.speed() };
+    let speed = base * effect;
+    if virtual_time.relative_speed() != speed {
+        virtual_time.set_relative_speed(speed);
+    }
+}
+
+fn clear_hit_stop(
+    mut hit_stop: ResMut<HitStop>,
+    mut slow_mo: ResMut<KillSlowMo>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    hit_stop.remaining = 0.0;
+    slow_mo.remaining = 0.0;
+    virtual_time.set_relative_speed(1.0);
+}
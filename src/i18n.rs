@@ -0,0 +1,268 @@
+// src/i18n.rs
+//! 中英文本表：界面文字按 key 查表。
+//! 静态文字挂 `LocalizedText`，切换语言时由 `sync_localized_texts` 统一刷新；
+//! 拼接出来的动态文字由各自的同步系统用 `tr` 重新生成。
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ui::types::GameSettings;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    Chinese,
+    English,
+}
+
+impl Language {
+    /// 语言名始终用该语言本身显示
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::Chinese => "中文",
+            Language::English => "English",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Language::Chinese => Language::English,
+            Language::English => Language::Chinese,
+        }
+    }
+}
+
+/// 带这个组件的 Text 会在语言切换时自动换成对应文本
+#[derive(Component, Clone, Copy)]
+pub struct LocalizedText(pub &'static str);
+
+/// 生成一段会随语言切换的静态文字
+pub fn localized(lang: Language, key: &'static str) -> (Text, LocalizedText) {
+    (Text::new(tr(lang, key)), LocalizedText(key))
+}
+
+/// 查表；没有收录的 key 原样返回，方便发现漏翻
+pub fn tr(lang: Language, key: &'static str) -> &'static str {
+    let (zh, en) = match key {
+        // 主菜单 / 暂停菜单
+        "menu.continue" => ("继续", "Continue"),
+        "menu.start" => ("开始游戏", "Start Game"),
+        "menu.saves" => ("存档", "Saves"),
+        "menu.settings" => ("设置", "Settings"),
+        "menu.upgrades" => ("强化", "Upgrades"),
+        "upgrades.title" => ("永久强化", "Permanent Upgrades"),
+        "upgrades.shards" => ("魂晶", "Shards"),
+        "upgrades.max_health" => ("最大生命 +10", "Max HP +10"),
+        "upgrades.damage" => ("伤害 +8%", "Damage +8%"),
+        "upgrades.move_speed" => ("移速 +5%", "Move speed +5%"),
+        "upgrades.buy" => ("购买", "Buy"),
+        "upgrades.maxed" => ("已满级", "Maxed"),
+        "game_over.shards_earned" => ("获得魂晶：", "Shards earned: "),
+        "menu.quit" => ("退出", "Quit"),
+        "pause.title" => ("已暂停", "Paused"),
+        "pause.resume" => ("继续游戏", "Resume"),
+        "pause.main_menu" => ("返回主菜单", "Main Menu"),
+        "pause.reload" => ("重新读取存档", "Reload Save"),
+
+        // 通用
+        "common.back" => ("返回", "Back"),
+        "common.toggle" => ("切换", "Toggle"),
+        "common.on" => ("开", "On"),
+        "common.off" => ("关", "Off"),
+
+        // 设置
+        "settings.title" => ("设置", "Settings"),
+        "settings.resolution" => ("分辨率", "Resolution"),
+        "settings.display_mode" => ("显示模式", "Display Mode"),
+        "settings.ui_scale" => ("界面缩放", "UI Scale"),
+        "settings.screen_shake" => ("屏幕震动", "Screen Shake"),
+        "settings.aim_assist" => ("辅助瞄准", "Aim Assist"),
+        "settings.kill_slow_mo" => ("终结慢动作", "Kill Slow-Mo"),
+        "settings.face_mode" => ("角色朝向", "Facing"),
+        "settings.game_speed" => ("游戏速度", "Game Speed"),
+        "settings.colorblind" => ("色盲模式", "Colorblind Mode"),
+        "settings.tutorial" => ("新手提示", "Controls Tutorial"),
+        "settings.vsync" => ("垂直同步", "VSync"),
+        "settings.fps_cap" => ("帧率上限", "FPS Cap"),
+        "settings.fps_unlimited" => ("不限", "Unlimited"),
+        "settings.volume" => ("音量", "Volume"),
+        "settings.pause_on_focus_loss" => ("失焦时暂停", "Pause on Focus Loss"),
+        "settings.difficulty" => ("难度", "Difficulty"),
+        "settings.language" => ("语言", "Language"),
+        "settings.keybindings" => ("键位", "Controls"),
+        "settings.apply" => ("应用", "Apply"),
+        "difficulty.easy" => ("简单", "Easy"),
+        "difficulty.normal" => ("普通", "Normal"),
+        "difficulty.hard" => ("困难", "Hard"),
+        "face_mode.movement" => ("跟随移动", "Movement"),
+        "face_mode.cursor" => ("朝向鼠标", "Cursor"),
+        "display.windowed" => ("窗口", "Windowed"),
+        "display.borderless" => ("无边框全屏", "Borderless"),
+        "display.fullscreen" => ("独占全屏", "Fullscreen"),
+
+        // 键位
+        "keys.title" => ("键位设置", "Key Bindings"),
+        "keys.rebind" => ("重新绑定", "Rebind"),
+        "keys.reset" => ("恢复默认", "Reset"),
+        "keys.press_key" => ("请按下按键...", "Press a key..."),
+        "keys.conflict" => ("键位冲突，已互换：", "Key conflict, swapped: "),
+        "action.move_up" => ("向上移动", "Move Up"),
+        "action.move_down" => ("向下移动", "Move Down"),
+        "action.move_left" => ("向左移动", "Move Left"),
+        "action.move_right" => ("向右移动", "Move Right"),
+        "action.sprint" => ("冲刺跑", "Sprint"),
+        "action.dash" => ("闪避", "Dash"),
+        "action.interact" => ("交互", "Interact"),
+        "action.skill_1" => ("技能 1", "Skill 1"),
+        "action.skill_2" => ("技能 2", "Skill 2"),
+        "action.skill_3" => ("技能 3", "Skill 3"),
+        "action.toggle_equipment" => ("装备面板", "Equipment"),
+        "action.cycle_target" => ("切换锁定目标", "Cycle Target"),
+
+        // HUD
+        "hud.threat_level" => ("威胁等级", "Threat Level"),
+
+        // 任务
+        "quest.title" => ("任务", "Quest"),
+        "quest.kill_enemies" => ("击杀敌人", "Kill enemies"),
+        "quest.kill_elites" => ("击杀精英", "Kill elites"),
+        "quest.survive" => ("存活（秒）", "Survive (s)"),
+        "quest.threat_level" => ("达到威胁等级", "Reach threat level"),
+
+        // 新手操作说明
+        "tutorial.title" => ("操作说明", "Controls"),
+        "tutorial.move" => ("移动", "Move"),
+        "tutorial.attack" => ("攻击", "Attack"),
+        "tutorial.secondary" => ("副武器", "Secondary Weapon"),
+        "tutorial.skills" => ("技能", "Skills"),
+        "tutorial.pause" => ("暂停", "Pause"),
+        "tutorial.mouse_left" => ("鼠标左键", "Left Click"),
+        "tutorial.mouse_right" => ("鼠标右键", "Right Click"),
+        "tutorial.dismiss" => ("知道了", "Got it"),
+
+        // 存档面板
+        "save.title" => ("存档", "Saves"),
+        "save.manual_save" => ("手动保存", "Save Game"),
+        "save.load_selected" => ("载入选中存档", "Load Selected"),
+        "save.empty" => ("暂无存档（请先手动保存一次）", "No saves yet (save manually first)"),
+        "save.auto_suffix" => ("(自动)", "(auto)"),
+
+        // 失败界面
+        "game_over.title" => ("游戏失败", "Game Over"),
+        "game_over.hint" => (
+            "请选择一个【手动存档】重新开始（不会使用自动存档）",
+            "Pick a manual save to restart (autosaves are not used)",
+        ),
+        "game_over.no_saves" => (
+            "暂无手动存档：请先在游戏内打开“存档面板”进行手动保存。",
+            "No manual saves: open the save panel in game and save manually first.",
+        ),
+        "game_over.save_prefix" => ("存档：", "Save: "),
+        "game_over.load_restart" => ("加载并重新开始", "Load & Restart"),
+        "game_over.back_title" => ("返回标题界面", "Back to Title"),
+        "game_over.high_scores" => ("排行榜", "High Scores"),
+        "game_over.new_record" => ("进入排行榜！", "New high score!"),
+        "stats.time" => ("存活", "Time"),
+        "stats.kills" => ("击杀", "Kills"),
+        "stats.waves" => ("波次", "Waves"),
+        "stats.score" => ("得分", "Score"),
+
+        // 地图加载失败
+        "map_error.title" => ("无法加载地图", "Failed to load map"),
+        "map_error.hint" => (
+            "请检查 assets/world.ldtk 是否存在且完整",
+            "Check that assets/world.ldtk exists and is valid",
+        ),
+
+        // 复活
+        "revive.title" => ("你倒下了", "You Fell"),
+        "revive.charges" => ("剩余复活次数：", "Revives left: "),
+        "revive.at_checkpoint" => ("在检查点复活", "Revive at Checkpoint"),
+        "revive.back_title" => ("返回标题", "Back to Title"),
+
+        // 提示
+        "notify.saved" => ("已保存：", "Saved: "),
+        "notify.save_failed" => ("保存失败：", "Save failed: "),
+        "notify.reloaded" => ("已读取存档：", "Loaded save: "),
+        "notify.no_save" => ("没有可读取的存档", "No save to load"),
+        "notify.autosaved" => ("已自动保存", "Autosaved"),
+        "notify.checkpoint" => ("已激活检查点", "Checkpoint reached"),
+        "notify.equipped" => ("装备：", "Equipped: "),
+        "notify.picked_up" => ("拾取：", "Picked up: "),
+        "notify.used" => ("使用：", "Used: "),
+        "notify.inventory_expanded" => ("背包扩展：格子 +", "Inventory expanded: slots +"),
+        "notify.pickup_radius" => ("拾取范围提升：", "Pickup radius: "),
+        "notify.buff" => ("获得增益：", "Buff: "),
+        "buff.berserk" => ("狂暴", "Berserk"),
+        "buff.haste" => ("疾行", "Haste"),
+        "notify.thorns" => ("荆棘：反弹接触伤害 ", "Thorns: reflecting contact damage "),
+        "notify.quest_complete" => ("任务完成：", "Quest complete: "),
+        "notify.inventory_full" => ("背包已满", "Inventory full"),
+        "notify.shop_no_gold" => ("金币不足", "Not enough gold"),
+        "notify.shop_bought" => ("购买：", "Bought: "),
+        "notify.shop_sold" => ("卖出：", "Sold: "),
+        "hud.gold" => ("金币", "Gold"),
+        "hud.enemies" => ("敌人", "Enemies"),
+        "feed.kill" => ("击杀", "Killed"),
+        "feed.enemy" => ("敌人", "enemy"),
+        "feed.elite" => ("精英", "elite"),
+        "shop.title" => ("商店", "Shop"),
+        "shop.buy" => ("购买", "Buy"),
+        "shop.sell" => ("出售", "Sell"),
+        "shop.nothing_to_sell" => ("背包是空的", "Nothing to sell"),
+        "shop.leave" => ("离开", "Leave"),
+
+        // 装备面板
+        "equip.inventory" => ("背包", "Inventory"),
+        "equip.page" => ("页", "Page"),
+        "equip.player" => ("角色", "Player"),
+        "equip.details" => ("物品详情", "Item Details"),
+        "equip.hover_hint" => ("将鼠标悬停在物品上查看详情。", "Hover an item to see details."),
+        "equip.no_data" => ("暂无详细数据。", "No detailed data."),
+        "equip.compare" => ("对比当前装备：", "Vs. equipped:"),
+        "equip.close" => ("关闭", "Close"),
+        "equip.primary" => ("主武器", "Primary"),
+        "equip.secondary" => ("副武器", "Secondary"),
+        "equip.armor" => ("护甲", "Armor"),
+        "item.rusty_sword" => ("生锈短剑", "Rusty Sword"),
+        "item.magic_wand" => ("法杖", "Magic Wand"),
+        "item.hunter_bow" => ("猎弓", "Hunter Bow"),
+        "item.health_potion" => ("治疗药水", "Health Potion"),
+        "item.leather_armor" => ("皮甲", "Leather Armor"),
+        "rarity.common" => ("普通", "Common"),
+        "rarity.rare" => ("稀有", "Rare"),
+        "rarity.epic" => ("史诗", "Epic"),
+
+        _ => return key,
+    };
+
+    match lang {
+        Language::Chinese => zh,
+        Language::English => en,
+    }
+}
+
+pub struct I18nPlugin;
+
+impl Plugin for I18nPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, sync_localized_texts);
+    }
+}
+
+/// 语言变化时刷新所有静态文字
+fn sync_localized_texts(
+    settings: Res<GameSettings>,
+    mut last: Local<Option<Language>>,
+    mut q: Query<(&mut Text, &LocalizedText)>,
+) {
+    let lang = settings.language;
+    if *last == Some(lang) {
+        return;
+    }
+    *last = Some(lang);
+
+    for (mut text, localized) in &mut q {
+        text.0 = tr(lang, localized.0).to_string();
+    }
+}
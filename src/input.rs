@@ -1,23 +1,178 @@
+use crate::checkpoint::Downed;
 use crate::state::GameState;
+use crate::ui::types::GameSettings;
 use bevy::prelude::*;
+use bevy::window::WindowFocused;
+use serde::{Deserialize, Serialize};
 
 pub struct InputPlugin;
 
 #[derive(Resource, Default)]
 pub struct MovementInput(pub Vec2);
 
+/// 可重新绑定的操作
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Sprint,
+    Dash,
+    Interact,
+    Skill1,
+    Skill2,
+    Skill3,
+    ToggleEquipment,
+    CycleTarget,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 12] = [
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::Sprint,
+        InputAction::Dash,
+        InputAction::Interact,
+        InputAction::Skill1,
+        InputAction::Skill2,
+        InputAction::Skill3,
+        InputAction::ToggleEquipment,
+        InputAction::CycleTarget,
+    ];
+
+    /// 文本表 key
+    pub fn label_key(self) -> &'static str {
+        match self {
+            InputAction::MoveUp => "action.move_up",
+            InputAction::MoveDown => "action.move_down",
+            InputAction::MoveLeft => "action.move_left",
+            InputAction::MoveRight => "action.move_right",
+            InputAction::Sprint => "action.sprint",
+            InputAction::Dash => "action.dash",
+            InputAction::Interact => "action.interact",
+            InputAction::Skill1 => "action.skill_1",
+            InputAction::Skill2 => "action.skill_2",
+            InputAction::Skill3 => "action.skill_3",
+            InputAction::ToggleEquipment => "action.toggle_equipment",
+            InputAction::CycleTarget => "action.cycle_target",
+        }
+    }
+}
+
+/// 键位表：随设置一起保存到 settings.json
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub move_up: KeyCode,
+    pub move_down: KeyCode,
+    pub move_left: KeyCode,
+    pub move_right: KeyCode,
+    pub sprint: KeyCode,
+    pub dash: KeyCode,
+    pub interact: KeyCode,
+    pub skill_1: KeyCode,
+    pub skill_2: KeyCode,
+    pub skill_3: KeyCode,
+    pub toggle_equipment: KeyCode,
+    /// 轮换辅助瞄准的锁定目标
+    pub cycle_target: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            move_up: KeyCode::KeyW,
+            move_down: KeyCode::KeyS,
+            move_left: KeyCode::KeyA,
+            move_right: KeyCode::KeyD,
+            sprint: KeyCode::ShiftLeft,
+            dash: KeyCode::ControlLeft,
+            interact: KeyCode::KeyE,
+            skill_1: KeyCode::Digit1,
+            skill_2: KeyCode::Digit2,
+            skill_3: KeyCode::Digit3,
+            toggle_equipment: KeyCode::KeyB,
+            cycle_target: KeyCode::Tab,
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn get(&self, action: InputAction) -> KeyCode {
+        match action {
+            InputAction::MoveUp => self.move_up,
+            InputAction::MoveDown => self.move_down,
+            InputAction::MoveLeft => self.move_left,
+            InputAction::MoveRight => self.move_right,
+            InputAction::Sprint => self.sprint,
+            InputAction::Dash => self.dash,
+            InputAction::Interact => self.interact,
+            InputAction::Skill1 => self.skill_1,
+            InputAction::Skill2 => self.skill_2,
+            InputAction::Skill3 => self.skill_3,
+            InputAction::ToggleEquipment => self.toggle_equipment,
+            InputAction::CycleTarget => self.cycle_target,
+        }
+    }
+
+    fn slot_mut(&mut self, action: InputAction) -> &mut KeyCode {
+        match action {
+            InputAction::MoveUp => &mut self.move_up,
+            InputAction::MoveDown => &mut self.move_down,
+            InputAction::MoveLeft => &mut self.move_left,
+            InputAction::MoveRight => &mut self.move_right,
+            InputAction::Sprint => &mut self.sprint,
+            InputAction::Dash => &mut self.dash,
+            InputAction::Interact => &mut self.interact,
+            InputAction::Skill1 => &mut self.skill_1,
+            InputAction::Skill2 => &mut self.skill_2,
+            InputAction::Skill3 => &mut self.skill_3,
+            InputAction::ToggleEquipment => &mut self.toggle_equipment,
+            InputAction::CycleTarget => &mut self.cycle_target,
+        }
+    }
+
+    /// 绑定新按键；如果该键已被其他操作占用，则两者互换，返回被换掉的操作
+    pub fn rebind(&mut self, action: InputAction, key: KeyCode) -> Option<InputAction> {
+        let old = self.get(action);
+        let conflict = InputAction::ALL
+            .into_iter()
+            .find(|other| *other != action && self.get(*other) == key);
+
+        if let Some(other) = conflict {
+            *self.slot_mut(other) = old;
+        }
+        *self.slot_mut(action) = key;
+        conflict
+    }
+
+    pub fn skill_keys(&self) -> [KeyCode; 3] {
+        [self.skill_1, self.skill_2, self.skill_3]
+    }
+}
+
+/// 按键显示名：去掉 Key/Digit 前缀
+pub fn key_label(key: KeyCode) -> String {
+    let raw = format!("{key:?}");
+    raw.strip_prefix("Key")
+        .or_else(|| raw.strip_prefix("Digit"))
+        .unwrap_or(&raw)
+        .to_string()
+}
+
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<MovementInput>()
+            .init_resource::<KeyBindings>()
             .add_systems(
                 Update,
                 cache_movement_input.run_if(in_state(GameState::InGame)),
             )
-            .add_systems(
-                Update,
-                start_game_from_menu.run_if(in_state(GameState::MainMenu)),
-            )
-            .add_systems(Update, toggle_pause.run_if(in_game_or_paused));
+            .add_systems(Update, toggle_pause.run_if(in_game_or_paused))
+            .add_systems(Update, pause_on_focus_loss.run_if(in_state(GameState::InGame)));
     }
 }
 
@@ -25,19 +180,23 @@ fn in_game_or_paused(state: Res<State<GameState>>) -> bool {
     matches!(state.get(), GameState::InGame | GameState::Paused)
 }
 
-fn cache_movement_input(mut movement: ResMut<MovementInput>, keyboard: Res<ButtonInput<KeyCode>>) {
+fn cache_movement_input(
+    mut movement: ResMut<MovementInput>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
     let mut direction = Vec2::ZERO;
 
-    if keyboard.pressed(KeyCode::KeyW) {
+    if keyboard.pressed(bindings.move_up) {
         direction.y += 1.0;
     }
-    if keyboard.pressed(KeyCode::KeyS) {
+    if keyboard.pressed(bindings.move_down) {
         direction.y -= 1.0;
     }
-    if keyboard.pressed(KeyCode::KeyA) {
+    if keyboard.pressed(bindings.move_left) {
         direction.x -= 1.0;
     }
-    if keyboard.pressed(KeyCode::KeyD) {
+    if keyboard.pressed(bindings.move_right) {
         direction.x += 1.0;
     }
 
@@ -48,21 +207,14 @@ fn cache_movement_input(mut movement: ResMut<MovementInput>, keyboard: Res<Butto
     };
 }
 
-fn start_game_from_menu(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-) {
-    if keyboard.just_pressed(KeyCode::Enter) {
-        next_state.set(GameState::InGame);
-    }
-}
-
-fn toggle_pause(
+pub(crate) fn toggle_pause(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut next_state: ResMut<NextState<GameState>>,
     current_state: Res<State<GameState>>,
+    downed_q: Query<(), With<Downed>>,
 ) {
-    if !keyboard.just_pressed(KeyCode::Escape) {
+    // 复活面板打开时不能暂停，否则退出暂停会提前恢复虚拟时间
+    if !keyboard.just_pressed(KeyCode::Escape) || !downed_q.is_empty() {
         return;
     }
 
@@ -72,3 +224,17 @@ fn toggle_pause(
         _ => {}
     }
 }
+
+/// 窗口失焦时自动进入暂停（可在设置里关闭）；复活面板打开时不处理
+fn pause_on_focus_loss(
+    mut focus_rx: MessageReader<WindowFocused>,
+    settings: Res<GameSettings>,
+    mut next_state: ResMut<NextState<GameState>>,
+    downed_q: Query<(), With<Downed>>,
+) {
+    let lost_focus = focus_rx.read().any(|ev| !ev.focused);
+    if lost_focus && settings.pause_on_focus_loss && downed_q.is_empty() {
+        next_state.set(GameState::Paused);
+    }
+}
+
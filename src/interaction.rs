@@ -1,3 +1,4 @@
+use crate::input::KeyBindings;
 use crate::movement::Player;
 use crate::state::GameState;
 use bevy::prelude::*;
@@ -8,6 +9,12 @@ pub struct InteractionPlugin;
 #[derive(Message)]
 pub struct InteractEvent;
 
+/// 可以按交互键互动的物体（商店等）：玩家在 radius 以内才会响应
+#[derive(Component)]
+pub struct Interactable {
+    pub radius: f32,
+}
+
 #[derive(Resource)]
 struct InteractionFlash(Timer);
 
@@ -22,16 +29,20 @@ impl Plugin for InteractionPlugin {
                 Update,
                 emit_interact_event.run_if(in_state(GameState::InGame)),
             )
-            .add_systems(Update, start_interaction_feedback)
-            .add_systems(Update, apply_interaction_feedback);
+            .add_systems(
+                Update,
+                (start_interaction_feedback, apply_interaction_feedback)
+                    .run_if(in_state(GameState::InGame)),
+            );
     }
 }
 
 fn emit_interact_event(
     keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
     mut writer: MessageWriter<InteractEvent>,
 ) {
-    if keyboard.just_pressed(KeyCode::KeyE) {
+    if keyboard.just_pressed(bindings.interact) {
         writer.write(InteractEvent);
     }
 }
@@ -1,10 +1,26 @@
-use crate::equipment::ItemId;
+use crate::equipment::{ItemId, ItemInstance};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ItemStack {
     pub id: ItemId,
     pub count: u32,
+    /// 带随机属性的武器实例：不可叠加，count 恒为 1；普通物品为 None
+    #[serde(default)]
+    pub instance: Option<ItemInstance>,
+}
+
+/// 背包容量配置：新角色的初始格子数
+#[derive(Resource, Clone, Debug)]
+pub struct InventoryConfig {
+    pub base_slots: usize,
+}
+
+impl Default for InventoryConfig {
+    fn default() -> Self {
+        Self { base_slots: 120 }
+    }
 }
 
 #[derive(Component)]
@@ -24,11 +40,17 @@ impl Inventory {
         self.slots.len()
     }
 
+    /// 扩容：在末尾追加 extra 个空格子，已有物品位置不变
+    pub fn grow(&mut self, extra: usize) {
+        self.slots.resize(self.slots.len() + extra, None);
+    }
+
     /// 尝试把 count 个物品放进背包（会优先叠加），返回剩余放不下的数量
+    #[allow(clippy::collapsible_if)]
     pub fn try_add(&mut self, id: ItemId, mut count: u32, max_stack: u32) -> u32 {
         for slot in self.slots.iter_mut() {
             if let Some(s) = slot.as_mut() {
-                if s.id == id && s.count < max_stack && count > 0 {
+                if s.id == id && s.instance.is_none() && s.count < max_stack && count > 0 {
                     let can = (max_stack - s.count).min(count);
                     s.count += can;
                     count -= can;
@@ -39,7 +61,7 @@ impl Inventory {
         for slot in self.slots.iter_mut() {
             if slot.is_none() && count > 0 {
                 let put = max_stack.min(count);
-                *slot = Some(ItemStack { id, count: put });
+                *slot = Some(ItemStack { id, count: put, instance: None });
                 count -= put;
             }
         }
@@ -47,27 +69,99 @@ impl Inventory {
         count
     }
 
-    /// 从背包中移除一个指定 ItemId（找到任意一个计数>0 的堆并减一）
-    pub fn try_remove_one(&mut self, id: ItemId) -> bool {
-        for slot in self.slots.iter_mut() {
-            if let Some(s) = slot {
-                if s.id == id && s.count > 0 {
-                    s.count -= 1;
-                    if s.count == 0 {
-                        *slot = None;
-                    }
-                    return true;
-                }
-            }
-        }
-        false
+    /// 把一件武器实例放进第一个空格子，背包满了返回 false
+    pub fn try_add_instance(&mut self, item: ItemInstance) -> bool {
+        let Some(slot) = self.slots.iter_mut().find(|s| s.is_none()) else {
+            return false;
+        };
+        *slot = Some(ItemStack {
+            id: item.id,
+            count: 1,
+            instance: Some(item),
+        });
+        true
     }
 
-    /// 交换两个索引
-    pub fn swap_slots(&mut self, a: usize, b: usize) {
-        if a >= self.slots.len() || b >= self.slots.len() {
-            return;
+    /// 第 index 格减一个，减到 0 清空该格；返回被移除的物品
+    pub fn remove_one_at(&mut self, index: usize) -> Option<ItemId> {
+        let slot = self.slots.get_mut(index)?;
+        let stack = slot.as_mut()?;
+        let id = stack.id;
+        stack.count = stack.count.saturating_sub(1);
+        if stack.count == 0 {
+            *slot = None;
         }
-        self.slots.swap(a, b);
+        Some(id)
+    }
+
+    /// 空格子数量
+    pub fn free_slots(&self) -> usize {
+        self.slots.iter().filter(|s| s.is_none()).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equipment::ItemDatabase;
+
+    fn count_of(inv: &Inventory, id: ItemId) -> u32 {
+        inv.slots.iter().flatten().filter(|s| s.id == id).map(|s| s.count).sum()
+    }
+
+    #[test]
+    fn add_beyond_capacity_returns_remainder() {
+        let mut inv = Inventory::new(2);
+        // 两格、每格最多 5 个：放进 12 个剩 2 个
+        assert_eq!(inv.try_add(ItemId::HealthPotion, 12, 5), 2);
+        assert_eq!(count_of(&inv, ItemId::HealthPotion), 10);
+        assert_eq!(inv.free_slots(), 0);
+
+        // 满了之后再放全部退回
+        assert_eq!(inv.try_add(ItemId::HealthPotion, 3, 5), 3);
+        assert_eq!(inv.try_add(ItemId::LeatherArmor, 1, 1), 1);
+    }
+
+    #[test]
+    fn stacking_tops_up_existing_stacks_before_using_new_slots() {
+        let mut inv = Inventory::new(4);
+        assert_eq!(inv.try_add(ItemId::HealthPotion, 3, 5), 0);
+        assert_eq!(inv.try_add(ItemId::HealthPotion, 4, 5), 0);
+
+        // 先补满第一格，剩下的才另开一格，任何一格都不超过上限
+        let counts: Vec<u32> = inv.slots.iter().flatten().map(|s| s.count).collect();
+        assert_eq!(counts, vec![5, 2]);
+        assert!(inv.slots.iter().flatten().all(|s| s.count <= 5));
+    }
+
+    #[test]
+    fn weapon_instances_are_never_stacked_onto() {
+        let mut inv = Inventory::new(3);
+        let sword = ItemDatabase::default().roll(ItemId::RustySword).expect("短剑应该在物品表里");
+        assert!(inv.try_add_instance(sword));
+        assert_eq!(inv.try_add(ItemId::RustySword, 1, 5), 0);
+
+        assert_eq!(inv.slots[0].as_ref().map(|s| s.count), Some(1));
+        assert_eq!(inv.slots[1].as_ref().map(|s| s.count), Some(1));
+        assert!(inv.slots[1].as_ref().is_some_and(|s| s.instance.is_none()));
+    }
+
+    #[test]
+    fn remove_from_empty_inventory_returns_none() {
+        let mut inv = Inventory::new(3);
+        assert_eq!(inv.remove_one_at(0), None);
+        assert_eq!(inv.remove_one_at(99), None);
+    }
+
+    #[test]
+    fn remove_clears_slot_when_stack_runs_out() {
+        let mut inv = Inventory::new(2);
+        inv.try_add(ItemId::HealthPotion, 2, 5);
+
+        assert_eq!(inv.remove_one_at(0), Some(ItemId::HealthPotion));
+        assert_eq!(count_of(&inv, ItemId::HealthPotion), 1);
+        assert_eq!(inv.remove_one_at(0), Some(ItemId::HealthPotion));
+        assert!(inv.slots[0].is_none());
+        assert_eq!(inv.remove_one_at(0), None);
     }
 }
@@ -0,0 +1,186 @@
+// src/kill_feed.rs
+//! 右下角的战况：场上存活敌人数，以及最近几条“击杀”记录（几秒后淡出）。
+//! 记录放在资源里，暂停回来重建 HUD 时不会丢。
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::combat_core::EnemyDied;
+use crate::enemy::Enemy;
+use crate::i18n::tr;
+use crate::state::GameState;
+use crate::ui::types::GameSettings;
+
+/// 同时显示的击杀记录条数，多出来的挤掉最旧的
+const MAX_FEED_ENTRIES: usize = 4;
+/// 每条记录显示多久（秒），最后 FEED_FADE_SECS 秒淡出
+const FEED_LIFETIME_SECS: f32 = 3.0;
+const FEED_FADE_SECS: f32 = 0.8;
+const FEED_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const FEED_ELITE_COLOR: Color = Color::srgb(1.0, 0.6, 0.25);
+
+struct KillEntry {
+    elite: bool,
+    age: f32,
+}
+
+/// 最近的击杀记录，新的在后面
+#[derive(Resource, Default)]
+pub struct KillFeed {
+    entries: VecDeque<KillEntry>,
+}
+
+impl KillFeed {
+    fn push(&mut self, elite: bool) {
+        self.entries.push_back(KillEntry { elite, age: 0.0 });
+        while self.entries.len() > MAX_FEED_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+
+    fn tick(&mut self, dt: f32) {
+        for entry in &mut self.entries {
+            entry.age += dt;
+        }
+        self.entries.retain(|e| e.age < FEED_LIFETIME_SECS);
+    }
+}
+
+#[derive(Component)]
+struct KillFeedRoot;
+
+#[derive(Component)]
+struct EnemyCountText;
+
+/// 第 i 条记录的文字（从上往下，最旧的在最上面）
+#[derive(Component)]
+struct KillFeedLine(usize);
+
+pub struct KillFeedPlugin;
+
+impl Plugin for KillFeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KillFeed>()
+            .add_systems(OnEnter(GameState::InGame), setup_kill_feed_hud)
+            .add_systems(OnExit(GameState::InGame), cleanup_kill_feed_hud)
+            .add_systems(
+                Update,
+                (record_kills, update_enemy_count, update_kill_feed_lines)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnEnter(GameState::MainMenu), reset_kill_feed);
+    }
+}
+
+fn reset_kill_feed(mut feed: ResMut<KillFeed>) {
+    *feed = KillFeed::default();
+}
+
+/// 放在右下角，技能栏在左下角，互不遮挡
+fn setup_kill_feed_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font: Handle<Font> = asset_server.load("fonts/YuFanLixing.otf");
+    commands
+        .spawn((
+            KillFeedRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(16.0),
+                bottom: Val::Px(16.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexEnd,
+                row_gap: Val::Px(2.0),
+                ..default()
+            },
+        ))
+        .with_children(|root| {
+            for i in 0..MAX_FEED_ENTRIES {
+                root.spawn((
+                    KillFeedLine(i),
+                    Text::new(""),
+                    TextFont { font: font.clone(), font_size: 14.0, ..default() },
+                    TextColor(FEED_COLOR),
+                ));
+            }
+            root.spawn((
+                EnemyCountText,
+                Text::new(""),
+                TextFont { font, font_size: 16.0, ..default() },
+                TextColor(Color::WHITE),
+                Node { margin: UiRect::top(Val::Px(4.0)), ..default() },
+            ));
+        });
+}
+
+fn record_kills(time: Res<Time>, mut feed: ResMut<KillFeed>, mut died_rx: MessageReader<EnemyDied>) {
+    feed.tick(time.delta_secs());
+    for ev in died_rx.read() {
+        feed.push(ev.elite);
+    }
+}
+
+fn update_enemy_count(
+    settings: Res<GameSettings>,
+    enemies_q: Query<(), With<Enemy>>,
+    mut q: Query<&mut Text, With<EnemyCountText>>,
+) {
+    let label = format!("{}: {}", tr(settings.language, "hud.enemies"), enemies_q.iter().count());
+    for mut t in &mut q {
+        if t.0 != label {
+            t.0 = label.clone();
+        }
+    }
+}
+
+/// 记录不满时空行放在上面，最新的一条总是贴着敌人数
+fn update_kill_feed_lines(
+    feed: Res<KillFeed>,
+    settings: Res<GameSettings>,
+    mut lines_q: Query<(&KillFeedLine, &mut Text, &mut TextColor)>,
+) {
+    let lang = settings.language;
+    let offset = MAX_FEED_ENTRIES - feed.entries.len();
+    for (line, mut text, mut color) in &mut lines_q {
+        let Some(entry) = line.0.checked_sub(offset).and_then(|i| feed.entries.get(i)) else {
+            if !text.0.is_empty() {
+                text.0.clear();
+            }
+            continue;
+        };
+        let target = if entry.elite { "feed.elite" } else { "feed.enemy" };
+        let label = format!("{} {}", tr(lang, "feed.kill"), tr(lang, target));
+        if text.0 != label {
+            text.0 = label;
+        }
+        let alpha = ((FEED_LIFETIME_SECS - entry.age) / FEED_FADE_SECS).clamp(0.0, 1.0);
+        color.0 = if entry.elite { FEED_ELITE_COLOR } else { FEED_COLOR }.with_alpha(alpha);
+    }
+}
+
+fn cleanup_kill_feed_hud(mut commands: Commands, q: Query<Entity, With<KillFeedRoot>>) {
+    for e in &q {
+        commands.entity(e).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feed_keeps_latest_entries_and_ages_them_out() {
+        let mut feed = KillFeed::default();
+        for i in 0..6 {
+            feed.push(i == 5);
+        }
+        assert_eq!(feed.entries.len(), MAX_FEED_ENTRIES);
+        assert!(feed.entries.back().unwrap().elite);
+
+        feed.tick(FEED_LIFETIME_SECS - 0.1);
+        feed.push(false);
+        assert_eq!(feed.entries.len(), MAX_FEED_ENTRIES);
+        feed.tick(0.2);
+        assert_eq!(feed.entries.len(), 1, "只剩刚加的那一条");
+    }
+}
@@ -0,0 +1,20 @@
+// src/layers.rs
+//! 世界内各类实体的绘制层级（z 值）。
+//! 不同种类的实体放在不同深度，避免相同 z 值导致的绘制顺序不稳定/闪烁。
+
+/// 标题界面背景
+pub const BACKGROUND: f32 = -100.0;
+/// 地面掉落物
+pub const PICKUP: f32 = 6.0;
+/// 敌人
+pub const ENEMY: f32 = 8.0;
+/// 玩家
+pub const PLAYER: f32 = 10.0;
+/// 子弹
+pub const PROJECTILE: f32 = 12.0;
+/// 技能特效（斩击等）
+pub const VFX: f32 = 15.0;
+/// 飘字（伤害数字）
+pub const DAMAGE_NUMBER: f32 = 90.0;
+/// 敌人头顶血条
+pub const HP_BAR: f32 = 100.0;
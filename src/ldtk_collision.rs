@@ -22,6 +22,44 @@ impl Default for WallColliders {
     }
 }
 
+impl WallColliders {
+    /// from 到 to 的连线是否没有穿过任何墙体
+    pub fn line_of_sight(&self, from: Vec2, to: Vec2) -> bool {
+        !self
+            .aabbs
+            .iter()
+            .any(|&(center, half)| segment_intersects_aabb(from, to, center, half))
+    }
+}
+
+/// 线段与 AABB 是否相交（slab 法）
+pub fn segment_intersects_aabb(a: Vec2, b: Vec2, center: Vec2, half: Vec2) -> bool {
+    let min = center - half;
+    let max = center + half;
+    let d = b - a;
+    let mut t_enter = 0.0_f32;
+    let mut t_exit = 1.0_f32;
+
+    for axis in 0..2 {
+        let (start, delta, lo, hi) = (a[axis], d[axis], min[axis], max[axis]);
+        if delta.abs() < f32::EPSILON {
+            // 平行于这条轴：起点必须落在 slab 内
+            if start < lo || start > hi {
+                return false;
+            }
+            continue;
+        }
+        let t1 = (lo - start) / delta;
+        let t2 = (hi - start) / delta;
+        t_enter = t_enter.max(t1.min(t2));
+        t_exit = t_exit.min(t1.max(t2));
+        if t_enter > t_exit {
+            return false;
+        }
+    }
+    true
+}
+
 pub struct LdtkCollisionPlugin;
 
 impl Plugin for LdtkCollisionPlugin {
@@ -75,7 +75,7 @@ fn on_level_spawned(
                         commands.spawn((
                             Enemy,
                             Transform::from_translation(tf.translation),
-                            Health { current: 50.0, max: 50.0 },
+                            Health::new(50.0),
                         ));
                     }
                     _ => {}
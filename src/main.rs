@@ -1,55 +1,121 @@
 use bevy::prelude::*;
-use bevy::window::{WindowPlugin, WindowResolution, WindowMode};
+use bevy::window::{PresentMode, WindowPlugin, WindowPosition, WindowResolution, WindowMode};
 use bevy_ecs_ldtk::prelude::*;
 
+mod aim_assist;
+mod balance;
+mod buffs;
+mod checkpoint;
 mod combat;
 mod combat_core;
+mod damage_indicator;
+mod difficulty_scaling;
+mod elite;
 mod enemy;
 mod enemy_combat;
+mod enemy_db;
 mod equipment;
 mod exit;
 mod game_over_ui;
 mod health;
+mod hit_stop;
+mod i18n;
 mod input;
 mod interaction;
 mod inventory;
+mod kill_feed;
+mod layers;
 mod ldtk_collision;
+mod map_load;
+mod meta_progress;
 mod movement;
+mod new_run;
+mod notifications;
+mod palette;
+mod pickup;
+mod quest;
+mod run_stats;
 mod save;
+mod screen_shake;
+mod sfx;
+mod shop;
 mod skills;
 mod skills_pool;
+mod sprite_animation;
 mod state;
+#[cfg(test)]
+mod test_support;
+mod transition;
+mod tutorial;
 mod ui;
 mod utils;
+mod wallet;
 
 use crate::{
+    aim_assist::AimAssistPlugin,
+    balance::BalancePlugin,
+    buffs::BuffsPlugin,
+    checkpoint::CheckpointPlugin,
     combat::CombatPlugin,
     combat_core::CombatCorePlugin,
+    damage_indicator::DamageIndicatorPlugin,
+    difficulty_scaling::DifficultyScalingPlugin,
+    elite::ElitePlugin,
     enemy::EnemyPlugin,
     enemy_combat::EnemyCombatPlugin,
     equipment::EquipmentPlugin,
     exit::ExitPlugin,
     game_over_ui::GameOverUiPlugin,
     health::HealthPlugin,
+    hit_stop::HitStopPlugin,
+    i18n::I18nPlugin,
     input::InputPlugin,
     interaction::InteractionPlugin,
+    kill_feed::KillFeedPlugin,
     ldtk_collision::LdtkCollisionPlugin,
+    map_load::MapLoadPlugin,
+    meta_progress::MetaProgressPlugin,
     movement::MovementPlugin,
+    new_run::NewRunPlugin,
+    notifications::NotificationsPlugin,
+    palette::PalettePlugin,
+    pickup::PickupPlugin,
+    quest::QuestPlugin,
+    run_stats::RunStatsPlugin,
     save::SavePlugin,
+    screen_shake::ScreenShakePlugin,
+    sfx::SfxPlugin,
+    shop::ShopPlugin,
     skills::SkillPlugin,
     skills_pool::SkillPoolPlugin,
     state::GameState,
+    transition::TransitionPlugin,
+    tutorial::TutorialPlugin,
     ui::MenuPlugin,
+    wallet::WalletPlugin,
 };
 
 fn main() {
     let mut app = App::new();
 
+    // 旧版本的存档和设置在当前目录，先搬到系统数据目录再读设置
+    save::migrate_legacy_data();
+
+    // 窗口按上次关闭时的大小/位置打开（其余设置在 Startup 时由 MenuPlugin 应用）
+    let saved = ui::settings::read_saved_settings().unwrap_or_default();
+    let (width, height) = saved.window_size.unwrap_or((1280, 720));
+    let position = saved
+        .window_position
+        .map(|(x, y)| WindowPosition::At(IVec2::new(x, y)))
+        .unwrap_or(WindowPosition::Automatic);
+
     app.add_plugins(
         DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 mode: WindowMode::Windowed,
-                resolution: WindowResolution::from((1280u32, 720u32)),
+                resolution: WindowResolution::from((width, height)),
+                position,
+                present_mode: PresentMode::AutoVsync,
                 title: "Oplus".into(),
                 ..default()
             }),
@@ -65,22 +131,46 @@ fn main() {
     app.init_state::<GameState>();
 
     // Add project plugins (single .add_plugins avoids tuple-size trait limit)
+    app.add_plugins(BalancePlugin);
     app.add_plugins(InputPlugin);
     app.add_plugins(MovementPlugin);
     app.add_plugins(InteractionPlugin);
     app.add_plugins(ExitPlugin);
     app.add_plugins(HealthPlugin);
+    app.add_plugins(CheckpointPlugin);
     app.add_plugins(EquipmentPlugin);
     app.add_plugins(EnemyPlugin);
+    app.add_plugins(ElitePlugin);
+    app.add_plugins(DifficultyScalingPlugin);
     app.add_plugins(SkillPoolPlugin);
     app.add_plugins(CombatCorePlugin);
     app.add_plugins(CombatPlugin);
+    app.add_plugins(AimAssistPlugin);
     app.add_plugins(EnemyCombatPlugin);
     app.add_plugins(SkillPlugin);
     app.add_plugins(SavePlugin);
     app.add_plugins(MenuPlugin);
+    app.add_plugins(I18nPlugin);
+    app.add_plugins(RunStatsPlugin);
     app.add_plugins(GameOverUiPlugin);
+    app.add_plugins(TransitionPlugin);
+    app.add_plugins(TutorialPlugin);
+    app.add_plugins(NotificationsPlugin);
+    app.add_plugins(PalettePlugin);
+    app.add_plugins(DamageIndicatorPlugin);
+    app.add_plugins(ScreenShakePlugin);
+    app.add_plugins(SfxPlugin);
+    app.add_plugins(HitStopPlugin);
+    app.add_plugins(PickupPlugin);
+    app.add_plugins(BuffsPlugin);
+    app.add_plugins(QuestPlugin);
+    app.add_plugins(WalletPlugin);
+    app.add_plugins(KillFeedPlugin);
+    app.add_plugins(NewRunPlugin);
+    app.add_plugins(ShopPlugin);
+    app.add_plugins(MetaProgressPlugin);
     app.add_plugins(LdtkCollisionPlugin);
+    app.add_plugins(MapLoadPlugin);
 
     // Common systems (camera / ldtk handlers)
     app.add_systems(Startup, setup_camera);
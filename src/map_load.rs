@@ -0,0 +1,161 @@
+// src/map_load.rs
+//! 地图加载失败的兜底：world.ldtk 缺失或解析失败时，玩家永远不会生成、画面一片黑。
+//! 这里检查 LDtk 工程的加载状态，失败时暂停模拟并弹出“无法加载地图”面板，提供返回标题的按钮。
+
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::LdtkProjectHandle;
+
+use crate::i18n::localized;
+use crate::state::GameState;
+use crate::transition::TransitionRequest;
+use crate::ui::types::GameSettings;
+use crate::utils::despawn_with_children;
+
+#[derive(Component)]
+struct MapLoadErrorRoot;
+
+#[derive(Component)]
+struct MapLoadErrorBackButton;
+
+pub struct MapLoadPlugin;
+
+impl Plugin for MapLoadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (detect_map_load_failure, handle_back_button).run_if(in_state(GameState::InGame)),
+        )
+        .add_systems(OnEnter(GameState::MainMenu), cleanup_map_load_error);
+    }
+}
+
+fn detect_map_load_failure(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    worlds: Query<&LdtkProjectHandle>,
+    existing: Query<(), With<MapLoadErrorRoot>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+    let failed = worlds.iter().find_map(|world| match asset_server.load_state(&world.handle) {
+        LoadState::Failed(err) => Some(err),
+        _ => None,
+    });
+    let Some(err) = failed else { return; };
+    error!("地图加载失败: {}", err);
+
+    // 没有地图就没有玩家，冻结模拟免得敌人在黑屏里继续刷
+    virtual_time.pause();
+
+    let font: Handle<Font> = asset_server.load("fonts/YuFanLixing.otf");
+    let lang = settings.language;
+
+    commands
+        .spawn((
+            MapLoadErrorRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            GlobalZIndex(5),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(520.0),
+                    padding: UiRect::all(Val::Px(26.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(14.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.12, 0.12, 0.16, 0.95)),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    localized(lang, "map_error.title"),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(1.0, 0.55, 0.5)),
+                ));
+                panel.spawn((
+                    localized(lang, "map_error.hint"),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgba(1.0, 1.0, 1.0, 0.85)),
+                ));
+                panel
+                    .spawn((
+                        Button,
+                        MapLoadErrorBackButton,
+                        Node {
+                            width: Val::Px(260.0),
+                            height: Val::Px(46.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.20, 0.20, 0.40)),
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((
+                            localized(lang, "game_over.back_title"),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 20.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            });
+        });
+}
+
+#[allow(clippy::type_complexity)]
+fn handle_back_button(
+    mut interactions: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<MapLoadErrorBackButton>)>,
+    mut transition_tx: MessageWriter<TransitionRequest>,
+) {
+    for (interaction, mut bg) in &mut interactions {
+        match *interaction {
+            Interaction::Pressed => {
+                bg.0 = Color::srgb(0.8, 0.8, 1.0);
+                transition_tx.write(TransitionRequest(GameState::MainMenu));
+            }
+            Interaction::Hovered => bg.0 = Color::srgb(0.6, 0.6, 0.8),
+            Interaction::None => bg.0 = Color::srgb(0.20, 0.20, 0.40),
+        }
+    }
+}
+
+/// 回到标题时收起面板并恢复模拟；下次开始游戏会重新尝试加载地图
+fn cleanup_map_load_error(
+    mut commands: Commands,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    roots: Query<Entity, With<MapLoadErrorRoot>>,
+    children_q: Query<&Children>,
+) {
+    if roots.is_empty() {
+        return;
+    }
+    virtual_time.unpause();
+    for root in &roots {
+        despawn_with_children(&mut commands, &children_q, root);
+    }
+}
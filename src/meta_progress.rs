@@ -0,0 +1,211 @@
+// src/meta_progress.rs
+//! 局外成长：每局结束按得分发放 "魂晶"，在主菜单的 "强化" 面板里购买永久加成。
+//! 进度单独存在数据目录下的 meta.json（和局内存档、排行榜分开），启动时读取、变化时写回。
+//! 每局开始生成玩家时把加成写进基础属性：最大生命直接加上，伤害和移速挂在 `MetaBonuses` 组件上。
+
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::health::Health;
+use crate::movement::Player;
+use crate::run_stats::RunStats;
+use crate::save::meta_file_path;
+use crate::state::GameState;
+
+/// 每多少分换 1 个魂晶
+const SCORE_PER_SHARD: u32 = 20;
+/// 每项强化的最高等级
+pub const MAX_UPGRADE_LEVEL: u32 = 5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MetaUpgrade {
+    MaxHealth,
+    Damage,
+    MoveSpeed,
+}
+
+impl MetaUpgrade {
+    pub const ALL: [MetaUpgrade; 3] = [MetaUpgrade::MaxHealth, MetaUpgrade::Damage, MetaUpgrade::MoveSpeed];
+
+    /// 文本表 key
+    pub fn label_key(self) -> &'static str {
+        match self {
+            MetaUpgrade::MaxHealth => "upgrades.max_health",
+            MetaUpgrade::Damage => "upgrades.damage",
+            MetaUpgrade::MoveSpeed => "upgrades.move_speed",
+        }
+    }
+
+    /// 从 level 升到下一级的价格：10, 20, 30 ...
+    pub fn cost(self, level: u32) -> u32 {
+        10 * (level + 1)
+    }
+}
+
+/// 永久进度：剩余魂晶和各项强化等级
+#[derive(Resource, Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetaProgress {
+    pub shards: u32,
+    pub max_health: u32,
+    pub damage: u32,
+    pub move_speed: u32,
+}
+
+impl MetaProgress {
+    pub fn level(&self, upgrade: MetaUpgrade) -> u32 {
+        match upgrade {
+            MetaUpgrade::MaxHealth => self.max_health,
+            MetaUpgrade::Damage => self.damage,
+            MetaUpgrade::MoveSpeed => self.move_speed,
+        }
+    }
+
+    fn level_mut(&mut self, upgrade: MetaUpgrade) -> &mut u32 {
+        match upgrade {
+            MetaUpgrade::MaxHealth => &mut self.max_health,
+            MetaUpgrade::Damage => &mut self.damage,
+            MetaUpgrade::MoveSpeed => &mut self.move_speed,
+        }
+    }
+
+    /// 满级或魂晶不够返回 false
+    pub fn try_purchase(&mut self, upgrade: MetaUpgrade) -> bool {
+        let level = self.level(upgrade);
+        let cost = upgrade.cost(level);
+        if level >= MAX_UPGRADE_LEVEL || self.shards < cost {
+            return false;
+        }
+        self.shards -= cost;
+        *self.level_mut(upgrade) += 1;
+        true
+    }
+
+    /// 每级 +10 最大生命
+    pub fn bonus_max_health(&self) -> f32 {
+        self.max_health as f32 * 10.0
+    }
+
+    /// 每级 +8% 伤害
+    pub fn damage_factor(&self) -> f32 {
+        1.0 + self.damage as f32 * 0.08
+    }
+
+    /// 每级 +5% 移速
+    pub fn speed_factor(&self) -> f32 {
+        1.0 + self.move_speed as f32 * 0.05
+    }
+}
+
+/// 本局生效的局外加成，生成玩家时按当时的 `MetaProgress` 算好；局中购买不影响本局
+#[derive(Component, Clone, Copy, Debug)]
+pub struct MetaBonuses {
+    /// 已经加进 `Health::max` 的部分；存档只存去掉它的基础值，读档时再加回来
+    pub max_health_bonus: f32,
+    pub damage_factor: f32,
+    pub speed_factor: f32,
+}
+
+/// 刚结束的一局获得的魂晶，失败界面显示
+#[derive(Resource, Default)]
+pub struct LastRunShards(pub u32);
+
+pub struct MetaProgressPlugin;
+
+impl Plugin for MetaProgressPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MetaProgress>()
+            .init_resource::<LastRunShards>()
+            .add_systems(Startup, load_meta_progress)
+            .add_systems(OnEnter(GameState::GameOver), award_meta_currency)
+            .add_systems(
+                Update,
+                apply_meta_bonuses
+                    .before(crate::save::apply_pending_load)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(Update, persist_meta_progress);
+    }
+}
+
+pub fn shards_for_run(stats: &RunStats) -> u32 {
+    stats.score() / SCORE_PER_SHARD
+}
+
+/// 失败时按本局得分发放魂晶（在 RunStats 被重置之前）
+pub fn award_meta_currency(stats: Res<RunStats>, mut meta: ResMut<MetaProgress>, mut last: ResMut<LastRunShards>) {
+    let earned = shards_for_run(&stats);
+    last.0 = earned;
+    if earned > 0 {
+        meta.shards += earned;
+    }
+}
+
+/// 新生成的玩家：抬高最大生命并回满，挂上伤害 / 移速加成
+fn apply_meta_bonuses(
+    mut commands: Commands,
+    meta: Res<MetaProgress>,
+    mut players: Query<(Entity, &mut Health), Added<Player>>,
+) {
+    for (entity, mut hp) in &mut players {
+        hp.max += meta.bonus_max_health();
+        hp.current = hp.max;
+        commands.entity(entity).insert(MetaBonuses {
+            max_health_bonus: meta.bonus_max_health(),
+            damage_factor: meta.damage_factor(),
+            speed_factor: meta.speed_factor(),
+        });
+    }
+}
+
+fn load_meta_progress(mut meta: ResMut<MetaProgress>) {
+    let path = meta_file_path();
+    let Ok(text) = fs::read_to_string(&path) else { return; };
+
+    match serde_json::from_str::<MetaProgress>(&text) {
+        Ok(loaded) => *meta = loaded,
+        Err(e) => warn!("读取局外进度失败 {:?}: {}", path, e),
+    }
+}
+
+fn persist_meta_progress(meta: Res<MetaProgress>) {
+    if !meta.is_changed() || meta.is_added() {
+        return;
+    }
+
+    let path = meta_file_path();
+    match serde_json::to_string_pretty(&*meta) {
+        Ok(text) => {
+            if let Err(e) = fs::write(&path, text) {
+                warn!("写入局外进度失败 {:?}: {}", path, e);
+            }
+        }
+        Err(e) => warn!("序列化局外进度失败: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn purchase_spends_shards_and_stops_at_max_level() {
+        let mut meta = MetaProgress { shards: 1000, ..default() };
+        for _ in 0..MAX_UPGRADE_LEVEL {
+            assert!(meta.try_purchase(MetaUpgrade::Damage));
+        }
+        assert!(!meta.try_purchase(MetaUpgrade::Damage));
+        assert_eq!(meta.damage, MAX_UPGRADE_LEVEL);
+        // 10 + 20 + 30 + 40 + 50
+        assert_eq!(meta.shards, 1000 - 150);
+    }
+
+    #[test]
+    fn purchase_fails_without_enough_shards() {
+        let mut meta = MetaProgress { shards: 9, ..default() };
+        assert!(!meta.try_purchase(MetaUpgrade::MaxHealth));
+        assert_eq!(meta, MetaProgress { shards: 9, ..default() });
+    }
+}
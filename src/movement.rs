@@ -1,9 +1,21 @@
 use bevy::input::keyboard::KeyCode;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 use bevy_ecs_ldtk::prelude::EntityInstance;
+use rand::Rng;
 
 use crate::{
-    health::Health, input::MovementInput, ldtk_collision::WallColliders, state::GameState,
+    buffs::Buffs,
+    equipment::WeaponKind,
+    health::{Health, Invulnerable},
+    input::{KeyBindings, MovementInput},
+    layers,
+    sfx::{PlaySfx, Sfx},
+    ldtk_collision::WallColliders,
+    meta_progress::MetaBonuses,
+    sprite_animation::{SpriteSheetConfig, WalkCycle},
+    state::GameState,
+    ui::types::{FaceMode, GameSettings},
 };
 
 pub struct MovementPlugin;
@@ -22,6 +34,17 @@ const SPRINT_MULTIPLIER: f32 = 1.5;
 const DASH_MULTIPLIER: f32 = 3.0;
 pub const DASH_DURATION: f32 = 0.4;
 pub const DASH_COOLDOWN: f32 = 10.0;
+/// 冲刺这一帧的实际位移不到预期的这个比例，就算被墙整个挡住（斜着冲贴墙滑行不算）
+const DASH_BLOCKED_RATIO: f32 = 0.1;
+/// 冲刺被墙挡住提前结束时，按没冲完的时长比例返还这部分冷却
+const DASH_BLOCKED_REFUND: f32 = 0.75;
+/// 脚步声的音调浮动范围，避免每步听起来一模一样
+const FOOTSTEP_PITCH_MIN: f32 = 0.9;
+const FOOTSTEP_PITCH_MAX: f32 = 1.1;
+/// 玩家贴图的显示尺寸
+const PLAYER_SPRITE_SIZE: f32 = 24.0;
+/// 碰撞箱占贴图的比例：行走图每帧四周有留白，按整张贴图算会离墙还有一段就被挡住
+const PLAYER_HITBOX_SCALE: f32 = 0.7;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum PlayerDirection {
@@ -42,6 +65,24 @@ impl PlayerDirection {
         }
     }
 
+    /// 取最接近 dir 的四方向；dir 为零时返回 None
+    pub fn from_vec2(dir: Vec2) -> Option<Self> {
+        if dir == Vec2::ZERO {
+            return None;
+        }
+        Some(if dir.x.abs() > dir.y.abs() {
+            if dir.x > 0.0 {
+                PlayerDirection::Right
+            } else {
+                PlayerDirection::Left
+            }
+        } else if dir.y > 0.0 {
+            PlayerDirection::Up
+        } else {
+            PlayerDirection::Down
+        })
+    }
+
     pub fn as_vec2(self) -> Vec2 {
         match self {
             PlayerDirection::Up => Vec2::new(0.0, 1.0),
@@ -52,33 +93,84 @@ impl PlayerDirection {
     }
 }
 
+/// 攻击动作片段。行走图里没有单独的攻击帧，先用当前方向那一行的行走帧快速播放
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttackClip {
+    /// 近战挥砍：快速连播后三帧
+    Swing,
+    /// 远程射击：定格在出手帧，稍作停顿
+    Shoot,
+}
+
+impl AttackClip {
+    pub fn for_weapon(kind: WeaponKind) -> Self {
+        match kind {
+            WeaponKind::Melee => AttackClip::Swing,
+            WeaponKind::Ranged => AttackClip::Shoot,
+        }
+    }
+
+    fn frames(self) -> &'static [usize] {
+        match self {
+            AttackClip::Swing => &[1, 2, 3],
+            AttackClip::Shoot => &[2, 2],
+        }
+    }
+
+    fn frame_secs(self) -> f32 {
+        match self {
+            AttackClip::Swing => 0.06,
+            AttackClip::Shoot => 0.08,
+        }
+    }
+
+    fn duration(self) -> f32 {
+        self.frames().len() as f32 * self.frame_secs()
+    }
+}
+
+/// 动画当前播放的片段
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnimationState {
+    Idle,
+    Walk,
+    /// 攻击期间覆盖行走动画，朝向锁定在出手方向
+    Attack { clip: AttackClip, elapsed: f32 },
+}
+
 #[derive(Component, Debug)]
 pub struct PlayerAnimation {
     pub direction: PlayerDirection,
     pub is_moving: bool,
-    frame: usize,
-    columns: usize,
-    rows: usize,
-    initialized: bool,
-    frame_size: Vec2,
-    timer: Timer,
+    pub state: AnimationState,
+    walk: WalkCycle,
 }
 
 impl Default for PlayerAnimation {
     fn default() -> Self {
         Self {
-            frame: 0,
-            columns: 1,
-            rows: 4,
             direction: PlayerDirection::Down,
-            initialized: false,
-            frame_size: Vec2::ZERO,
-            timer: Timer::from_seconds(0.12, TimerMode::Repeating),
+            state: AnimationState::Idle,
+            walk: WalkCycle::new(0.12),
             is_moving: false,
         }
     }
 }
 
+impl PlayerAnimation {
+    /// 出手时调用：朝向转到攻击方向并从头播放攻击片段
+    pub fn play_attack(&mut self, clip: AttackClip, dir: Vec2) {
+        if let Some(direction) = PlayerDirection::from_vec2(dir) {
+            self.direction = direction;
+        }
+        self.state = AnimationState::Attack { clip, elapsed: 0.0 };
+    }
+
+    pub fn is_attacking(&self) -> bool {
+        matches!(self.state, AnimationState::Attack { .. })
+    }
+}
+
 #[derive(Component, Default, Debug)]
 pub struct PlayerDash {
     pub is_dashing: bool,
@@ -87,15 +179,31 @@ pub struct PlayerDash {
     pub direction: Vec2,
 }
 
+impl PlayerDash {
+    /// 撞墙提前结束冲刺，没冲完的部分按比例返还冷却
+    fn end_blocked(&mut self) {
+        let unused = (self.remaining / DASH_DURATION).clamp(0.0, 1.0);
+        self.cooldown = (self.cooldown - DASH_COOLDOWN * DASH_BLOCKED_REFUND * unused).max(0.0);
+        self.is_dashing = false;
+        self.remaining = 0.0;
+    }
+}
+
+/// 这一帧预期移动 intended、实际只移动了 moved：是否算被墙挡死
+fn movement_blocked(intended: Vec2, moved: Vec2) -> bool {
+    moved.length() < intended.length() * DASH_BLOCKED_RATIO
+}
+
 #[derive(Component, Clone, Copy, Debug)]
 pub struct PlayerHitbox {
     pub half: Vec2,
 }
 
-impl Default for PlayerHitbox {
-    fn default() -> Self {
+impl PlayerHitbox {
+    /// 按贴图显示尺寸计算碰撞箱半宽高
+    pub fn from_sprite_size(size: Vec2) -> Self {
         Self {
-            half: Vec2::new(1.0, 1.0),
+            half: size * 0.5 * PLAYER_HITBOX_SCALE,
         }
     }
 }
@@ -110,54 +218,47 @@ fn load_player_texture(mut commands: Commands, asset_server: Res<AssetServer>) {
 
 fn init_player_animation(
     images: Res<Assets<Image>>,
-    mut query: Query<(&mut Sprite, &mut PlayerAnimation), With<Player>>,
+    mut query: Query<(&mut Sprite, &mut PlayerAnimation, Option<&SpriteSheetConfig>), With<Player>>,
 ) {
-    for (mut sprite, mut anim) in &mut query {
-        if anim.initialized {
-            continue;
+    for (mut sprite, mut anim, config) in &mut query {
+        // 贴图还没加载完就等下一帧
+        if anim.walk.init(&images, &sprite, config) {
+            anim.walk.apply(&mut sprite, anim.direction.row_index());
         }
-
-        // If image hasn't loaded yet, wait until next tick.
-        let Some(image) = images.get(&sprite.image) else {
-            continue;
-        };
-
-        let size = image.size();
-        let tex_width = size.x as f32;
-        let tex_height = size.y as f32;
-
-        let rows = 4.0;
-        let columns = 4.0;
-
-        let frame_width = tex_width / columns;
-        let frame_height = tex_height / rows;
-
-        anim.rows = 4;
-        anim.columns = 4;
-        anim.frame_size = Vec2::new(frame_width, frame_height);
-        anim.initialized = true;
-
-        update_sprite_rect(&mut sprite, &anim);
     }
 }
 
-fn apply_player_movement(
+/// 鼠标在世界中的位置；鼠标不在窗口内时返回 None
+pub fn cursor_world_pos(window: &Window, camera: &Camera, cam_tf: &GlobalTransform) -> Option<Vec2> {
+    window
+        .cursor_position()
+        .and_then(|screen_pos| camera.viewport_to_world_2d(cam_tf, screen_pos).ok())
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn apply_player_movement(
     time: Res<Time>,
     keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
     movement: Res<MovementInput>,
     walls: Res<WallColliders>,
+    settings: Res<GameSettings>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
     mut query: Query<
         (
             &mut Transform,
             &mut PlayerAnimation,
             &mut PlayerDash,
             &PlayerHitbox,
+            Option<&MetaBonuses>,
+            Option<&Buffs>,
         ),
         With<Player>,
     >,
 ) {
     let dt = time.delta_secs();
-    let Ok((mut transform, mut anim, mut dash, hitbox)) = query.single_mut() else {
+    let Ok((mut transform, mut anim, mut dash, hitbox, bonuses, buffs)) = query.single_mut() else {
         return;
     };
 
@@ -177,24 +278,27 @@ fn apply_player_movement(
         }
     }
 
-    if move_dir != Vec2::ZERO {
-        anim.direction = if move_dir.x.abs() > move_dir.y.abs() {
-            if move_dir.x > 0.0 {
-                PlayerDirection::Right
-            } else {
-                PlayerDirection::Left
-            }
-        } else if move_dir.y > 0.0 {
-            PlayerDirection::Up
-        } else {
-            PlayerDirection::Down
+    // 攻击片段播放中保持出手方向；朝向鼠标模式下鼠标不在窗口内时退回移动方向
+    if !anim.is_attacking() {
+        let cursor_dir = match settings.face_mode {
+            FaceMode::Cursor => window_q
+                .single()
+                .ok()
+                .zip(camera_q.single().ok())
+                .and_then(|(window, (camera, cam_tf))| cursor_world_pos(window, camera, cam_tf))
+                .map(|world_pos| world_pos - transform.translation.truncate()),
+            FaceMode::Movement => None,
         };
+        if let Some(direction) = PlayerDirection::from_vec2(cursor_dir.unwrap_or(move_dir)) {
+            anim.direction = direction;
+        }
     }
 
-    let mut speed = PLAYER_SPEED;
+    let mut speed = PLAYER_SPEED * bonuses.map_or(1.0, |b| b.speed_factor) * buffs.map_or(1.0, Buffs::speed_factor);
     if dash.is_dashing {
         speed *= DASH_MULTIPLIER;
-    } else if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+    } else if keyboard.pressed(bindings.sprint) || keyboard.pressed(KeyCode::ShiftRight) {
+        // 右 Shift 一直保留为冲刺跑键，改绑不影响它
         speed *= SPRINT_MULTIPLIER;
     }
 
@@ -206,8 +310,12 @@ fn apply_player_movement(
     }
 
     let delta = move_dir.normalize_or_zero() * speed * dt;
-    let mut pos = transform.translation.truncate();
-    pos = move_with_walls(pos, delta, hitbox.half, &walls.aabbs);
+    let start = transform.translation.truncate();
+    let pos = move_with_walls(start, delta, hitbox.half, &walls.aabbs);
+    // 冲刺正面撞墙就别白白耗着冷却，提前结束并返还一部分
+    if dash.is_dashing && movement_blocked(delta, pos - start) {
+        dash.end_blocked();
+    }
 
     transform.translation.x = pos.x;
     transform.translation.y = pos.y;
@@ -218,7 +326,7 @@ fn aabb_intersects(a_center: Vec2, a_half: Vec2, b_center: Vec2, b_half: Vec2) -
     d.x.abs() < (a_half.x + b_half.x) && d.y.abs() < (a_half.y + b_half.y)
 }
 
-fn move_with_walls(start: Vec2, delta: Vec2, player_half: Vec2, walls: &[(Vec2, Vec2)]) -> Vec2 {
+pub(crate) fn move_with_walls(start: Vec2, delta: Vec2, player_half: Vec2, walls: &[(Vec2, Vec2)]) -> Vec2 {
     if walls.is_empty() || delta == Vec2::ZERO {
         return start + delta;
     }
@@ -252,46 +360,44 @@ fn move_with_walls(start: Vec2, delta: Vec2, player_half: Vec2, walls: &[(Vec2,
     pos
 }
 
+/// 行走时在落脚帧（偶数帧）播放脚步声，音调随机浮动一点
 fn update_player_animation(
     time: Res<Time>,
     mut query: Query<(&mut Sprite, &mut PlayerAnimation), With<Player>>,
+    mut sfx_tx: MessageWriter<PlaySfx>,
 ) {
     for (mut sprite, mut anim) in &mut query {
-        if !anim.initialized {
+        if anim.walk.layout.is_none() {
             continue;
         }
-
-        anim.timer.tick(time.delta());
-
-        if anim.is_moving {
-            if anim.timer.just_finished() {
-                anim.frame = (anim.frame + 1) % anim.columns.max(1);
+        let row = anim.direction.row_index();
+
+        if let AnimationState::Attack { clip, elapsed } = anim.state {
+            let elapsed = elapsed + time.delta_secs();
+            if elapsed < clip.duration() {
+                let frames = clip.frames();
+                let index = ((elapsed / clip.frame_secs()) as usize).min(frames.len() - 1);
+                anim.walk.frame = frames[index];
+                anim.state = AnimationState::Attack { clip, elapsed };
+                anim.walk.apply(&mut sprite, row);
+                continue;
             }
-        } else {
-            anim.frame = 0;
+            // 攻击结束，回到行走 / 站立，从第一帧重新走
+            anim.walk.restart();
         }
 
-        update_sprite_rect(&mut sprite, &anim);
-    }
-}
-
-fn update_sprite_rect(sprite: &mut Sprite, anim: &PlayerAnimation) {
-    let frame_w = anim.frame_size.x;
-    let frame_h = anim.frame_size.y;
-    if frame_w <= 0.0 || frame_h <= 0.0 {
-        return;
+        let moving = anim.is_moving;
+        anim.state = if moving { AnimationState::Walk } else { AnimationState::Idle };
+        let stepped = anim.walk.tick(time.delta(), moving);
+        if stepped && anim.walk.frame % 2 == 0 {
+            let pitch = rand::thread_rng().gen_range(FOOTSTEP_PITCH_MIN..FOOTSTEP_PITCH_MAX);
+            sfx_tx.write(PlaySfx { sfx: Sfx::Footstep, pitch });
+        }
+        anim.walk.apply(&mut sprite, row);
     }
-
-    let col = anim.frame as f32;
-    let row = anim.direction.row_index() as f32;
-
-    let min = Vec2::new(col * frame_w, row * frame_h);
-    let max = min + anim.frame_size;
-
-    sprite.rect = Some(Rect { min, max });
 }
 
-fn follow_player_camera(
+pub(crate) fn follow_player_camera(
     player_query: Query<&Transform, With<Player>>,
     mut camera_query: Query<&mut Transform, (With<PlayerCamera>, Without<Player>)>,
 ) {
@@ -306,41 +412,9 @@ fn follow_player_camera(
     camera_transform.translation.y = player_transform.translation.y;
 }
 
-fn attach_ldtk_player(
-    mut commands: Commands,
-    query: Query<(Entity, &EntityInstance), Added<EntityInstance>>,
-    sprite_q: Query<&Sprite>,
-    player_tex: Res<PlayerTexture>,
-) {
-    for (entity, instance) in &query {
-        if instance.identifier == "Player" {
-            let has_sprite = sprite_q.get(entity).is_ok();
-
-            if !has_sprite {
-                let texture = player_tex.0.clone();
-                let mut sprite = Sprite::from_image(texture);
-                sprite.custom_size = Some(Vec2::splat(48.0));
-                sprite.color = Color::WHITE;
-
-                commands
-                    .entity(entity)
-                    .insert((sprite, PlayerAnimation::default()));
-            } else {
-                commands.entity(entity).insert(PlayerAnimation::default());
-            }
-
-            commands.entity(entity).insert((
-                Player,
-                PlayerDash::default(),
-                Health {
-                    current: 100.0,
-                    max: 100.0,
-                },
-            ));
-        }
-    }
-}
-
+/// 本次关卡加载是否已经放置过玩家。玩家只由 `spawn_or_move_player_from_ldtk` 生成，
+/// 回到标题（关卡和玩家都被清掉）或游戏结束（玩家死亡被移除，之后可能直接读档回 InGame）时重置；
+/// 暂停后回到 InGame 不会重置，否则玩家会被拉回出生点
 #[derive(Resource, Default)]
 struct PlayerSpawnedFromLdtk(pub bool);
 
@@ -349,11 +423,13 @@ impl Plugin for MovementPlugin {
         app.init_resource::<PlayerSpawnedFromLdtk>()
             // load player texture at startup
             .add_systems(Startup, load_player_texture)
-            .add_systems(OnEnter(GameState::InGame), reset_player_spawn_flag)
+            .add_systems(OnEnter(GameState::MainMenu), reset_player_spawn_flag)
+            .add_systems(OnEnter(GameState::GameOver), reset_player_spawn_flag)
             .add_systems(
                 Update,
                 (
-                    spawn_or_move_player_from_ldtk
+                    (spawn_or_move_player_from_ldtk, despawn_duplicate_players)
+                        .chain()
                         .run_if(in_state(GameState::InGame))
                         .before(apply_player_movement),
                     init_player_animation.run_if(in_state(GameState::InGame)),
@@ -384,9 +460,11 @@ fn spawn_or_move_player_from_ldtk(
         return;
     }
 
+    // 优先用专门的 PlayerSpawn 点，没有再退回关卡里的 Player 实体位置
     let Some((spawn_e, _inst)) = spawn_points
         .iter()
-        .find(|(_, inst)| inst.identifier == "PlayerSpawn" || inst.identifier == "Player")
+        .find(|(_, inst)| inst.identifier == "PlayerSpawn")
+        .or_else(|| spawn_points.iter().find(|(_, inst)| inst.identifier == "Player"))
     else {
         return;
     };
@@ -404,33 +482,53 @@ fn spawn_or_move_player_from_ldtk(
         cur = parents.get(e).ok().map(|p| p.parent());
     }
 
-    world.z = 10.0;
+    world.z = layers::PLAYER;
 
-    if let Ok(mut t) = player_q.single_mut() {
+    // 已有玩家（例如读档先生成了）就只挪位置，绝不再生成第二个
+    if let Some(mut t) = player_q.iter_mut().next() {
         t.translation = world;
     } else {
         // Directly create a textured sprite using the globally loaded texture.
         let texture = player_tex.0.clone();
+        let sprite_size = Vec2::splat(PLAYER_SPRITE_SIZE);
         let mut sprite = Sprite::from_image(texture);
-        sprite.custom_size = Some(Vec2::splat(24.0));
+        sprite.custom_size = Some(sprite_size);
 
         commands.spawn((
             sprite,
             Transform::from_translation(world),
             Player,
             PlayerAnimation::default(),
-            PlayerDash::default(),
-            PlayerHitbox::default(),
-            Health {
-                current: 100.0,
-                max: 100.0,
+            // player.png：4 列行走帧 × 上左下右 4 行，帧不是正方形
+            SpriteSheetConfig {
+                columns: Some(4),
+                rows: Some(4),
+                ..default()
             },
+            PlayerDash::default(),
+            PlayerHitbox::from_sprite_size(sprite_size),
+            Health::new(100.0),
+            Invulnerable::default(),
         ));
     }
 
     flag.0 = true;
 }
 
+/// 兜底：场上出现多个玩家时只保留最早生成的那个，保证各处 `single()` 查询可用
+fn despawn_duplicate_players(mut commands: Commands, players: Query<Entity, With<Player>>) {
+    if players.iter().len() <= 1 {
+        return;
+    }
+
+    let mut all: Vec<Entity> = players.iter().collect();
+    all.sort();
+    warn!("发现 {} 个玩家实体，只保留 {:?}", all.len(), all[0]);
+    for extra in &all[1..] {
+        commands.entity(*extra).despawn();
+    }
+}
+
 pub(crate) fn toggle_debug_colliders(
     keys: Res<ButtonInput<KeyCode>>,
     mut dbg: ResMut<DebugColliders>,
@@ -444,11 +542,125 @@ pub(crate) fn toggle_debug_colliders(
 #[derive(Resource, Default)]
 pub(crate) struct DebugColliders(pub bool);
 
+/// F3 打开后绘制墙体 AABB（红）和玩家碰撞箱（绿）
 pub(crate) fn draw_colliders_gizmos(
-    _dbg: Res<DebugColliders>,
-    _walls: Res<crate::ldtk_collision::WallColliders>,
-    _mut_gizmos: Gizmos,
-    _player: Query<(&Transform, &PlayerHitbox), With<Player>>,
+    dbg: Res<DebugColliders>,
+    walls: Res<crate::ldtk_collision::WallColliders>,
+    mut gizmos: Gizmos,
+    player: Query<(&Transform, &PlayerHitbox), With<Player>>,
 ) {
-    // 若需要在调试时绘制碰撞箱，可在此实现。当前保留空实现以免未实现时报错。
+    if !dbg.0 {
+        return;
+    }
+
+    let wall_color = Color::srgb(1.0, 0.25, 0.25);
+    for (center, half) in &walls.aabbs {
+        gizmos.rect_2d(Isometry2d::from_translation(*center), *half * 2.0, wall_color);
+    }
+
+    let player_color = Color::srgb(0.3, 1.0, 0.4);
+    for (tf, hitbox) in &player {
+        let center = tf.translation.truncate();
+        gizmos.rect_2d(Isometry2d::from_translation(center), hitbox.half * 2.0, player_color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 原点处一块 16 x 16 的墙，玩家半尺寸 4 x 4
+    const WALL: (Vec2, Vec2) = (Vec2::ZERO, Vec2::splat(8.0));
+    const HALF: Vec2 = Vec2::splat(4.0);
+
+    #[test]
+    fn stops_at_wall_boundary_from_each_side() {
+        let walls = [WALL];
+        // 贴墙时中心离墙中心正好 墙半宽 + 玩家半宽 = 12
+        assert_eq!(move_with_walls(Vec2::new(-20.0, 0.0), Vec2::new(15.0, 0.0), HALF, &walls), Vec2::new(-12.0, 0.0));
+        assert_eq!(move_with_walls(Vec2::new(20.0, 0.0), Vec2::new(-15.0, 0.0), HALF, &walls), Vec2::new(12.0, 0.0));
+        assert_eq!(move_with_walls(Vec2::new(0.0, -20.0), Vec2::new(0.0, 15.0), HALF, &walls), Vec2::new(0.0, -12.0));
+        assert_eq!(move_with_walls(Vec2::new(0.0, 20.0), Vec2::new(0.0, -15.0), HALF, &walls), Vec2::new(0.0, 12.0));
+    }
+
+    #[test]
+    fn diagonal_move_slides_along_blocked_axis() {
+        let walls = [WALL];
+        // 向右上斜走撞上墙的左侧：X 被挡住，Y 照常移动
+        let pos = move_with_walls(Vec2::new(-20.0, 0.0), Vec2::new(15.0, 5.0), HALF, &walls);
+        assert_eq!(pos, Vec2::new(-12.0, 5.0));
+
+        // 向右下斜走压在墙顶：Y 被挡住，X 照常移动
+        let pos = move_with_walls(Vec2::new(-5.0, 14.0), Vec2::new(3.0, -6.0), HALF, &walls);
+        assert_eq!(pos, Vec2::new(-2.0, 12.0));
+    }
+
+    #[test]
+    fn touching_wall_without_overlap_is_not_blocked() {
+        let walls = [WALL];
+        // 刚好贴着墙边滑过，不算相交
+        let pos = move_with_walls(Vec2::new(-12.0, -20.0), Vec2::new(0.0, 40.0), HALF, &walls);
+        assert_eq!(pos, Vec2::new(-12.0, 20.0));
+    }
+
+    #[test]
+    fn dash_into_wall_is_blocked_but_sliding_is_not() {
+        let walls = [WALL];
+        let start = Vec2::new(-12.0, 0.0);
+        let head_on = Vec2::new(10.0, 0.0);
+        assert!(movement_blocked(head_on, move_with_walls(start, head_on, HALF, &walls) - start));
+        let diagonal = Vec2::new(7.0, 7.0);
+        assert!(!movement_blocked(diagonal, move_with_walls(start, diagonal, HALF, &walls) - start));
+    }
+
+    #[test]
+    fn blocked_dash_refunds_unused_cooldown() {
+        let mut dash = PlayerDash {
+            is_dashing: true,
+            remaining: DASH_DURATION * 0.5,
+            cooldown: DASH_COOLDOWN,
+            direction: Vec2::X,
+        };
+        dash.end_blocked();
+        assert!(!dash.is_dashing);
+        assert!((dash.cooldown - DASH_COOLDOWN * (1.0 - DASH_BLOCKED_REFUND * 0.5)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn no_walls_passes_movement_through() {
+        let start = Vec2::new(3.0, -7.0);
+        let delta = Vec2::new(100.0, 50.0);
+        assert_eq!(move_with_walls(start, delta, HALF, &[]), start + delta);
+        assert_eq!(move_with_walls(start, Vec2::ZERO, HALF, &[WALL]), start);
+    }
+
+    #[test]
+    fn loading_after_game_over_spawns_exactly_one_player() {
+        use crate::test_support::{headless_app, step};
+
+        let mut app = headless_app();
+        app.insert_resource(PlayerTexture(Handle::default())).add_plugins(MovementPlugin);
+        // 关卡里的出生点：LDtk 实体挂在层下面
+        let level = app.world_mut().spawn(Transform::from_xyz(100.0, 50.0, 0.0)).id();
+        app.world_mut().spawn((
+            EntityInstance { identifier: "PlayerSpawn".into(), ..default() },
+            Transform::default(),
+            ChildOf(level),
+        ));
+        let mut players = app.world_mut().query_filtered::<Entity, With<Player>>();
+
+        step(&mut app, 2);
+        let player = players.single(app.world()).unwrap();
+
+        // 死亡：玩家被移除，进入 GameOver；再从失败界面读档直接回到 InGame
+        app.world_mut().get_mut::<Health>(player).unwrap().current = 0.0;
+        step(&mut app, 2);
+        assert_eq!(players.iter(app.world()).count(), 0);
+        app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::GameOver);
+        step(&mut app, 2);
+        app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::InGame);
+        step(&mut app, 3);
+
+        assert_eq!(players.iter(app.world()).count(), 1);
+    }
 }
@@ -0,0 +1,156 @@
+// src/new_run.rs
+//! 新开一局：主菜单点“开始”时打上标记，进入 InGame 时清掉读档目标、把本局资源全部重置。
+//! 暂停中重新读档、失败界面读档打另一个标记：保留读档目标，清掉场上的敌人，本局资源同样重置。
+//! 主菜单的继续 / 读档不打标记（回标题时已经清过）；普通的从暂停回到 InGame 也不会触发。
+
+use bevy::prelude::*;
+
+use crate::checkpoint::{Checkpoint, ReviveCharges};
+use crate::difficulty_scaling::DifficultyScaling;
+use crate::enemy::{Enemy, EnemySpawnTimer};
+use crate::quest::QuestLog;
+use crate::run_stats::RunStats;
+use crate::save::{CurrentSlot, PendingLoad};
+use crate::screen_shake::ScreenShake;
+use crate::state::GameState;
+use crate::transition::TransitionRequest;
+use crate::wallet::Wallet;
+
+/// 下次进入 InGame 是否是全新的一局（只有主菜单的“开始”会置 true）
+#[derive(Resource, Default, Debug)]
+pub struct NewRunPending(pub bool);
+
+/// 下次进入 InGame 是否是读档回来（暂停菜单的“重新读档”、存档面板的“读取”、失败界面的存档按钮会置 true）
+#[derive(Resource, Default, Debug)]
+pub struct ReloadPending(pub bool);
+
+pub struct NewRunPlugin;
+
+impl Plugin for NewRunPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NewRunPending>()
+            .init_resource::<ReloadPending>()
+            .add_systems(OnEnter(GameState::InGame), reset_run_state.run_if(new_run_pending.or(reload_pending)));
+    }
+}
+
+/// 开新局的唯一入口：打上标记再切到 InGame
+pub fn start_new_run(pending: &mut NewRunPending, transition_tx: &mut MessageWriter<TransitionRequest>) {
+    pending.0 = true;
+    transition_tx.write(TransitionRequest(GameState::InGame));
+}
+
+fn new_run_pending(pending: Res<NewRunPending>) -> bool {
+    pending.0
+}
+
+fn reload_pending(pending: Res<ReloadPending>) -> bool {
+    pending.0
+}
+
+/// 回标题时各模块已经各自清过一遍，这里再统一兜底，保证新局不会沿用自动存档的目标或上一局的残留。
+/// 读档时读档目标要留着，换成清掉读档前场上的敌人
+#[allow(clippy::too_many_arguments)]
+fn reset_run_state(
+    mut commands: Commands,
+    mut pending: ResMut<NewRunPending>,
+    mut reload: ResMut<ReloadPending>,
+    enemies: Query<Entity, With<Enemy>>,
+    mut pending_load: ResMut<PendingLoad>,
+    mut current_slot: ResMut<CurrentSlot>,
+    mut wallet: ResMut<Wallet>,
+    mut stats: ResMut<RunStats>,
+    mut scaling: ResMut<DifficultyScaling>,
+    mut quests: ResMut<QuestLog>,
+    mut checkpoint: ResMut<Checkpoint>,
+    mut charges: ResMut<ReviveCharges>,
+    mut shake: ResMut<ScreenShake>,
+    mut spawn_timer: ResMut<EnemySpawnTimer>,
+) {
+    if pending.0 {
+        pending_load.file_name = None;
+        current_slot.file_name = None;
+    }
+    if reload.0 {
+        for e in &enemies {
+            commands.entity(e).despawn();
+        }
+    }
+    pending.0 = false;
+    reload.0 = false;
+    *wallet = Wallet::default();
+    *stats = RunStats::default();
+    *scaling = DifficultyScaling::default();
+    *quests = QuestLog::default();
+    *checkpoint = Checkpoint::default();
+    *charges = ReviveCharges::default();
+    shake.trauma = 0.0;
+    *spawn_timer = EnemySpawnTimer::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{headless_app, spawn_enemy, spawn_player};
+
+    fn go_to(app: &mut App, state: GameState) {
+        app.world_mut().resource_mut::<NextState<GameState>>().set(state);
+        app.update();
+    }
+
+    #[test]
+    fn reloading_from_pause_resets_the_run_but_keeps_the_slot() {
+        let mut app = headless_app();
+        app.init_resource::<Wallet>()
+            .init_resource::<QuestLog>()
+            .init_resource::<ScreenShake>()
+            .add_plugins(NewRunPlugin);
+        spawn_player(&mut app, Vec2::ZERO);
+        let enemy = spawn_enemy(&mut app, Vec2::new(0.0, 300.0), 50.0);
+        app.world_mut().resource_mut::<Wallet>().gold = 80;
+        app.world_mut().resource_mut::<RunStats>().kills = 12;
+
+        go_to(&mut app, GameState::Paused);
+        app.world_mut().resource_mut::<CurrentSlot>().file_name = Some("25.12.06.1.json".into());
+        app.world_mut().resource_mut::<ReloadPending>().0 = true;
+        go_to(&mut app, GameState::InGame);
+
+        assert!(app.world().get_entity(enemy).is_err(), "读档前的敌人应该被清掉");
+        assert_eq!(app.world().resource::<Wallet>().gold, 0);
+        assert_eq!(app.world().resource::<RunStats>().kills, 0);
+        assert_eq!(app.world().resource::<CurrentSlot>().file_name.as_deref(), Some("25.12.06.1.json"));
+        assert!(!app.world().resource::<ReloadPending>().0);
+    }
+
+    #[test]
+    fn loading_from_game_over_resets_the_run() {
+        use crate::game_over_ui::{handle_manual_save_slot_buttons, ManualSaveSlotButton};
+
+        let mut app = headless_app();
+        app.init_resource::<Wallet>()
+            .init_resource::<QuestLog>()
+            .init_resource::<ScreenShake>()
+            .add_plugins(NewRunPlugin)
+            .add_systems(Update, handle_manual_save_slot_buttons.run_if(in_state(GameState::GameOver)));
+        app.world_mut().resource_mut::<Wallet>().gold = 80;
+        app.world_mut().resource_mut::<DifficultyScaling>().level = 3;
+        app.world_mut().resource_mut::<Checkpoint>().position = Some(Vec2::new(10.0, 20.0));
+
+        go_to(&mut app, GameState::GameOver);
+        app.world_mut().spawn((
+            Button,
+            Interaction::Pressed,
+            BackgroundColor::default(),
+            ManualSaveSlotButton { file_name: "25.12.06.1.json".into() },
+        ));
+        app.update();
+        assert!(app.world().resource::<ReloadPending>().0);
+        go_to(&mut app, GameState::InGame);
+
+        assert_eq!(app.world().resource::<Wallet>().gold, 0);
+        assert_eq!(app.world().resource::<DifficultyScaling>().level, 0);
+        assert!(app.world().resource::<Checkpoint>().position.is_none());
+        assert_eq!(app.world().resource::<PendingLoad>().file_name.as_deref(), Some("25.12.06.1.json"));
+        assert!(!app.world().resource::<ReloadPending>().0);
+    }
+}
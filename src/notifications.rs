@@ -0,0 +1,158 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// 单条提示的显示时长（秒）
+const TOAST_LIFETIME_SECS: f32 = 3.0;
+/// 结束前多少秒开始淡出
+const TOAST_FADE_SECS: f32 = 0.6;
+/// 同时显示的最大条数，多出来的在队列里排队
+const MAX_VISIBLE_TOASTS: usize = 5;
+const TOAST_BG_ALPHA: f32 = 0.85;
+
+/// 游戏内提示队列：任何系统拿到 `ResMut<Notifications>` 后调用 `notify` 即可
+#[derive(Resource, Default)]
+pub struct Notifications {
+    queue: VecDeque<String>,
+}
+
+impl Notifications {
+    pub fn notify(&mut self, text: impl Into<String>) {
+        self.queue.push_back(text.into());
+    }
+}
+
+/// 淡出结束的提示条不销毁，隐藏后放在这里等下次复用。
+/// 同时可见的条数有上限，所以池子大小天然不会超过 `MAX_VISIBLE_TOASTS`
+#[derive(Resource, Default)]
+struct ToastPool {
+    free: Vec<Entity>,
+}
+
+#[derive(Component)]
+struct ToastRoot;
+
+#[derive(Component)]
+struct Toast {
+    timer: Timer,
+}
+
+pub struct NotificationsPlugin;
+
+impl Plugin for NotificationsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Notifications>()
+            .init_resource::<ToastPool>()
+            .add_systems(Startup, setup_toast_root)
+            .add_systems(Update, (show_pending_toasts, fade_toasts).chain());
+    }
+}
+
+fn setup_toast_root(mut commands: Commands) {
+    commands.spawn((
+        ToastRoot,
+        GlobalZIndex(200),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(16.0),
+            right: Val::Px(16.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::FlexEnd,
+            row_gap: Val::Px(6.0),
+            ..default()
+        },
+    ));
+}
+
+#[allow(clippy::collapsible_if, clippy::too_many_arguments)]
+fn show_pending_toasts(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut notifications: ResMut<Notifications>,
+    mut pool: ResMut<ToastPool>,
+    root_q: Query<Entity, With<ToastRoot>>,
+    toasts_q: Query<(), With<Toast>>,
+    children_q: Query<&Children>,
+    mut text_q: Query<&mut Text>,
+) {
+    if notifications.queue.is_empty() {
+        return;
+    }
+    let Ok(root) = root_q.single() else { return; };
+
+    let font: Handle<Font> = asset_server.load("fonts/YuFanLixing.otf");
+    let mut visible = toasts_q.iter().count();
+
+    while visible < MAX_VISIBLE_TOASTS {
+        let Some(text) = notifications.queue.pop_front() else { break; };
+
+        // 池里有隐藏的提示条就换掉文字重新挂到末尾（保证新提示排在最下面）
+        if let Some(toast) = pool.free.pop() {
+            if let Some(label) = children_q.get(toast).ok().and_then(|c| c.first().copied()) {
+                if let Ok(mut label_text) = text_q.get_mut(label) {
+                    label_text.0 = text;
+                }
+            }
+            commands
+                .entity(toast)
+                .remove::<ChildOf>()
+                .insert(Toast { timer: Timer::from_seconds(TOAST_LIFETIME_SECS, TimerMode::Once) });
+            commands.entity(root).add_child(toast);
+            visible += 1;
+            continue;
+        }
+
+        let toast = commands
+            .spawn((
+                Toast { timer: Timer::from_seconds(TOAST_LIFETIME_SECS, TimerMode::Once) },
+                Node {
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.08, 0.08, 0.10, TOAST_BG_ALPHA)),
+            ))
+            .with_children(|t| {
+                t.spawn((
+                    Text::new(text),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            })
+            .id();
+
+        commands.entity(root).add_child(toast);
+        visible += 1;
+    }
+}
+
+/// 淡出提示条；结束后隐藏（不占布局）并回收到池里
+fn fade_toasts(
+    time: Res<Time<Real>>,
+    mut commands: Commands,
+    mut pool: ResMut<ToastPool>,
+    mut toasts_q: Query<(Entity, &mut Toast, &mut BackgroundColor, &mut Node, &Children)>,
+    mut text_q: Query<&mut TextColor>,
+) {
+    for (entity, mut toast, mut bg, mut node, children) in &mut toasts_q {
+        toast.timer.tick(time.delta());
+        if toast.timer.is_finished() {
+            node.display = Display::None;
+            commands.entity(entity).remove::<Toast>();
+            pool.free.push(entity);
+            continue;
+        }
+        // 复用的提示条在这里重新显示
+        node.display = Display::Flex;
+
+        let alpha = (toast.timer.remaining_secs() / TOAST_FADE_SECS).min(1.0);
+        bg.0.set_alpha(TOAST_BG_ALPHA * alpha);
+        for child in children.iter() {
+            if let Ok(mut color) = text_q.get_mut(child) {
+                color.0.set_alpha(alpha);
+            }
+        }
+    }
+}
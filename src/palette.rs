@@ -0,0 +1,84 @@
+// src/palette.rs
+//! 配色：血条、受伤方向提示、伤害数字、暴击和回血飘字、受击闪白以及精英染色统一从 `Palette` 取色，
+//! 不在各处写死颜色。设置里打开色盲模式时整体换成蓝 / 橙配色，不再依赖红绿区分。
+
+use bevy::prelude::*;
+
+use crate::ui::types::GameSettings;
+
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct Palette {
+    /// 玩家血条和护盾层
+    pub hp_fill: Color,
+    pub shield_fill: Color,
+    /// 屏幕边缘的受伤方向提示
+    pub damage_indicator: Color,
+    /// 伤害飘字：普通 / 暴击 / 吸血回复
+    pub damage_number: Color,
+    pub crit_number: Color,
+    pub heal_number: Color,
+    /// 敌人受击闪烁
+    pub hit_flash: Color,
+    /// 精英词缀染色
+    pub elite_swift: Color,
+    pub elite_armored: Color,
+    pub elite_explosive: Color,
+}
+
+impl Palette {
+    pub const STANDARD: Self = Self {
+        hp_fill: Color::srgb(0.8, 0.15, 0.15),
+        shield_fill: Color::srgba(0.3, 0.6, 1.0, 0.75),
+        damage_indicator: Color::srgba(0.9, 0.1, 0.1, 0.8),
+        damage_number: Color::WHITE,
+        crit_number: Color::srgb(1.0, 0.85, 0.2),
+        heal_number: Color::srgb(0.3, 0.95, 0.4),
+        hit_flash: Color::srgb(1.0, 0.45, 0.45),
+        elite_swift: Color::srgb(0.55, 0.85, 1.0),
+        elite_armored: Color::srgb(0.75, 0.75, 0.8),
+        elite_explosive: Color::srgb(1.0, 0.45, 0.35),
+    };
+
+    /// 色盲模式：红 → 橙，绿 → 蓝
+    pub const COLORBLIND: Self = Self {
+        hp_fill: Color::srgb(0.95, 0.55, 0.1),
+        shield_fill: Color::srgba(0.3, 0.6, 1.0, 0.75),
+        damage_indicator: Color::srgba(1.0, 0.6, 0.0, 0.8),
+        damage_number: Color::WHITE,
+        crit_number: Color::srgb(1.0, 0.7, 0.0),
+        heal_number: Color::srgb(0.35, 0.7, 1.0),
+        hit_flash: Color::srgb(1.0, 0.7, 0.3),
+        elite_swift: Color::srgb(0.4, 0.65, 1.0),
+        elite_armored: Color::srgb(0.8, 0.8, 0.85),
+        elite_explosive: Color::srgb(1.0, 0.6, 0.1),
+    };
+
+    pub fn for_settings(settings: &GameSettings) -> &'static Self {
+        if settings.colorblind { &Self::COLORBLIND } else { &Self::STANDARD }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+pub struct PalettePlugin;
+
+impl Plugin for PalettePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Palette>().add_systems(Update, sync_palette);
+    }
+}
+
+/// 设置变化时切换配色；只在真正换了配色时写资源，方便其它系统用 is_changed 判断
+fn sync_palette(settings: Res<GameSettings>, mut palette: ResMut<Palette>) {
+    if !settings.is_changed() {
+        return;
+    }
+    let wanted = Palette::for_settings(&settings);
+    if *palette != *wanted {
+        *palette = wanted.clone();
+    }
+}
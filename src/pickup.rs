@@ -0,0 +1,418 @@
+// src/pickup.rs
+//! 地面掉落物：敌人死亡时概率掉落血球、武器、背包扩展、磁石、荆棘遗物或限时强化，进入玩家的拾取半径后被吸过来，碰到玩家即生效。
+//! 拾取半径是玩家属性 `PickupRadius`，捡到磁石会永久（本局）扩大；荆棘遗物同样叠加到玩家的 `Thorns` 上。
+//! 掉落物上下浮动，位置记在 `Pickup::base` 里，浮动只叠加在显示位置上。
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::buffs::{BuffKind, Buffs};
+use crate::combat_core::EnemyDied;
+use crate::equipment::{ExpandInventoryMsg, ItemDatabase, ItemId, ItemInstance, LootItemMsg};
+use crate::health::{Health, Thorns};
+use crate::i18n::tr;
+use crate::inventory::Inventory;
+use crate::layers;
+use crate::movement::Player;
+use crate::notifications::Notifications;
+use crate::state::GameState;
+use crate::ui::types::GameSettings;
+
+/// 敌人死亡时掉血球的概率
+const HEALTH_ORB_DROP_CHANCE: f64 = 0.25;
+const HEALTH_ORB_HEAL: f32 = 10.0;
+const HEALTH_ORB_SIZE: f32 = 10.0;
+/// 武器掉落概率；掉落时按品质随机属性
+const WEAPON_DROP_CHANCE: f64 = 0.04;
+const WEAPON_DROP_SIZE: f32 = 16.0;
+/// 背包扩展的掉落概率和每个增加的格子数（正好一行）
+const BAG_EXPANSION_DROP_CHANCE: f64 = 0.02;
+const BAG_EXPANSION_SLOTS: usize = 10;
+const BAG_EXPANSION_SIZE: f32 = 12.0;
+/// 磁石：扩大拾取半径，极少掉落；半径有上限
+const MAGNET_RELIC_DROP_CHANCE: f64 = 0.01;
+const MAGNET_RELIC_BONUS: f32 = 30.0;
+const MAGNET_RELIC_SIZE: f32 = 12.0;
+const MAX_PICKUP_RADIUS: f32 = 300.0;
+/// 荆棘遗物：每个反弹的接触伤害比例，叠加有上限
+const THORNS_RELIC_DROP_CHANCE: f64 = 0.01;
+const THORNS_RELIC_FRACTION: f32 = 0.25;
+const THORNS_RELIC_SIZE: f32 = 12.0;
+const MAX_THORNS_FRACTION: f32 = 1.0;
+/// 限时强化道具：掉落概率和持续时间
+const POWER_UP_DROP_CHANCE: f64 = 0.03;
+const POWER_UP_DURATION: f32 = 10.0;
+const POWER_UP_SIZE: f32 = 12.0;
+/// 玩家默认的拾取半径：进入这个距离后开始被吸向玩家
+pub const BASE_PICKUP_RADIUS: f32 = 90.0;
+/// 刚进入半径时的吸附速度；越靠近玩家越快，贴身时是这个速度的 (1 + MAGNET_ACCELERATION) 倍
+const MAGNET_SPEED: f32 = 260.0;
+const MAGNET_ACCELERATION: f32 = 2.0;
+/// 进入这个距离即拾取
+const PICKUP_RADIUS: f32 = 18.0;
+/// 浮动幅度（像素）和频率（弧度/秒）
+const BOB_HEIGHT: f32 = 3.0;
+const BOB_SPEED: f32 = 4.0;
+
+/// 所有地面掉落物共用：真实位置和浮动相位
+#[derive(Component)]
+pub struct Pickup {
+    pub base: Vec2,
+    pub age: f32,
+}
+
+/// 血球：拾取时回复 amount 点生命
+#[derive(Component)]
+pub struct HealthOrb {
+    pub amount: f32,
+}
+
+/// 地上的武器：拾取时放进背包，背包满了就留在地上
+#[derive(Component)]
+pub struct WeaponDrop {
+    pub item: ItemInstance,
+}
+
+/// 背包扩展：拾取时背包增加 extra 格
+#[derive(Component)]
+pub struct BagExpansion {
+    pub extra: usize,
+}
+
+/// 磁石：拾取时玩家的拾取半径增加 bonus
+#[derive(Component)]
+pub struct MagnetRelic {
+    pub bonus: f32,
+}
+
+/// 荆棘遗物：拾取时玩家的荆棘反弹比例增加 fraction
+#[derive(Component)]
+pub struct ThornsRelic {
+    pub fraction: f32,
+}
+
+/// 限时强化：拾取时给玩家加上 duration 秒的增益
+#[derive(Component)]
+pub struct PowerUp {
+    pub kind: BuffKind,
+    pub duration: f32,
+}
+
+/// 玩家的拾取半径；没有这个组件时按 `BASE_PICKUP_RADIUS` 算
+#[derive(Component, Clone, Copy, Debug)]
+pub struct PickupRadius(pub f32);
+
+impl Default for PickupRadius {
+    fn default() -> Self {
+        Self(BASE_PICKUP_RADIUS)
+    }
+}
+
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                drop_loot,
+                magnet_pickups,
+                bob_pickups,
+                collect_health_orbs,
+                collect_weapon_drops,
+                collect_bag_expansions,
+                collect_magnet_relics,
+                collect_thorns_relics,
+                collect_power_ups,
+            )
+                .chain()
+                .run_if(in_state(GameState::InGame)),
+        )
+        .add_systems(OnEnter(GameState::MainMenu), cleanup_pickups);
+    }
+}
+
+/// 敌人死亡时按概率在原地掉血球、随机武器，极小概率再掉一个背包扩展
+fn drop_loot(
+    mut commands: Commands,
+    mut died_rx: MessageReader<EnemyDied>,
+    asset_server: Res<AssetServer>,
+    db: Res<ItemDatabase>,
+) {
+    let mut rng = rand::thread_rng();
+    for ev in died_rx.read() {
+        if rng.gen_bool(HEALTH_ORB_DROP_CHANCE) {
+            spawn_health_orb(&mut commands, ev.position, HEALTH_ORB_HEAL);
+        }
+        if rng.gen_bool(WEAPON_DROP_CHANCE) {
+            let id = ItemId::WEAPONS[rng.gen_range(0..ItemId::WEAPONS.len())];
+            if let Some(item) = db.roll(id) {
+                spawn_weapon_drop(&mut commands, &asset_server, ev.position, item);
+            }
+        }
+        if rng.gen_bool(BAG_EXPANSION_DROP_CHANCE) {
+            spawn_bag_expansion(&mut commands, ev.position, BAG_EXPANSION_SLOTS);
+        }
+        if rng.gen_bool(MAGNET_RELIC_DROP_CHANCE) {
+            spawn_magnet_relic(&mut commands, ev.position, MAGNET_RELIC_BONUS);
+        }
+        if rng.gen_bool(THORNS_RELIC_DROP_CHANCE) {
+            spawn_thorns_relic(&mut commands, ev.position, THORNS_RELIC_FRACTION);
+        }
+        if rng.gen_bool(POWER_UP_DROP_CHANCE) {
+            let kind = BuffKind::ALL[rng.gen_range(0..BuffKind::ALL.len())];
+            spawn_power_up(&mut commands, ev.position, kind, POWER_UP_DURATION);
+        }
+    }
+}
+
+pub fn spawn_health_orb(commands: &mut Commands, pos: Vec2, amount: f32) {
+    commands.spawn((
+        Sprite::from_color(Color::srgb(0.9, 0.2, 0.3), Vec2::splat(HEALTH_ORB_SIZE)),
+        Transform::from_translation(pos.extend(layers::PICKUP)),
+        Pickup { base: pos, age: rand::thread_rng().gen_range(0.0..std::f32::consts::TAU) },
+        HealthOrb { amount },
+    ));
+}
+
+pub fn spawn_weapon_drop(commands: &mut Commands, asset_server: &AssetServer, pos: Vec2, item: ItemInstance) {
+    let mut sprite = Sprite::from_image(asset_server.load(item.id.icon_path()));
+    sprite.custom_size = Some(Vec2::splat(WEAPON_DROP_SIZE));
+    commands.spawn((
+        sprite,
+        Transform::from_translation(pos.extend(layers::PICKUP)),
+        Pickup { base: pos, age: rand::thread_rng().gen_range(0.0..std::f32::consts::TAU) },
+        WeaponDrop { item },
+    ));
+}
+
+pub fn spawn_bag_expansion(commands: &mut Commands, pos: Vec2, extra: usize) {
+    commands.spawn((
+        Sprite::from_color(Color::srgb(0.85, 0.7, 0.3), Vec2::splat(BAG_EXPANSION_SIZE)),
+        Transform::from_translation(pos.extend(layers::PICKUP)),
+        Pickup { base: pos, age: rand::thread_rng().gen_range(0.0..std::f32::consts::TAU) },
+        BagExpansion { extra },
+    ));
+}
+
+pub fn spawn_magnet_relic(commands: &mut Commands, pos: Vec2, bonus: f32) {
+    commands.spawn((
+        Sprite::from_color(Color::srgb(0.3, 0.8, 0.95), Vec2::splat(MAGNET_RELIC_SIZE)),
+        Transform::from_translation(pos.extend(layers::PICKUP)),
+        Pickup { base: pos, age: rand::thread_rng().gen_range(0.0..std::f32::consts::TAU) },
+        MagnetRelic { bonus },
+    ));
+}
+
+pub fn spawn_thorns_relic(commands: &mut Commands, pos: Vec2, fraction: f32) {
+    commands.spawn((
+        Sprite::from_color(Color::srgb(0.35, 0.75, 0.3), Vec2::splat(THORNS_RELIC_SIZE)),
+        Transform::from_translation(pos.extend(layers::PICKUP)),
+        Pickup { base: pos, age: rand::thread_rng().gen_range(0.0..std::f32::consts::TAU) },
+        ThornsRelic { fraction },
+    ));
+}
+
+pub fn spawn_power_up(commands: &mut Commands, pos: Vec2, kind: BuffKind, duration: f32) {
+    commands.spawn((
+        Sprite::from_color(kind.color(), Vec2::splat(POWER_UP_SIZE)),
+        Transform::from_translation(pos.extend(layers::PICKUP)),
+        Pickup { base: pos, age: rand::thread_rng().gen_range(0.0..std::f32::consts::TAU) },
+        PowerUp { kind, duration },
+    ));
+}
+
+/// 拾取半径内的掉落物被吸向玩家，越近越快
+fn magnet_pickups(
+    time: Res<Time>,
+    player_q: Query<(&Transform, Option<&PickupRadius>), With<Player>>,
+    mut pickups_q: Query<&mut Pickup>,
+) {
+    let Ok((player_tf, radius)) = player_q.single() else { return; };
+    let ppos = player_tf.translation.truncate();
+    let radius = radius.copied().unwrap_or_default().0;
+
+    for mut pickup in &mut pickups_q {
+        let to_player = ppos - pickup.base;
+        let dist = to_player.length();
+        if dist <= radius && dist > f32::EPSILON {
+            let closeness = 1.0 - dist / radius;
+            let step = MAGNET_SPEED * (1.0 + MAGNET_ACCELERATION * closeness) * time.delta_secs();
+            pickup.base += to_player / dist * step.min(dist);
+        }
+    }
+}
+
+fn bob_pickups(time: Res<Time>, mut q: Query<(&mut Pickup, &mut Transform)>) {
+    for (mut pickup, mut tf) in &mut q {
+        pickup.age += time.delta_secs();
+        let bob = (pickup.age * BOB_SPEED).sin() * BOB_HEIGHT;
+        tf.translation.x = pickup.base.x;
+        tf.translation.y = pickup.base.y + bob;
+    }
+}
+
+fn collect_health_orbs(
+    mut commands: Commands,
+    mut player_q: Query<(&Transform, &mut Health), With<Player>>,
+    orbs_q: Query<(Entity, &Pickup, &HealthOrb)>,
+) {
+    let Ok((player_tf, mut health)) = player_q.single_mut() else { return; };
+    let ppos = player_tf.translation.truncate();
+
+    for (entity, pickup, orb) in &orbs_q {
+        if pickup.base.distance(ppos) <= PICKUP_RADIUS {
+            health.current = (health.current + orb.amount).min(health.max);
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn collect_weapon_drops(
+    mut commands: Commands,
+    mut loot_tx: MessageWriter<LootItemMsg>,
+    player_q: Query<(&Transform, &Inventory), With<Player>>,
+    drops_q: Query<(Entity, &Pickup, &WeaponDrop)>,
+) {
+    let Ok((player_tf, inv)) = player_q.single() else { return; };
+    let ppos = player_tf.translation.truncate();
+    // 本帧能放下几件就捡几件
+    let mut free = inv.free_slots();
+
+    for (entity, pickup, drop) in &drops_q {
+        if free == 0 {
+            break;
+        }
+        if pickup.base.distance(ppos) <= PICKUP_RADIUS {
+            loot_tx.write(LootItemMsg { item: drop.item.clone() });
+            commands.entity(entity).despawn();
+            free -= 1;
+        }
+    }
+}
+
+fn collect_bag_expansions(
+    mut commands: Commands,
+    mut expand_tx: MessageWriter<ExpandInventoryMsg>,
+    mut notifications: ResMut<Notifications>,
+    settings: Res<GameSettings>,
+    player_q: Query<&Transform, With<Player>>,
+    bags_q: Query<(Entity, &Pickup, &BagExpansion)>,
+) {
+    let Ok(player_tf) = player_q.single() else { return; };
+    let ppos = player_tf.translation.truncate();
+
+    for (entity, pickup, bag) in &bags_q {
+        if pickup.base.distance(ppos) <= PICKUP_RADIUS {
+            expand_tx.write(ExpandInventoryMsg { extra: bag.extra });
+            notifications.notify(format!("{}{}", tr(settings.language, "notify.inventory_expanded"), bag.extra));
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn collect_magnet_relics(
+    mut commands: Commands,
+    mut notifications: ResMut<Notifications>,
+    settings: Res<GameSettings>,
+    mut player_q: Query<(Entity, &Transform, Option<&mut PickupRadius>), With<Player>>,
+    relics_q: Query<(Entity, &Pickup, &MagnetRelic)>,
+) {
+    let Ok((player, player_tf, radius)) = player_q.single_mut() else { return; };
+    let ppos = player_tf.translation.truncate();
+    let mut current = radius.as_deref().copied().unwrap_or_default();
+    let mut collected = false;
+
+    for (entity, pickup, relic) in &relics_q {
+        if pickup.base.distance(ppos) <= PICKUP_RADIUS {
+            current.0 = (current.0 + relic.bonus).min(MAX_PICKUP_RADIUS);
+            notifications.notify(format!("{}{:.0}", tr(settings.language, "notify.pickup_radius"), current.0));
+            commands.entity(entity).despawn();
+            collected = true;
+        }
+    }
+
+    if !collected {
+        return;
+    }
+    match radius {
+        Some(mut r) => *r = current,
+        None => {
+            commands.entity(player).insert(current);
+        }
+    }
+}
+
+fn collect_thorns_relics(
+    mut commands: Commands,
+    mut notifications: ResMut<Notifications>,
+    settings: Res<GameSettings>,
+    mut player_q: Query<(Entity, &Transform, Option<&mut Thorns>), With<Player>>,
+    relics_q: Query<(Entity, &Pickup, &ThornsRelic)>,
+) {
+    let Ok((player, player_tf, thorns)) = player_q.single_mut() else { return; };
+    let ppos = player_tf.translation.truncate();
+    let mut current = thorns.as_deref().map_or(0.0, |t| t.0);
+    let mut collected = false;
+
+    for (entity, pickup, relic) in &relics_q {
+        if pickup.base.distance(ppos) <= PICKUP_RADIUS {
+            current = (current + relic.fraction).min(MAX_THORNS_FRACTION);
+            notifications.notify(format!("{}{:.0}%", tr(settings.language, "notify.thorns"), current * 100.0));
+            commands.entity(entity).despawn();
+            collected = true;
+        }
+    }
+
+    if !collected {
+        return;
+    }
+    match thorns {
+        Some(mut t) => t.0 = current,
+        None => {
+            commands.entity(player).insert(Thorns(current));
+        }
+    }
+}
+
+fn collect_power_ups(
+    mut commands: Commands,
+    mut notifications: ResMut<Notifications>,
+    settings: Res<GameSettings>,
+    mut player_q: Query<(Entity, &Transform, Option<&mut Buffs>), With<Player>>,
+    power_ups_q: Query<(Entity, &Pickup, &PowerUp)>,
+) {
+    let Ok((player, player_tf, buffs)) = player_q.single_mut() else { return; };
+    let ppos = player_tf.translation.truncate();
+    let mut current = buffs.as_deref().cloned().unwrap_or_default();
+    let mut collected = false;
+
+    for (entity, pickup, power_up) in &power_ups_q {
+        if pickup.base.distance(ppos) <= PICKUP_RADIUS {
+            current.apply(power_up.kind, power_up.duration);
+            notifications.notify(format!(
+                "{}{}",
+                tr(settings.language, "notify.buff"),
+                power_up.kind.display_name(settings.language)
+            ));
+            commands.entity(entity).despawn();
+            collected = true;
+        }
+    }
+
+    if !collected {
+        return;
+    }
+    match buffs {
+        Some(mut b) => *b = current,
+        None => {
+            commands.entity(player).insert(current);
+        }
+    }
+}
+
+fn cleanup_pickups(mut commands: Commands, q: Query<Entity, With<Pickup>>) {
+    for e in &q {
+        commands.entity(e).despawn();
+    }
+}
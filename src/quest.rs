@@ -0,0 +1,284 @@
+// src/quest.rs
+//! 局内任务：同一时间只有一个任务（如 "击杀敌人 3/10"），显示在左上角威胁等级下方。
+//! 击杀、精英击杀、存活时间和威胁等级推动进度；完成后在玩家脚下掉落奖励，并切到下一个任务。
+//! 任务链走完一轮后从头再来，目标数按轮次翻倍。回到标题时重置。
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::combat_core::EnemyDied;
+use crate::difficulty_scaling::DifficultyScaling;
+use crate::equipment::{ItemDatabase, ItemId};
+use crate::i18n::tr;
+use crate::movement::Player;
+use crate::notifications::Notifications;
+use crate::pickup::{spawn_bag_expansion, spawn_health_orb, spawn_magnet_relic, spawn_weapon_drop};
+use crate::run_stats::RunStats;
+use crate::state::GameState;
+use crate::ui::types::GameSettings;
+
+/// 奖励掉落在玩家周围的距离，免得和玩家重叠时一落地就被捡走看不到
+const REWARD_DROP_OFFSET: f32 = 40.0;
+/// 各种奖励的数值：血球回血量、背包扩展格数、磁石增加的拾取半径
+const REWARD_HEAL: f32 = 40.0;
+const REWARD_BAG_SLOTS: usize = 5;
+const REWARD_MAGNET_BONUS: f32 = 30.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QuestKind {
+    KillEnemies,
+    KillElites,
+    /// 本局存活秒数
+    Survive,
+    ReachThreatLevel,
+}
+
+impl QuestKind {
+    fn label_key(self) -> &'static str {
+        match self {
+            QuestKind::KillEnemies => "quest.kill_enemies",
+            QuestKind::KillElites => "quest.kill_elites",
+            QuestKind::Survive => "quest.survive",
+            QuestKind::ReachThreatLevel => "quest.threat_level",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QuestReward {
+    HealthOrb,
+    Weapon,
+    BagExpansion,
+    MagnetRelic,
+}
+
+#[derive(Clone, Debug)]
+pub struct Quest {
+    pub kind: QuestKind,
+    pub progress: u32,
+    pub goal: u32,
+    pub reward: QuestReward,
+}
+
+impl Quest {
+    pub fn is_complete(&self) -> bool {
+        self.progress >= self.goal
+    }
+
+    pub fn description(&self, settings: &GameSettings) -> String {
+        format!("{} {}/{}", tr(settings.language, self.kind.label_key()), self.progress.min(self.goal), self.goal)
+    }
+}
+
+/// 任务链：(类型, 基础目标, 奖励)
+const QUEST_CHAIN: [(QuestKind, u32, QuestReward); 5] = [
+    (QuestKind::KillEnemies, 10, QuestReward::HealthOrb),
+    (QuestKind::Survive, 60, QuestReward::Weapon),
+    (QuestKind::KillElites, 2, QuestReward::MagnetRelic),
+    (QuestKind::ReachThreatLevel, 3, QuestReward::BagExpansion),
+    (QuestKind::KillEnemies, 50, QuestReward::Weapon),
+];
+
+/// 当前任务和它在任务链中的序号（跨轮次累计）
+#[derive(Resource, Debug)]
+pub struct QuestLog {
+    pub index: usize,
+    pub current: Quest,
+}
+
+impl QuestLog {
+    fn quest_at(index: usize) -> Quest {
+        let (kind, goal, reward) = QUEST_CHAIN[index % QUEST_CHAIN.len()];
+        let round = (index / QUEST_CHAIN.len()) as u32;
+        Quest { kind, progress: 0, goal: goal << round.min(8), reward }
+    }
+
+    fn advance(&mut self) {
+        self.index += 1;
+        self.current = Self::quest_at(self.index);
+    }
+}
+
+impl Default for QuestLog {
+    fn default() -> Self {
+        Self { index: 0, current: Self::quest_at(0) }
+    }
+}
+
+#[derive(Component)]
+struct QuestText;
+
+pub struct QuestPlugin;
+
+impl Plugin for QuestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<QuestLog>()
+            .add_systems(OnEnter(GameState::InGame), setup_quest_hud)
+            .add_systems(OnExit(GameState::InGame), cleanup_quest_hud)
+            .add_systems(
+                Update,
+                (track_quest_progress, complete_quest, update_quest_hud)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnEnter(GameState::MainMenu), reset_quests);
+    }
+}
+
+fn track_quest_progress(
+    mut log: ResMut<QuestLog>,
+    mut died_rx: MessageReader<EnemyDied>,
+    stats: Res<RunStats>,
+    scaling: Res<DifficultyScaling>,
+) {
+    let deaths: Vec<EnemyDied> = died_rx.read().copied().collect();
+    let progress = match log.current.kind {
+        QuestKind::KillEnemies => log.current.progress + deaths.len() as u32,
+        QuestKind::KillElites => log.current.progress + deaths.iter().filter(|d| d.elite).count() as u32,
+        QuestKind::Survive => stats.time as u32,
+        QuestKind::ReachThreatLevel => scaling.level + 1,
+    };
+    if progress != log.current.progress {
+        log.current.progress = progress;
+    }
+}
+
+fn complete_quest(
+    mut commands: Commands,
+    mut log: ResMut<QuestLog>,
+    mut notifications: ResMut<Notifications>,
+    settings: Res<GameSettings>,
+    asset_server: Res<AssetServer>,
+    db: Res<ItemDatabase>,
+    player_q: Query<&Transform, With<Player>>,
+) {
+    if !log.current.is_complete() {
+        return;
+    }
+    let Ok(player_tf) = player_q.single() else { return; };
+
+    let mut rng = rand::thread_rng();
+    let offset = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU)) * REWARD_DROP_OFFSET;
+    let pos = player_tf.translation.truncate() + offset;
+    match log.current.reward {
+        QuestReward::HealthOrb => spawn_health_orb(&mut commands, pos, REWARD_HEAL),
+        QuestReward::Weapon => {
+            let id = ItemId::WEAPONS[rng.gen_range(0..ItemId::WEAPONS.len())];
+            if let Some(item) = db.roll(id) {
+                spawn_weapon_drop(&mut commands, &asset_server, pos, item);
+            }
+        }
+        QuestReward::BagExpansion => spawn_bag_expansion(&mut commands, pos, REWARD_BAG_SLOTS),
+        QuestReward::MagnetRelic => spawn_magnet_relic(&mut commands, pos, REWARD_MAGNET_BONUS),
+    }
+
+    let lang = settings.language;
+    notifications.notify(format!("{}{}", tr(lang, "notify.quest_complete"), tr(lang, log.current.kind.label_key())));
+    log.advance();
+}
+
+fn reset_quests(mut log: ResMut<QuestLog>) {
+    *log = QuestLog::default();
+}
+
+/// 放在威胁等级下方
+fn setup_quest_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        QuestText,
+        Text::new(""),
+        TextFont {
+            font: asset_server.load("fonts/YuFanLixing.otf"),
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.85, 0.9, 1.0)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(16.0),
+            top: Val::Px(84.0),
+            ..default()
+        },
+    ));
+}
+
+fn update_quest_hud(log: Res<QuestLog>, settings: Res<GameSettings>, mut q: Query<&mut Text, With<QuestText>>) {
+    for mut t in &mut q {
+        let label = format!("{}: {}", tr(settings.language, "quest.title"), log.current.description(&settings));
+        if t.0 != label {
+            t.0 = label;
+        }
+    }
+}
+
+fn cleanup_quest_hud(mut commands: Commands, q: Query<Entity, With<QuestText>>) {
+    for e in &q {
+        commands.entity(e).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 只跑 track_quest_progress 的最小 App，当前任务换成任务链第 index 个
+    fn tracker_app(index: usize) -> App {
+        let mut app = App::new();
+        app.add_message::<EnemyDied>()
+            .init_resource::<RunStats>()
+            .init_resource::<DifficultyScaling>()
+            .insert_resource(QuestLog { index, current: QuestLog::quest_at(index) })
+            .add_systems(Update, track_quest_progress);
+        app
+    }
+
+    fn kill(app: &mut App, elite: bool) {
+        app.world_mut().write_message(EnemyDied { entity: Entity::PLACEHOLDER, position: Vec2::ZERO, elite });
+    }
+
+    #[test]
+    fn kill_quests_count_matching_deaths() {
+        let mut app = tracker_app(0);
+        kill(&mut app, false);
+        kill(&mut app, true);
+        app.update();
+        assert_eq!(app.world().resource::<QuestLog>().current.progress, 2);
+
+        let mut app = tracker_app(2);
+        assert_eq!(app.world().resource::<QuestLog>().current.kind, QuestKind::KillElites);
+        kill(&mut app, false);
+        kill(&mut app, true);
+        app.update();
+        assert_eq!(app.world().resource::<QuestLog>().current.progress, 1, "只算精英");
+    }
+
+    #[test]
+    fn survive_and_threat_quests_follow_run_state() {
+        let mut app = tracker_app(1);
+        app.world_mut().resource_mut::<RunStats>().time = 42.7;
+        app.update();
+        assert_eq!(app.world().resource::<QuestLog>().current.progress, 42);
+
+        let mut app = tracker_app(3);
+        app.world_mut().resource_mut::<DifficultyScaling>().level = 2;
+        app.update();
+        assert_eq!(app.world().resource::<QuestLog>().current.progress, 3, "威胁等级从 1 开始显示");
+    }
+
+    #[test]
+    fn goals_double_each_round_of_the_chain() {
+        let mut log = QuestLog::default();
+        let first_goal = log.current.goal;
+        log.current.progress = 7;
+        for _ in 0..QUEST_CHAIN.len() {
+            log.advance();
+        }
+        assert_eq!(log.current.kind, QUEST_CHAIN[0].0);
+        assert_eq!(log.current.goal, first_goal * 2);
+        assert_eq!(log.current.progress, 0);
+
+        for _ in 0..QUEST_CHAIN.len() {
+            log.advance();
+        }
+        assert_eq!(log.current.goal, first_goal * 4);
+    }
+}
@@ -0,0 +1,143 @@
+// src/run_stats.rs
+//! 单局统计与排行榜：局内累计存活时间、击杀数和刷怪波次，失败时算分写进数据目录下的 scores.json，
+//! 只保留前 `HIGH_SCORE_LIMIT` 名。失败界面读取 `RunStats`、`HighScores` 和 `LastRunRank` 显示。
+
+use std::fs;
+
+use bevy::prelude::*;
+use chrono::Local as ChronoLocal;
+use serde::{Deserialize, Serialize};
+
+use crate::combat_core::EnemyDied;
+use crate::save::scores_file_path;
+use crate::state::GameState;
+
+/// 排行榜保留的条数
+pub const HIGH_SCORE_LIMIT: usize = 5;
+/// 每次击杀的得分；存活时间每秒 1 分
+const SCORE_PER_KILL: u32 = 10;
+
+/// 本局统计，回到标题或失败后重新开局时清零
+#[derive(Resource, Default, Clone, Debug)]
+pub struct RunStats {
+    /// 存活时间（秒，暂停不计）
+    pub time: f32,
+    pub kills: u32,
+    /// 刷怪批次数
+    pub waves: u32,
+}
+
+impl RunStats {
+    pub fn score(&self) -> u32 {
+        self.kills * SCORE_PER_KILL + self.time as u32
+    }
+}
+
+/// 排行榜中的一条记录
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub score: u32,
+    pub time: f32,
+    pub kills: u32,
+    pub waves: u32,
+    /// 记录时间，例如 "2025-12-06 21:30"
+    pub date: String,
+}
+
+/// 排行榜，按得分从高到低
+#[derive(Resource, Default, Clone, Debug, Serialize, Deserialize)]
+pub struct HighScores {
+    pub entries: Vec<ScoreEntry>,
+}
+
+impl HighScores {
+    /// 插入一条记录并保持有序、截断到上限；返回它的名次（从 0 开始），没进榜返回 None
+    fn insert(&mut self, entry: ScoreEntry) -> Option<usize> {
+        let rank = self.entries.iter().position(|e| entry.score > e.score).unwrap_or(self.entries.len());
+        if rank >= HIGH_SCORE_LIMIT {
+            return None;
+        }
+        self.entries.insert(rank, entry);
+        self.entries.truncate(HIGH_SCORE_LIMIT);
+        Some(rank)
+    }
+}
+
+/// 刚结束的一局在排行榜上的名次
+#[derive(Resource, Default)]
+pub struct LastRunRank(pub Option<usize>);
+
+pub struct RunStatsPlugin;
+
+impl Plugin for RunStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>()
+            .init_resource::<HighScores>()
+            .init_resource::<LastRunRank>()
+            .add_systems(Startup, load_high_scores)
+            .add_systems(
+                Update,
+                (tick_run_time, count_kills).run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnEnter(GameState::GameOver), record_high_score)
+            // 回标题、或在失败界面读档重开，都算新的一局
+            .add_systems(OnEnter(GameState::MainMenu), reset_run_stats)
+            .add_systems(OnExit(GameState::GameOver), reset_run_stats);
+    }
+}
+
+fn tick_run_time(time: Res<Time>, mut stats: ResMut<RunStats>) {
+    stats.time += time.delta_secs();
+}
+
+fn count_kills(mut stats: ResMut<RunStats>, mut died_rx: MessageReader<EnemyDied>) {
+    let kills = died_rx.read().count() as u32;
+    if kills > 0 {
+        stats.kills += kills;
+    }
+}
+
+fn reset_run_stats(mut stats: ResMut<RunStats>) {
+    *stats = RunStats::default();
+}
+
+fn load_high_scores(mut scores: ResMut<HighScores>) {
+    let path = scores_file_path();
+    let Ok(text) = fs::read_to_string(&path) else { return; };
+
+    match serde_json::from_str::<HighScores>(&text) {
+        Ok(loaded) => *scores = loaded,
+        Err(e) => warn!("读取排行榜失败 {:?}: {}", path, e),
+    }
+}
+
+/// 失败时把本局成绩写进排行榜，进榜才写文件
+pub fn record_high_score(stats: Res<RunStats>, mut scores: ResMut<HighScores>, mut rank: ResMut<LastRunRank>) {
+    let entry = ScoreEntry {
+        score: stats.score(),
+        time: stats.time,
+        kills: stats.kills,
+        waves: stats.waves,
+        date: ChronoLocal::now().format("%Y-%m-%d %H:%M").to_string(),
+    };
+    rank.0 = scores.insert(entry);
+    if rank.0.is_none() {
+        return;
+    }
+
+    let path = scores_file_path();
+    match serde_json::to_string_pretty(&*scores) {
+        Ok(text) => {
+            if let Err(e) = fs::write(&path, text) {
+                warn!("写入排行榜失败 {:?}: {}", path, e);
+            }
+        }
+        Err(e) => warn!("序列化排行榜失败: {}", e),
+    }
+}
+
+/// 把秒数显示成 分:秒
+pub fn format_run_time(secs: f32) -> String {
+    let total = secs.max(0.0) as u32;
+    format!("{:02}:{:02}", total / 60, total % 60)
+}
@@ -1,18 +1,28 @@
 use bevy::prelude::*;
-use chrono::{Datelike, Local as ChronoLocal};
+use chrono::{DateTime, Datelike, Local as ChronoLocal, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
-use crate::health::Health;
-use crate::movement::Player;
-use crate::state::GameState;
+use crate::checkpoint::{Checkpoint, Downed};
+use crate::combat::{ensure_attack_state, AttackState};
+use crate::equipment::{
+    ensure_player_inventory_and_equipment, EquipmentSet, EquippedItems, ItemDatabase, ItemId, ItemInstance,
+    SecondaryEquipmentSet,
+};
+use crate::health::{Armor, Health};
+use crate::i18n::{tr, Language};
+use crate::inventory::{Inventory, ItemStack};
+use crate::meta_progress::MetaBonuses;
+use crate::movement::{Player, PlayerDash};
+use crate::notifications::Notifications;
+use crate::state::{window_has_focus, GameState};
+use crate::ui::types::GameSettings;
 
 /// 手动保存事件：file_name = Some("xxx.json") => 覆盖该文件，None => 新建
 #[derive(Debug, Clone, Message)]
 pub struct ManualSaveEvent {
     pub file_name: Option<String>,
-    pub slot_index: Option<u32>,
 }
 
 /// 选择加载某一个存档槽位（UI “激活”后发送）
@@ -31,10 +41,27 @@ pub struct SaveSlotMeta {
     pub file_name: String,
     /// 是否自动存档（仅用于 UI 显示）
     pub is_auto: bool,
-    /// 可选：创建时间或显示信息
+    /// 文件最后修改时间（本地时间，`%Y-%m-%d %H:%M:%S`），列表按它从新到旧排序
     pub created_at: String,
 }
 
+/// 存档所在目录。默认是 <数据目录>/saves；测试换成临时目录，不碰玩家的真实存档
+#[derive(Resource, Clone, Debug)]
+pub struct SaveDir(pub PathBuf);
+
+impl Default for SaveDir {
+    fn default() -> Self {
+        Self(saves_dir())
+    }
+}
+
+impl SaveDir {
+    fn file(&self, file_name: &str) -> PathBuf {
+        let _ = fs::create_dir_all(&self.0);
+        self.0.join(file_name)
+    }
+}
+
 /// 所有存档槽列表（从磁盘扫描出来）
 #[derive(Resource, Default, Debug)]
 pub struct SaveSlots {
@@ -42,6 +69,11 @@ pub struct SaveSlots {
 }
 
 /// 当前使用中的存档文件名（自动保存 / 手动保存默认写到这里）
+///
+/// 约定：
+/// - 只有读档、手动保存、第一次自动保存会设置它；新开一局和游戏结束都会清空
+/// - 暂停后“继续游戏”不会读档；要回到存档里的局面只能走暂停菜单的“重新读取存档”，
+///   它读的就是这里指向的槽（见 [`reload_target`]）
 #[derive(Resource, Default, Debug)]
 pub struct CurrentSlot {
     /// 文件名，例如 "25.12.06.1.json"
@@ -60,9 +92,34 @@ pub struct SaveData {
     pub player_x: f32,
     pub player_y: f32,
     pub hp_current: f32,
+    /// 不含局外强化加成的最大生命，读档时按当局的 `MetaBonuses` 加回去
     pub hp_max: f32,
+    /// 背包格子（含每件武器的品质和随机属性）；旧存档没有这些字段，读档时保留当前背包和装备
+    #[serde(default)]
+    pub inventory: Option<Vec<Option<ItemStack>>>,
+    #[serde(default)]
+    pub equipped_primary: Option<ItemInstance>,
+    #[serde(default)]
+    pub equipped_secondary: Option<ItemInstance>,
+    #[serde(default)]
+    pub equipped_armor: Option<ItemId>,
 }
 
+/// 写存档需要的玩家数据
+type SaveSourceQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        &'static Health,
+        Option<&'static Inventory>,
+        Option<&'static EquippedItems>,
+        Option<&'static MetaBonuses>,
+    ),
+    // 倒地等待复活时不存档，否则会把 HP≤0 和倒地位置写进存档
+    (With<Player>, Without<Downed>),
+>;
+
 /// 自动存档间隔（秒）
 const AUTOSAVE_INTERVAL_SECS: f32 = 60.0;
 
@@ -71,7 +128,8 @@ pub struct SavePlugin;
 
 impl Plugin for SavePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<SaveSlots>()
+        app.init_resource::<SaveDir>()
+            .init_resource::<SaveSlots>()
             .init_resource::<CurrentSlot>()
             .init_resource::<PendingLoad>()
             .add_message::<ManualSaveEvent>()
@@ -81,9 +139,13 @@ impl Plugin for SavePlugin {
         app.add_systems(Update, handle_load_slot_events);
 
         //  InGame 或 Paused 都允许“应用激活存档”
+        // 排在补齐背包/装备/攻击状态之后：刚生成的玩家同一帧就能读档，且不会被初始背包覆盖
         app.add_systems(
             Update,
-            apply_pending_load.run_if(in_state(GameState::InGame).or(in_state(GameState::Paused))),
+            apply_pending_load
+                .after(ensure_player_inventory_and_equipment)
+                .after(ensure_attack_state)
+                .run_if(in_state(GameState::InGame).or(in_state(GameState::Paused))),
         );
 
         //  InGame 或 Paused 都允许“手动保存”
@@ -96,44 +158,133 @@ impl Plugin for SavePlugin {
         //  只在 InGame 自动保存（每分钟一次）
         app.add_systems(
             Update,
-            auto_save_every_minute.run_if(in_state(GameState::InGame)),
+            auto_save_every_minute.run_if(in_state(GameState::InGame).and(window_has_focus)),
         );
     }
 }
 
-/// 存档目录：./saves
+/// 设置了这个环境变量时，存档、设置和排行榜都放在它指向的目录（便携版用）
+const DATA_DIR_ENV: &str = "OPLUS_DATA_DIR";
+/// 数据目录下的游戏文件夹名
+const DATA_DIR_NAME: &str = "Oplus";
+
+/// 玩家数据目录：环境变量优先，否则是系统的用户数据目录
+/// （Windows 为 %APPDATA%/Oplus，Linux 为 ~/.local/share/Oplus，macOS 为 ~/Library/Application Support/Oplus），
+/// 都拿不到时退回当前目录
+pub fn data_dir() -> PathBuf {
+    let dir = std::env::var_os(DATA_DIR_ENV)
+        .map(PathBuf::from)
+        .or_else(|| dirs::data_dir().map(|d| d.join(DATA_DIR_NAME)))
+        .unwrap_or_else(legacy_data_dir);
+    let _ = fs::create_dir_all(&dir);
+    dir
+}
+
+/// 旧版本把数据写在启动时的当前目录
+fn legacy_data_dir() -> PathBuf {
+    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+}
+
+/// 存档目录：<数据目录>/saves
 fn saves_dir() -> PathBuf {
-    let mut dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    dir.push("saves");
+    let dir = data_dir().join("saves");
     let _ = fs::create_dir_all(&dir);
     dir
 }
 
-fn slot_file_path(file_name: &str) -> PathBuf {
-    let mut path = saves_dir();
-    path.push(file_name);
-    path
+/// 设置文件：<数据目录>/settings.json（与 saves 同级）
+pub fn settings_file_path() -> PathBuf {
+    data_dir().join("settings.json")
+}
+
+/// 局外成长进度：<数据目录>/meta.json
+pub fn meta_file_path() -> PathBuf {
+    data_dir().join("meta.json")
+}
+
+/// 排行榜文件：<数据目录>/scores.json（与 saves 同级）
+pub fn scores_file_path() -> PathBuf {
+    data_dir().join("scores.json")
+}
+
+/// 首次使用新数据目录时，把当前目录下旧版本留下的 saves/、settings.json、scores.json 复制过去。
+/// 只复制新目录里还没有的文件，旧文件原样保留；要在读取设置之前调用
+pub fn migrate_legacy_data() {
+    let legacy = legacy_data_dir();
+    let target = data_dir();
+    if legacy.canonicalize().ok() == target.canonicalize().ok() {
+        return;
+    }
+    // 新目录里已经有存档目录，说明迁移过或者是新玩家正常运行过
+    if target.join("saves").exists() {
+        return;
+    }
+
+    let mut files: Vec<(PathBuf, PathBuf)> = ["settings.json", "scores.json"]
+        .into_iter()
+        .map(|name| (legacy.join(name), target.join(name)))
+        .collect();
+    if let Ok(read_dir) = fs::read_dir(legacy.join("saves")) {
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            if path.is_file() {
+                files.push((path, target.join("saves").join(entry.file_name())));
+            }
+        }
+    }
+
+    let _ = fs::create_dir_all(target.join("saves"));
+    for (from, to) in files {
+        if !from.is_file() || to.exists() {
+            continue;
+        }
+        match fs::copy(&from, &to) {
+            Ok(_) => info!("已迁移旧数据 {:?} -> {:?}", from, to),
+            Err(e) => warn!("迁移旧数据失败 {:?}: {}", from, e),
+        }
+    }
+}
+
+/// 某一天第 seq 个存档的显示名，格式为 `yy.MM.dd.n`，比如 `25.12.06.1`
+fn slot_display_name(date: NaiveDate, seq: u32) -> String {
+    format!("{:02}.{:02}.{:02}.{}", date.year() % 100, date.month(), date.day(), seq)
 }
 
-/// 生成格式为 `yy.MM.dd.n` 的显示名，比如 `25.12.06.1`
-pub fn generate_slot_display_name(index: u32) -> String {
-    let now = ChronoLocal::now();
-    let yy = now.year() % 100;
-    let mm = now.month();
-    let dd = now.day();
-    format!("{:02}.{:02}.{:02}.{}", yy, mm, dd, index)
+/// 新建存档的序号：当天已有存档的最大序号 + 1；格式不对的名字和其他日期的存档都不算
+#[allow(clippy::collapsible_if)]
+fn next_slot_sequence<'a>(names: impl IntoIterator<Item = &'a str>, date: NaiveDate) -> u32 {
+    let (y, m, d) = ((date.year() % 100) as u32, date.month(), date.day());
+
+    let mut max_seq: u32 = 0;
+    for name in names {
+        let parts: Vec<_> = name.split('.').collect();
+        if parts.len() != 4 {
+            continue;
+        }
+        let (yy, mm, dd, seq) = (
+            parts[0].parse::<u32>().ok(),
+            parts[1].parse::<u32>().ok(),
+            parts[2].parse::<u32>().ok(),
+            parts[3].parse::<u32>().ok(),
+        );
+        if yy == Some(y) && mm == Some(m) && dd == Some(d) {
+            if let Some(s) = seq {
+                max_seq = max_seq.max(s);
+            }
+        }
+    }
+    max_seq + 1
 }
 
-fn load_save_slots_from_disk(mut slots_res: ResMut<SaveSlots>) {
-    refresh_save_slots_from_disk(&mut slots_res);
+pub fn load_save_slots_from_disk(mut slots_res: ResMut<SaveSlots>, save_dir: Res<SaveDir>) {
+    refresh_save_slots_from_disk(&mut slots_res, &save_dir);
 }
 
-/// Scan ./saves and fill SaveSlots (public for UI to refresh)
-pub fn refresh_save_slots_from_disk(slots_res: &mut SaveSlots) {
-    let dir = saves_dir();
+/// Scan the save dir and fill SaveSlots (public for UI to refresh)
+pub fn refresh_save_slots_from_disk(slots_res: &mut SaveSlots, save_dir: &SaveDir) {
     let mut slots = Vec::new();
 
-    if let Ok(read_dir) = fs::read_dir(&dir) {
+    if let Ok(read_dir) = fs::read_dir(&save_dir.0) {
         for entry in read_dir.flatten() {
             let path = entry.path();
             if !path.is_file() {
@@ -149,21 +300,52 @@ pub fn refresh_save_slots_from_disk(slots_res: &mut SaveSlots) {
 
             let display_name = file_name.trim_end_matches(".json").to_string();
             let is_auto = display_name.starts_with("auto_") || display_name == "autosave";
+            let created_at = fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .map(|t| format_timestamp(DateTime::<ChronoLocal>::from(t)))
+                .unwrap_or_default();
 
             slots.push(SaveSlotMeta {
                 display_name,
                 file_name,
                 is_auto,
-                created_at: String::new(),
+                created_at,
             });
         }
     }
 
-    // 按名字排序（日期.序号 这种格式基本能排出时间顺序）
-    slots.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    sort_slots_newest_first(&mut slots);
     slots_res.slots = slots;
 }
 
+fn format_timestamp(t: DateTime<ChronoLocal>) -> String {
+    t.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// 按修改时间从新到旧排序；时间相同（或读不到时间）时按名字倒序
+fn sort_slots_newest_first(slots: &mut [SaveSlotMeta]) {
+    slots.sort_by(|a, b| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then_with(|| b.display_name.cmp(&a.display_name))
+    });
+}
+
+/// 刚写入的存档：更新（或新增）它的时间戳并重新排序
+fn touch_slot(slots: &mut SaveSlots, file_name: &str, is_auto: bool) {
+    let now = format_timestamp(ChronoLocal::now());
+    match slots.slots.iter_mut().find(|s| s.file_name == file_name) {
+        Some(slot) => slot.created_at = now,
+        None => slots.slots.push(SaveSlotMeta {
+            display_name: file_name.trim_end_matches(".json").to_string(),
+            file_name: file_name.to_string(),
+            is_auto,
+            created_at: now,
+        }),
+    }
+    sort_slots_newest_first(&mut slots.slots);
+}
+
 /// UI 点击“激活存档”后：
 /// - 只设置 PendingLoad（真正读档在 apply_pending_load 里发生）
 /// - 并把 CurrentSlot 指向该文件（之后自动存档写到这个槽）
@@ -182,18 +364,45 @@ fn handle_load_slot_events(
     }
 }
 
+/// 暂停菜单“重新读取存档”要读的槽：优先当前槽；当前槽为空或文件已经不在列表里时，退回最近修改的存档。
+/// 都没有就返回 None，不读档
+pub fn reload_target(current: &CurrentSlot, slots: &SaveSlots) -> Option<String> {
+    current
+        .file_name
+        .as_ref()
+        .filter(|name| slots.slots.iter().any(|s| &s.file_name == *name))
+        .cloned()
+        .or_else(|| slots.slots.first().map(|s| s.file_name.clone()))
+}
+
 /// 真正读档（只会在 PendingLoad 有值时触发）
-/// 注意：如果玩家实体还没生成，就先不 take()，避免丢掉请求。
-fn apply_pending_load(
+/// 注意：如果玩家实体还没生成、或者背包/装备还没由 `ensure_player_inventory_and_equipment` 补齐，
+/// 就先不 take()，避免丢掉请求，也避免存档里的背包被后补的初始背包覆盖。
+#[allow(clippy::type_complexity)]
+pub(crate) fn apply_pending_load(
+    mut commands: Commands,
     mut pending: ResMut<PendingLoad>,
-    mut player_q: Query<(&mut Transform, &mut Health), With<Player>>,
+    db: Res<ItemDatabase>,
+    save_dir: Res<SaveDir>,
+    mut player_q: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Health,
+            &mut Inventory,
+            &mut EquippedItems,
+            &mut EquipmentSet,
+            Option<&MetaBonuses>,
+        ),
+        With<Player>,
+    >,
 ) {
     if pending.file_name.is_none() {
         return;
     }
 
-    // 玩家还不存在：等下一帧再试（不要清 pending）
-    let Ok((mut tf, mut hp)) = player_q.single_mut() else {
+    // 玩家还没准备好：等下一帧再试（不要清 pending）
+    let Ok((player, mut tf, mut hp, mut inv, mut equipped, mut equip_set, bonuses)) = player_q.single_mut() else {
         return;
     };
 
@@ -201,7 +410,7 @@ fn apply_pending_load(
         return;
     };
 
-    let path = slot_file_path(&file_name);
+    let path = save_dir.file(&file_name);
     let Ok(bytes) = fs::read(path) else {
         // 文件不存在就当作加载失败（不回退、不强制改位置）
         return;
@@ -213,8 +422,39 @@ fn apply_pending_load(
 
     tf.translation.x = data.player_x;
     tf.translation.y = data.player_y;
-    hp.max = data.hp_max.max(1.0);
+    hp.max = data.hp_max.max(1.0) + bonuses.map_or(0.0, |b| b.max_health_bonus);
     hp.current = data.hp_current.clamp(0.0, hp.max);
+
+    // 读档前的攻击冷却、冲刺状态不带进存档里的局面
+    commands.entity(player).insert((AttackState::default(), PlayerDash::default()));
+
+    if let Some(slots) = data.inventory {
+        inv.slots = slots;
+    }
+    if let Some(primary) = data.equipped_primary {
+        *equip_set = EquipmentSet::from_weapon(&primary.rolled);
+        match &data.equipped_secondary {
+            Some(item) => {
+                commands
+                    .entity(player)
+                    .insert(SecondaryEquipmentSet(EquipmentSet::from_weapon(&item.rolled)));
+            }
+            None => {
+                commands.entity(player).remove::<SecondaryEquipmentSet>();
+            }
+        }
+        match data.equipped_armor.and_then(|id| db.armor(id)) {
+            Some(def) => {
+                commands.entity(player).insert(Armor(def.damage_reduction));
+            }
+            None => {
+                commands.entity(player).remove::<Armor>();
+            }
+        }
+        equipped.primary = primary;
+        equipped.secondary = data.equipped_secondary;
+        equipped.armor = data.equipped_armor;
+    }
 }
 
 /// 手动保存：
@@ -222,106 +462,107 @@ fn apply_pending_load(
 /// - file_name=None => 新建当天序号存档
 fn handle_manual_save_events(
     mut ev_save: MessageReader<ManualSaveEvent>,
-    player_q: Query<(&Transform, &Health), With<Player>>,
+    player_q: SaveSourceQuery,
     mut slots: ResMut<SaveSlots>,
     mut current: ResMut<CurrentSlot>,
+    mut notifications: ResMut<Notifications>,
+    settings: Res<GameSettings>,
+    save_dir: Res<SaveDir>,
 ) {
     if ev_save.is_empty() {
         return;
     }
 
-    let Ok((tf, hp)) = player_q.single() else {
+    let Ok((tf, hp, inv, equipped, bonuses)) = player_q.single() else {
         return; // 主菜单没有玩家，直接忽略
     };
 
     for ev in ev_save.read() {
         if let Some(file_name) = &ev.file_name {
-            write_save_to_file(file_name, tf, hp);
+            notify_save_result(
+                &mut notifications,
+                settings.language,
+                file_name,
+                write_save_to_file(&save_dir, file_name, tf, hp, inv, equipped, bonuses),
+            );
 
-            if !slots.slots.iter().any(|s| &s.file_name == file_name) {
-                slots.slots.push(SaveSlotMeta {
-                    display_name: file_name.trim_end_matches(".json").to_string(),
-                    file_name: file_name.clone(),
-                    is_auto: false,
-                    created_at: ChronoLocal::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-                });
-                slots
-                    .slots
-                    .sort_by(|a, b| a.display_name.cmp(&b.display_name));
-            }
+            touch_slot(&mut slots, file_name, false);
 
             current.file_name = Some(file_name.clone());
         } else {
-            // 新建：找出当天最大序号 + 1
-            let now = ChronoLocal::now();
-            let y = (now.year() % 100) as u32;
-            let m = now.month();
-            let d = now.day();
-
-            let mut max_seq: u32 = 0;
-            for slot in &slots.slots {
-                let parts: Vec<_> = slot.display_name.split('.').collect();
-                if parts.len() != 4 {
-                    continue;
-                }
-                let (yy, mm, dd, seq) = (
-                    parts[0].parse::<u32>().ok(),
-                    parts[1].parse::<u32>().ok(),
-                    parts[2].parse::<u32>().ok(),
-                    parts[3].parse::<u32>().ok(),
-                );
-                if yy == Some(y) && mm == Some(m) && dd == Some(d) {
-                    if let Some(s) = seq {
-                        max_seq = max_seq.max(s);
-                    }
-                }
-            }
-
-            let new_seq = max_seq + 1;
-            let display_name = format!("{:02}.{:02}.{:02}.{}", y, m, d, new_seq);
+            // 新建：当天最大序号 + 1
+            let today = ChronoLocal::now().date_naive();
+            let new_seq = next_slot_sequence(slots.slots.iter().map(|s| s.display_name.as_str()), today);
+            let display_name = slot_display_name(today, new_seq);
             let file_name = format!("{display_name}.json");
 
-            write_save_to_file(&file_name, tf, hp);
+            notify_save_result(
+                &mut notifications,
+                settings.language,
+                &file_name,
+                write_save_to_file(&save_dir, &file_name, tf, hp, inv, equipped, bonuses),
+            );
 
-            slots.slots.push(SaveSlotMeta {
-                display_name,
-                file_name: file_name.clone(),
-                is_auto: false,
-                created_at: ChronoLocal::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-            });
-            slots
-                .slots
-                .sort_by(|a, b| a.display_name.cmp(&b.display_name));
+            touch_slot(&mut slots, &file_name, false);
 
             current.file_name = Some(file_name);
         }
     }
 }
 
-fn write_save_to_file(file_name: &str, tf: &Transform, hp: &Health) {
+/// 写入存档文件，返回是否成功
+fn write_save_to_file(
+    save_dir: &SaveDir,
+    file_name: &str,
+    tf: &Transform,
+    hp: &Health,
+    inv: Option<&Inventory>,
+    equipped: Option<&EquippedItems>,
+    bonuses: Option<&MetaBonuses>,
+) -> bool {
     let data = SaveData {
         player_x: tf.translation.x,
         player_y: tf.translation.y,
         hp_current: hp.current,
-        hp_max: hp.max,
+        hp_max: hp.max - bonuses.map_or(0.0, |b| b.max_health_bonus),
+        inventory: inv.map(|inv| inv.slots.clone()),
+        equipped_primary: equipped.map(|e| e.primary.clone()),
+        equipped_secondary: equipped.and_then(|e| e.secondary.clone()),
+        equipped_armor: equipped.and_then(|e| e.armor),
     };
 
-    let path = slot_file_path(file_name);
-    if let Ok(bytes) = serde_json::to_vec_pretty(&data) {
-        if let Err(e) = fs::write(&path, bytes) {
-            error!("Failed to write save to {:?}: {}", path, e);
-        }
+    let path = save_dir.file(file_name);
+    let Ok(bytes) = serde_json::to_vec_pretty(&data) else {
+        return false;
+    };
+    if let Err(e) = fs::write(&path, bytes) {
+        error!("Failed to write save to {:?}: {}", path, e);
+        return false;
     }
+    true
+}
+
+fn notify_save_result(notifications: &mut Notifications, lang: Language, file_name: &str, ok: bool) {
+    let name = file_name.trim_end_matches(".json");
+    let prefix = if ok { "notify.saved" } else { "notify.save_failed" };
+    notifications.notify(format!("{}{name}", tr(lang, prefix)));
 }
 
 /// 自动存档：每 60 秒一次（如果 CurrentSlot 为空，就写到 autosave.json）
-/// Bevy 官方 Timer 用法：tick(delta) + just_finished()
+/// 按真实时间计时，不受顿帧和慢动作影响。
+/// 复活面板、商店这类界面停住 Time<Virtual> 时状态仍是 InGame，这段时间不计时也不存档
+#[allow(clippy::too_many_arguments)]
 fn auto_save_every_minute(
-    time: Res<Time>,
+    time: Res<Time<Real>>,
+    virtual_time: Res<Time<Virtual>>,
     mut timer: Local<Option<Timer>>,
-    player_q: Query<(&Transform, &Health), With<Player>>,
+    player_q: SaveSourceQuery,
     mut current: ResMut<CurrentSlot>,
     mut slots: ResMut<SaveSlots>,
+    mut notifications: ResMut<Notifications>,
+    settings: Res<GameSettings>,
+    mut checkpoint: ResMut<Checkpoint>,
+    save_dir: Res<SaveDir>,
 ) {
     if timer.is_none() {
         *timer = Some(Timer::from_seconds(
@@ -330,12 +571,16 @@ fn auto_save_every_minute(
         ));
     }
 
+    if virtual_time.is_paused() {
+        return;
+    }
+
     let t = timer.as_mut().unwrap();
     if !t.tick(time.delta()).just_finished() {
         return;
     }
 
-    let Ok((tf, hp)) = player_q.single() else {
+    let Ok((tf, hp, inv, equipped, bonuses)) = player_q.single() else {
         return;
     };
 
@@ -344,23 +589,175 @@ fn auto_save_every_minute(
         .clone()
         .unwrap_or_else(|| "autosave.json".to_string());
 
-    write_save_to_file(&file_name, tf, hp);
-
-    // 确保 UI 列表能看到 autosave
-    if !slots.slots.iter().any(|s| s.file_name == file_name) {
-        slots.slots.push(SaveSlotMeta {
-            display_name: file_name.trim_end_matches(".json").to_string(),
-            file_name: file_name.clone(),
-            is_auto: true,
-            created_at: ChronoLocal::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-        });
-        slots
-            .slots
-            .sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    if write_save_to_file(&save_dir, &file_name, tf, hp, inv, equipped, bonuses) {
+        notifications.notify(tr(settings.language, "notify.autosaved"));
+        // 自动存档的位置同时作为复活点
+        checkpoint.position = Some(tf.translation.truncate());
     }
 
+    // 确保 UI 列表能看到 autosave，并把它排到最前
+    touch_slot(&mut slots, &file_name, true);
+
     // 如果之前没有 current slot，就把 autosave 设为当前
     if current.file_name.is_none() {
         current.file_name = Some(file_name);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::InventoryConfig;
+
+    /// 模拟“从主菜单读档进入游戏”：玩家实体刚生成、只有位置和血量，同一帧读档
+    #[test]
+    fn loading_into_fresh_session_restores_a_complete_player() {
+        // 存档写进临时目录，不碰玩家数据目录里的真实存档
+        let dir = std::env::temp_dir().join(format!("oplus_test_saves_{}", std::process::id()));
+        let save_dir = SaveDir(dir.clone());
+        let db = ItemDatabase::default();
+        let file_name = "test_restore_player.json".to_string();
+
+        // 存档：主武器猎弓、副武器法杖、穿着皮甲，背包里只剩两瓶药
+        let bow = db.roll(ItemId::HunterBow).unwrap();
+        let wand = db.roll(ItemId::MagicWand).unwrap();
+        let mut saved_inv = Inventory::new(30);
+        saved_inv.try_add(ItemId::HealthPotion, 2, ItemId::HealthPotion.max_stack());
+        let saved_equipped = EquippedItems {
+            primary: bow.clone(),
+            secondary: Some(wand.clone()),
+            armor: Some(ItemId::LeatherArmor),
+        };
+        // 存档时带着 +20 的局外生命加成，存进去的是去掉加成的 100
+        let saved_hp = Health { current: 42.0, max: 120.0 };
+        let saved_bonuses = MetaBonuses { max_health_bonus: 20.0, damage_factor: 1.0, speed_factor: 1.0 };
+        assert!(write_save_to_file(
+            &save_dir,
+            &file_name,
+            &Transform::from_xyz(64.0, -32.0, 0.0),
+            &saved_hp,
+            Some(&saved_inv),
+            Some(&saved_equipped),
+            Some(&saved_bonuses),
+        ));
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(db)
+            .insert_resource(save_dir)
+            .init_resource::<InventoryConfig>()
+            .insert_resource(PendingLoad { file_name: Some(file_name.clone()) })
+            .add_systems(
+                Update,
+                (
+                    ensure_player_inventory_and_equipment,
+                    ensure_attack_state,
+                    apply_pending_load
+                        .after(ensure_player_inventory_and_equipment)
+                        .after(ensure_attack_state),
+                ),
+            );
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::default(),
+                Health::new(130.0),
+                // 读档时局外强化已经升到 +30
+                MetaBonuses { max_health_bonus: 30.0, damage_factor: 1.0, speed_factor: 1.0 },
+            ))
+            .id();
+
+        app.update();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(app.world().resource::<PendingLoad>().file_name.is_none());
+
+        let entity = app.world().entity(player);
+        assert!(entity.contains::<AttackState>());
+        assert!(entity.contains::<PlayerDash>());
+
+        let tf = entity.get::<Transform>().unwrap();
+        assert_eq!(tf.translation.truncate(), Vec2::new(64.0, -32.0));
+        let hp = entity.get::<Health>().unwrap();
+        assert_eq!((hp.current, hp.max), (42.0, 130.0));
+
+        // 背包是存档里的，而不是新角色的初始背包
+        let inv = entity.get::<Inventory>().unwrap();
+        assert_eq!(inv.slot_count(), 30);
+        let stacks: Vec<_> = inv.slots.iter().flatten().map(|s| (s.id, s.count)).collect();
+        assert_eq!(stacks, vec![(ItemId::HealthPotion, 2)]);
+
+        let equipped = entity.get::<EquippedItems>().unwrap();
+        assert_eq!(equipped.primary, bow);
+        assert_eq!(equipped.secondary, Some(wand.clone()));
+        assert_eq!(equipped.armor, Some(ItemId::LeatherArmor));
+
+        // 战斗用的数值和装备一致
+        let set = entity.get::<EquipmentSet>().unwrap();
+        let expected = EquipmentSet::from_weapon(&bow.rolled);
+        assert_eq!(set.weapon_kind, expected.weapon_kind);
+        assert_eq!(set.weapon_damage, expected.weapon_damage);
+        assert_eq!(set.weapon_attack_cooldown, expected.weapon_attack_cooldown);
+        let secondary = entity.get::<SecondaryEquipmentSet>().unwrap();
+        assert_eq!(secondary.0.weapon_damage, EquipmentSet::from_weapon(&wand.rolled).weapon_damage);
+        assert!(entity.contains::<Armor>());
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn slots_named(names: &[&str]) -> SaveSlots {
+        SaveSlots {
+            slots: names
+                .iter()
+                .map(|n| SaveSlotMeta {
+                    display_name: n.to_string(),
+                    file_name: format!("{n}.json"),
+                    is_auto: false,
+                    created_at: String::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn reload_prefers_current_slot_and_falls_back_to_newest() {
+        let slots = slots_named(&["25.12.06.2", "25.12.06.1"]);
+        let current = CurrentSlot { file_name: Some("25.12.06.1.json".into()) };
+        assert_eq!(reload_target(&current, &slots).as_deref(), Some("25.12.06.1.json"));
+
+        // 当前槽的文件已经被删掉：不能去读一个不存在的槽，改读最近的
+        let stale = CurrentSlot { file_name: Some("25.12.05.1.json".into()) };
+        assert_eq!(reload_target(&stale, &slots).as_deref(), Some("25.12.06.2.json"));
+
+        assert_eq!(reload_target(&CurrentSlot::default(), &slots).as_deref(), Some("25.12.06.2.json"));
+        assert_eq!(reload_target(&CurrentSlot::default(), &SaveSlots::default()), None);
+    }
+
+    #[test]
+    fn display_name_is_zero_padded() {
+        assert_eq!(slot_display_name(date(2025, 12, 6), 1), "25.12.06.1");
+        assert_eq!(slot_display_name(date(2031, 1, 9), 12), "31.01.09.12");
+    }
+
+    #[test]
+    fn next_sequence_follows_highest_of_today() {
+        let names = ["25.12.06.1", "25.12.06.3", "25.12.06.2"];
+        assert_eq!(next_slot_sequence(names, date(2025, 12, 6)), 4);
+    }
+
+    #[test]
+    fn next_sequence_resets_each_day() {
+        let names = ["25.12.05.7", "25.11.06.2", "24.12.06.9"];
+        assert_eq!(next_slot_sequence(names, date(2025, 12, 6)), 1);
+        assert_eq!(next_slot_sequence([], date(2025, 12, 6)), 1);
+    }
+
+    #[test]
+    fn next_sequence_ignores_malformed_names() {
+        let names = ["autosave", "25.12.06", "25.12.06.x", "25.12.06.5.1", "我的存档", "25.12.06.2"];
+        assert_eq!(next_slot_sequence(names, date(2025, 12, 6)), 3);
+    }
+}
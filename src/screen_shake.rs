@@ -0,0 +1,93 @@
+// src/screen_shake.rs
+//! 屏幕震动：trauma 越大震得越狠，随时间衰减。
+//! 偏移叠加在镜头跟随之后，跟随每帧重置镜头位置，所以偏移不会累积。
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::combat_core::DamageDealt;
+use crate::health::PlayerDamaged;
+use crate::movement::PlayerCamera;
+use crate::state::GameState;
+use crate::ui::types::GameSettings;
+
+/// 最大偏移（像素），trauma = 1 且强度 100% 时
+const MAX_OFFSET: f32 = 14.0;
+/// 每秒衰减的 trauma
+const TRAUMA_DECAY: f32 = 1.6;
+/// 玩家受到至少这么多伤害才震
+const PLAYER_HIT_THRESHOLD: f32 = 10.0;
+const CRIT_TRAUMA: f32 = 0.15;
+
+#[derive(Resource, Default)]
+pub struct ScreenShake {
+    pub trauma: f32,
+}
+
+impl ScreenShake {
+    pub fn add(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+}
+
+pub struct ScreenShakePlugin;
+
+impl Plugin for ScreenShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScreenShake>()
+            .add_systems(
+                Update,
+                (add_trauma_from_hits, apply_screen_shake)
+                    .chain()
+                    .after(crate::movement::follow_player_camera)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnExit(GameState::InGame), reset_screen_shake);
+    }
+}
+
+fn add_trauma_from_hits(
+    mut shake: ResMut<ScreenShake>,
+    mut damaged_rx: MessageReader<PlayerDamaged>,
+    mut dealt_rx: MessageReader<DamageDealt>,
+) {
+    for ev in damaged_rx.read() {
+        if ev.amount >= PLAYER_HIT_THRESHOLD {
+            shake.add((ev.amount / 50.0).clamp(0.2, 0.6));
+        }
+    }
+    for ev in dealt_rx.read() {
+        if ev.crit {
+            shake.add(CRIT_TRAUMA);
+        }
+    }
+}
+
+#[allow(clippy::collapsible_if)]
+fn apply_screen_shake(
+    time: Res<Time>,
+    settings: Res<GameSettings>,
+    mut shake: ResMut<ScreenShake>,
+    mut camera_q: Query<&mut Transform, With<PlayerCamera>>,
+) {
+    if shake.trauma <= 0.0 {
+        return;
+    }
+
+    let intensity = settings.screen_shake.clamp(0.0, 1.0);
+    if intensity > 0.0 {
+        if let Ok(mut tf) = camera_q.single_mut() {
+            // trauma 平方让小震更轻、大震更明显
+            let amount = shake.trauma * shake.trauma * intensity * MAX_OFFSET;
+            let mut rng = rand::thread_rng();
+            tf.translation.x += rng.gen_range(-1.0..=1.0) * amount;
+            tf.translation.y += rng.gen_range(-1.0..=1.0) * amount;
+        }
+    }
+
+    shake.trauma = (shake.trauma - TRAUMA_DECAY * time.delta_secs()).max(0.0);
+}
+
+fn reset_screen_shake(mut shake: ResMut<ScreenShake>) {
+    shake.trauma = 0.0;
+}
@@ -0,0 +1,153 @@
+// src/sfx.rs
+//! 音效：各处只发 `PlaySfx` 消息，由这里统一播放，音量跟随设置里的音量。
+//! 同一种音效在 `Sfx::min_interval` 内只播一次，避免一批刷怪或连续脚步叠出一大串声音。
+//! 音频文件放在 assets/audio/ 下；启动时只加载实际存在的文件，缺失的音效不出声，也不会刷加载错误。
+
+use std::collections::HashMap;
+
+use bevy::asset::io::file::FileAssetReader;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+use crate::ui::types::GameSettings;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Sfx {
+    /// 附近屏幕外刷出了敌人
+    EnemySpawn,
+    /// 精英出现，比普通刷怪更急促
+    EliteSpawn,
+    /// 玩家走路的脚步
+    Footstep,
+    /// 冲刺起步的风声
+    Dash,
+}
+
+impl Sfx {
+    const ALL: [Sfx; 4] = [Sfx::EnemySpawn, Sfx::EliteSpawn, Sfx::Footstep, Sfx::Dash];
+
+    fn path(self) -> &'static str {
+        match self {
+            Sfx::EnemySpawn => "audio/enemy_spawn.ogg",
+            Sfx::EliteSpawn => "audio/elite_spawn.ogg",
+            Sfx::Footstep => "audio/footstep.ogg",
+            Sfx::Dash => "audio/dash.ogg",
+        }
+    }
+
+    /// 同种音效两次播放的最短间隔（秒，真实时间）
+    fn min_interval(self) -> f32 {
+        match self {
+            Sfx::EnemySpawn => 1.0,
+            Sfx::EliteSpawn => 0.5,
+            Sfx::Footstep => 0.12,
+            Sfx::Dash => 0.1,
+        }
+    }
+
+    /// 相对于总音量的音量
+    fn volume(self) -> f32 {
+        match self {
+            Sfx::EnemySpawn => 0.35,
+            Sfx::EliteSpawn => 0.7,
+            Sfx::Footstep => 0.25,
+            Sfx::Dash => 0.5,
+        }
+    }
+}
+
+/// 播放一次音效；pitch 为播放速度倍率（1.0 为原调）
+#[derive(Message, Clone, Copy, Debug)]
+pub struct PlaySfx {
+    pub sfx: Sfx,
+    pub pitch: f32,
+}
+
+impl PlaySfx {
+    pub fn new(sfx: Sfx) -> Self {
+        Self { sfx, pitch: 1.0 }
+    }
+}
+
+#[derive(Resource, Default)]
+struct SfxHandles(HashMap<Sfx, Handle<AudioSource>>);
+
+/// 每种音效上次播放的时间（Time<Real> 的 elapsed 秒数）
+#[derive(Resource, Default)]
+struct SfxThrottle(HashMap<Sfx, f32>);
+
+impl SfxThrottle {
+    /// 距离上次播放够久就记下这次并返回 true
+    #[allow(clippy::collapsible_if)]
+    fn allow(&mut self, sfx: Sfx, now: f32) -> bool {
+        if let Some(last) = self.0.get(&sfx) {
+            if now - last < sfx.min_interval() {
+                return false;
+            }
+        }
+        self.0.insert(sfx, now);
+        true
+    }
+}
+
+pub struct SfxPlugin;
+
+impl Plugin for SfxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<PlaySfx>()
+            .init_resource::<SfxHandles>()
+            .init_resource::<SfxThrottle>()
+            .add_systems(Startup, load_sfx)
+            .add_systems(Update, play_sfx);
+    }
+}
+
+fn load_sfx(asset_server: Res<AssetServer>, mut handles: ResMut<SfxHandles>) {
+    let assets_dir = FileAssetReader::get_base_path().join("assets");
+    for sfx in Sfx::ALL {
+        if !assets_dir.join(sfx.path()).is_file() {
+            continue;
+        }
+        handles.0.insert(sfx, asset_server.load(sfx.path()));
+    }
+}
+
+fn play_sfx(
+    mut commands: Commands,
+    mut sfx_rx: MessageReader<PlaySfx>,
+    handles: Res<SfxHandles>,
+    settings: Res<GameSettings>,
+    real: Res<Time<Real>>,
+    mut throttle: ResMut<SfxThrottle>,
+) {
+    let now = real.elapsed_secs();
+    for ev in sfx_rx.read() {
+        if settings.volume <= 0.0 || !throttle.allow(ev.sfx, now) {
+            continue;
+        }
+        let Some(handle) = handles.0.get(&ev.sfx) else {
+            continue;
+        };
+        commands.spawn((
+            AudioPlayer::new(handle.clone()),
+            PlaybackSettings::DESPAWN
+                .with_volume(Volume::Linear(settings.volume * ev.sfx.volume()))
+                .with_speed(ev.pitch),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttle_drops_repeats_within_interval() {
+        let mut throttle = SfxThrottle::default();
+        assert!(throttle.allow(Sfx::EnemySpawn, 0.0));
+        assert!(!throttle.allow(Sfx::EnemySpawn, 0.5));
+        // 不同音效互不影响
+        assert!(throttle.allow(Sfx::EliteSpawn, 0.5));
+        assert!(throttle.allow(Sfx::EnemySpawn, 1.0));
+    }
+}
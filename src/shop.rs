@@ -0,0 +1,546 @@
+// src/shop.rs
+//! 商店：关卡里的 "Shop" 实体（没有就在出生点旁边放一个），靠近后按交互键打开。
+//! 面板左边是 `ShopInventory` 的货架，花金币买；右边列出背包物品，按半价卖出。
+//! 武器买下时按品质随机一份属性；打开期间暂停模拟，Esc 或 "离开" 关闭。
+
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::EntityInstance;
+
+use crate::combat_core::CombatSet;
+use crate::equipment::{ItemDatabase, ItemId};
+use crate::i18n::{localized, tr};
+use crate::input::toggle_pause;
+use crate::interaction::{InteractEvent, Interactable};
+use crate::inventory::Inventory;
+use crate::layers;
+use crate::movement::Player;
+use crate::notifications::Notifications;
+use crate::state::GameState;
+use crate::ui::types::GameSettings;
+use crate::utils::despawn_with_children;
+use crate::wallet::Wallet;
+
+/// 没有 LDtk 商店点时，放在玩家出生点旁边的偏移
+const FALLBACK_SHOP_OFFSET: Vec2 = Vec2::new(96.0, 0.0);
+const SHOP_INTERACT_RADIUS: f32 = 48.0;
+const SHOP_SIZE: f32 = 28.0;
+/// 卖出面板最多列出的格子数
+const MAX_SELL_ROWS: usize = 8;
+
+#[derive(Component)]
+pub struct Shop;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ShopEntry {
+    pub id: ItemId,
+    pub price: u32,
+}
+
+/// 货架：无限库存，价格固定
+#[derive(Resource, Clone, Debug)]
+pub struct ShopInventory {
+    pub entries: Vec<ShopEntry>,
+}
+
+impl Default for ShopInventory {
+    fn default() -> Self {
+        Self {
+            entries: vec![
+                ShopEntry { id: ItemId::HealthPotion, price: 15 },
+                ShopEntry { id: ItemId::LeatherArmor, price: 40 },
+                ShopEntry { id: ItemId::RustySword, price: 50 },
+                ShopEntry { id: ItemId::MagicWand, price: 70 },
+                ShopEntry { id: ItemId::HunterBow, price: 60 },
+            ],
+        }
+    }
+}
+
+impl ShopInventory {
+    /// 卖出价：货架价的一半；货架上没有的东西按 5 金币回收
+    pub fn sell_price(&self, id: ItemId) -> u32 {
+        self.entries.iter().find(|e| e.id == id).map_or(5, |e| e.price / 2)
+    }
+}
+
+/// 商店面板打开中；`dirty` 表示买卖后需要重建列表
+#[derive(Resource, Default)]
+struct ShopSession {
+    dirty: bool,
+}
+
+/// 本局是否已经放置过商店，回到标题时重置
+#[derive(Resource, Default)]
+struct ShopSpawned(bool);
+
+#[derive(Component)]
+struct ShopRoot;
+
+#[derive(Component, Clone, Copy)]
+enum ShopAction {
+    Buy(usize),
+    Sell(usize),
+    Close,
+}
+
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShopInventory>()
+            .init_resource::<ShopSpawned>()
+            .add_systems(OnExit(GameState::InGame), close_shop)
+            .add_systems(OnEnter(GameState::MainMenu), cleanup_shop)
+            .add_systems(
+                Update,
+                (spawn_shop, open_shop_on_interact, handle_shop_buttons, sync_shop_ui)
+                    .chain()
+                    .before(toggle_pause)
+                    .before(CombatSet)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// 优先用关卡里的 "Shop" 实体位置，否则放在玩家旁边；每局只放一次
+fn spawn_shop(
+    mut commands: Commands,
+    mut spawned: ResMut<ShopSpawned>,
+    instances: Query<(&EntityInstance, &GlobalTransform)>,
+    player_q: Query<&Transform, With<Player>>,
+) {
+    if spawned.0 {
+        return;
+    }
+    let from_level = instances
+        .iter()
+        .find(|(inst, _)| inst.identifier == "Shop")
+        .map(|(_, gt)| gt.translation().truncate());
+    let Some(pos) = from_level.or_else(|| {
+        player_q
+            .iter()
+            .next()
+            .map(|t| t.translation.truncate() + FALLBACK_SHOP_OFFSET)
+    }) else {
+        return;
+    };
+
+    commands
+        .spawn((
+            Shop,
+            Interactable { radius: SHOP_INTERACT_RADIUS },
+            Sprite::from_color(Color::srgb(0.85, 0.65, 0.2), Vec2::splat(SHOP_SIZE)),
+            Transform::from_translation(pos.extend(layers::PICKUP)),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text2d::new("$"),
+                TextFont { font_size: 20.0, ..default() },
+                TextColor(Color::BLACK),
+                Transform::from_xyz(0.0, 0.0, 0.1),
+            ));
+        });
+    spawned.0 = true;
+}
+
+fn open_shop_on_interact(
+    mut commands: Commands,
+    mut interact_rx: MessageReader<InteractEvent>,
+    session: Option<Res<ShopSession>>,
+    shops: Query<(&Transform, &Interactable), With<Shop>>,
+    player_q: Query<&Transform, With<Player>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if interact_rx.read().count() == 0 || session.is_some() {
+        return;
+    }
+    let Some(player) = player_q.iter().next() else {
+        return;
+    };
+    let player_pos = player.translation.truncate();
+    let near = shops
+        .iter()
+        .any(|(t, i)| t.translation.truncate().distance(player_pos) <= i.radius);
+    if near {
+        commands.insert_resource(ShopSession { dirty: true });
+        virtual_time.pause();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_shop_buttons(
+    mut commands: Commands,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+    session: Option<ResMut<ShopSession>>,
+    mut buttons: Query<(&Interaction, &ShopAction, &mut BackgroundColor), Changed<Interaction>>,
+    shop: Res<ShopInventory>,
+    db: Res<ItemDatabase>,
+    settings: Res<GameSettings>,
+    mut wallet: ResMut<Wallet>,
+    mut player_q: Query<&mut Inventory, With<Player>>,
+    mut notifications: ResMut<Notifications>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    let Some(mut session) = session else {
+        return;
+    };
+    let lang = settings.language;
+    // 点商店按钮的那一下不能再被战斗系统当成开火
+    mouse.clear_just_pressed(MouseButton::Left);
+    mouse.clear_just_pressed(MouseButton::Right);
+
+    let mut action = None;
+    for (interaction, act, mut bg) in &mut buttons {
+        match *interaction {
+            Interaction::Pressed => {
+                bg.0 = Color::srgb(0.8, 0.8, 1.0);
+                action = Some(*act);
+            }
+            Interaction::Hovered => bg.0 = Color::srgb(0.6, 0.6, 0.8),
+            Interaction::None => bg.0 = Color::srgb(0.20, 0.20, 0.40),
+        }
+    }
+    // 商店开着时 Esc 只关商店，不打开暂停菜单
+    if keyboard.just_pressed(KeyCode::Escape) {
+        keyboard.clear_just_pressed(KeyCode::Escape);
+        action = Some(ShopAction::Close);
+    }
+    let Some(action) = action else {
+        return;
+    };
+
+    let Ok(mut inv) = player_q.single_mut() else {
+        return;
+    };
+    match action {
+        ShopAction::Buy(index) => {
+            let Some(entry) = shop.entries.get(index).copied() else {
+                return;
+            };
+            if let Err(key) = buy(entry, &db, &mut wallet, &mut inv) {
+                notifications.notify(tr(lang, key));
+                return;
+            }
+            notifications.notify(format!(
+                "{}{}",
+                tr(lang, "notify.shop_bought"),
+                entry.id.display_name(lang)
+            ));
+            session.dirty = true;
+        }
+        ShopAction::Sell(slot) => {
+            let Some((id, price)) = sell(slot, &shop, &mut wallet, &mut inv) else {
+                return;
+            };
+            notifications.notify(format!(
+                "{}{} (+{})",
+                tr(lang, "notify.shop_sold"),
+                id.display_name(lang),
+                price
+            ));
+            session.dirty = true;
+        }
+        ShopAction::Close => {
+            commands.remove_resource::<ShopSession>();
+            virtual_time.unpause();
+        }
+    }
+}
+
+/// 买一件：先确认钱够、背包放得下，都满足才扣钱。失败时返回提示文本的 key
+fn buy(entry: ShopEntry, db: &ItemDatabase, wallet: &mut Wallet, inv: &mut Inventory) -> Result<(), &'static str> {
+    if wallet.gold < entry.price {
+        return Err("notify.shop_no_gold");
+    }
+    // 武器按品质随机一份属性，其余按普通物品叠加
+    let added = match db.roll(entry.id) {
+        Some(instance) => inv.try_add_instance(instance),
+        None => inv.try_add(entry.id, 1, entry.id.max_stack()) == 0,
+    };
+    if !added {
+        return Err("notify.inventory_full");
+    }
+    wallet.try_spend(entry.price);
+    Ok(())
+}
+
+/// 卖掉背包第 slot 格的一个物品，返回卖掉的物品和得到的金币；空格子返回 None
+fn sell(slot: usize, shop: &ShopInventory, wallet: &mut Wallet, inv: &mut Inventory) -> Option<(ItemId, u32)> {
+    let id = inv.remove_one_at(slot)?;
+    let price = shop.sell_price(id);
+    wallet.earn(price);
+    Some((id, price))
+}
+
+/// 面板跟着 ShopSession 走：打开或买卖后重建，关闭后收起
+#[allow(clippy::too_many_arguments)]
+fn sync_shop_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    session: Option<ResMut<ShopSession>>,
+    roots: Query<Entity, With<ShopRoot>>,
+    children_q: Query<&Children>,
+    shop: Res<ShopInventory>,
+    settings: Res<GameSettings>,
+    wallet: Res<Wallet>,
+    player_q: Query<&Inventory, With<Player>>,
+) {
+    let rebuild = match &session {
+        Some(s) => s.dirty || roots.is_empty(),
+        None => false,
+    };
+    if session.is_none() || rebuild {
+        for root in &roots {
+            despawn_with_children(&mut commands, &children_q, root);
+        }
+    }
+    let Some(mut session) = session else {
+        return;
+    };
+    if !rebuild {
+        return;
+    }
+    session.dirty = false;
+
+    let font: Handle<Font> = asset_server.load("fonts/YuFanLixing.otf");
+    let lang = settings.language;
+    let text_font = |size: f32| TextFont { font: font.clone(), font_size: size, ..default() };
+    let button_node = || Node {
+        width: Val::Px(260.0),
+        height: Val::Px(46.0),
+        justify_content: JustifyContent::SpaceBetween,
+        align_items: AlignItems::Center,
+        padding: UiRect::horizontal(Val::Px(12.0)),
+        ..default()
+    };
+
+    // 背包里非空的格子：(格子下标, 物品, 数量)
+    let sellable: Vec<(usize, ItemId, u32)> = player_q
+        .iter()
+        .next()
+        .map(|inv| {
+            inv.slots
+                .iter()
+                .enumerate()
+                .filter_map(|(i, s)| s.as_ref().map(|s| (i, s.id, s.count)))
+                .take(MAX_SELL_ROWS)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    commands
+        .spawn((
+            ShopRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            GlobalZIndex(5),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    max_width: Val::Percent(90.0),
+                    max_height: Val::Percent(90.0),
+                    overflow: Overflow::scroll_y(),
+                    padding: UiRect::all(Val::Px(26.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(12.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.12, 0.12, 0.16, 0.95)),
+            ))
+            .with_children(|panel| {
+                panel.spawn((localized(lang, "shop.title"), text_font(32.0), TextColor(Color::WHITE)));
+                panel.spawn((
+                    Text::new(format!("{}: {}", tr(lang, "hud.gold"), wallet.gold)),
+                    text_font(18.0),
+                    TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                ));
+
+                panel
+                    .spawn(Node { column_gap: Val::Px(24.0), ..default() })
+                    .with_children(|columns| {
+                        columns
+                            .spawn(Node { flex_direction: FlexDirection::Column, row_gap: Val::Px(8.0), ..default() })
+                            .with_children(|col| {
+                                col.spawn((localized(lang, "shop.buy"), text_font(20.0), TextColor(Color::WHITE)));
+                                for (i, entry) in shop.entries.iter().enumerate() {
+                                    col.spawn((
+                                        Button,
+                                        ShopAction::Buy(i),
+                                        button_node(),
+                                        BackgroundColor(Color::srgb(0.20, 0.20, 0.40)),
+                                    ))
+                                    .with_children(|btn| {
+                                        btn.spawn((
+                                            Text::new(entry.id.display_name(lang)),
+                                            text_font(18.0),
+                                            TextColor(Color::WHITE),
+                                        ));
+                                        btn.spawn((
+                                            Text::new(entry.price.to_string()),
+                                            text_font(18.0),
+                                            TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                                        ));
+                                    });
+                                }
+                            });
+
+                        columns
+                            .spawn(Node { flex_direction: FlexDirection::Column, row_gap: Val::Px(8.0), ..default() })
+                            .with_children(|col| {
+                                col.spawn((localized(lang, "shop.sell"), text_font(20.0), TextColor(Color::WHITE)));
+                                if sellable.is_empty() {
+                                    col.spawn((
+                                        localized(lang, "shop.nothing_to_sell"),
+                                        text_font(18.0),
+                                        TextColor(Color::srgba(1.0, 1.0, 1.0, 0.6)),
+                                    ));
+                                }
+                                for &(slot, id, count) in &sellable {
+                                    col.spawn((
+                                        Button,
+                                        ShopAction::Sell(slot),
+                                        button_node(),
+                                        BackgroundColor(Color::srgb(0.20, 0.20, 0.40)),
+                                    ))
+                                    .with_children(|btn| {
+                                        let name = if count > 1 {
+                                            format!("{} x{}", id.display_name(lang), count)
+                                        } else {
+                                            id.display_name(lang).to_string()
+                                        };
+                                        btn.spawn((Text::new(name), text_font(18.0), TextColor(Color::WHITE)));
+                                        btn.spawn((
+                                            Text::new(format!("+{}", shop.sell_price(id))),
+                                            text_font(18.0),
+                                            TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                                        ));
+                                    });
+                                }
+                            });
+                    });
+
+                panel
+                    .spawn((
+                        Button,
+                        ShopAction::Close,
+                        Node {
+                            width: Val::Px(260.0),
+                            height: Val::Px(46.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.20, 0.20, 0.40)),
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((localized(lang, "shop.leave"), text_font(20.0), TextColor(Color::WHITE)));
+                    });
+            });
+        });
+}
+
+/// 离开 InGame（暂停、回标题）时关掉面板并恢复模拟
+fn close_shop(
+    mut commands: Commands,
+    session: Option<Res<ShopSession>>,
+    roots: Query<Entity, With<ShopRoot>>,
+    children_q: Query<&Children>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if session.is_some() {
+        commands.remove_resource::<ShopSession>();
+        virtual_time.unpause();
+    }
+    for root in &roots {
+        despawn_with_children(&mut commands, &children_q, root);
+    }
+}
+
+fn cleanup_shop(
+    mut commands: Commands,
+    mut spawned: ResMut<ShopSpawned>,
+    shops: Query<Entity, With<Shop>>,
+    children_q: Query<&Children>,
+) {
+    spawned.0 = false;
+    for e in &shops {
+        despawn_with_children(&mut commands, &children_q, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: ItemId) -> ShopEntry {
+        *ShopInventory::default().entries.iter().find(|e| e.id == id).unwrap()
+    }
+
+    #[test]
+    fn buying_takes_gold_and_adds_the_item() {
+        let potion = entry(ItemId::HealthPotion);
+        let mut wallet = Wallet { gold: potion.price + 1 };
+        let mut inv = Inventory::new(2);
+
+        assert_eq!(buy(potion, &ItemDatabase::default(), &mut wallet, &mut inv), Ok(()));
+        assert_eq!(wallet.gold, 1);
+        assert_eq!(inv.slots[0].as_ref().map(|s| s.id), Some(ItemId::HealthPotion));
+    }
+
+    #[test]
+    fn buying_a_weapon_rolls_an_instance() {
+        let sword = entry(ItemId::RustySword);
+        let mut wallet = Wallet { gold: sword.price };
+        let mut inv = Inventory::new(2);
+
+        assert_eq!(buy(sword, &ItemDatabase::default(), &mut wallet, &mut inv), Ok(()));
+        assert!(inv.slots[0].as_ref().is_some_and(|s| s.instance.is_some()));
+    }
+
+    #[test]
+    fn buying_without_enough_gold_changes_nothing() {
+        let armor = entry(ItemId::LeatherArmor);
+        let mut wallet = Wallet { gold: armor.price - 1 };
+        let mut inv = Inventory::new(2);
+
+        assert_eq!(buy(armor, &ItemDatabase::default(), &mut wallet, &mut inv), Err("notify.shop_no_gold"));
+        assert_eq!(wallet.gold, armor.price - 1);
+        assert_eq!(inv.free_slots(), 2);
+    }
+
+    #[test]
+    fn buying_into_a_full_inventory_keeps_the_gold() {
+        let armor = entry(ItemId::LeatherArmor);
+        let mut wallet = Wallet { gold: 1000 };
+        let mut inv = Inventory::new(1);
+        inv.try_add(ItemId::LeatherArmor, 1, ItemId::LeatherArmor.max_stack());
+
+        assert_eq!(buy(armor, &ItemDatabase::default(), &mut wallet, &mut inv), Err("notify.inventory_full"));
+        assert_eq!(wallet.gold, 1000);
+    }
+
+    #[test]
+    fn selling_pays_half_price_and_frees_the_slot() {
+        let shop = ShopInventory::default();
+        let mut wallet = Wallet::default();
+        let mut inv = Inventory::new(2);
+        inv.try_add(ItemId::LeatherArmor, 1, 1);
+
+        let price = entry(ItemId::LeatherArmor).price / 2;
+        assert_eq!(sell(0, &shop, &mut wallet, &mut inv), Some((ItemId::LeatherArmor, price)));
+        assert_eq!(wallet.gold, price);
+        assert!(inv.slots[0].is_none());
+        assert_eq!(sell(0, &shop, &mut wallet, &mut inv), None, "空格子没东西可卖");
+        assert_eq!(wallet.gold, price);
+    }
+}
@@ -1,14 +1,33 @@
 use bevy::prelude::*;
-
-use crate::combat_core::{skill_slash, spawn_slash_vfx, CombatSet, VfxPool};
+use rand::Rng;
+
+use crate::buffs::Buffs;
+use crate::checkpoint::Downed;
+use crate::combat_core::{
+    skill_slash, spawn_slash_vfx, CombatSet, DamageDealt, VfxPool, SKILL_SLASH_DAMAGE, SLASH_VFX_COLOR,
+    SLASH_WHIFF_VFX_COLOR,
+};
 use crate::enemy::Enemy;
-use crate::health::Health;
+use crate::health::{grant_shield, Health, Shield};
+use crate::hit_stop::{HitStop, SLASH_HIT_STOP_SECS};
+use crate::input::{KeyBindings, MovementInput};
+use crate::ldtk_collision::WallColliders;
+use crate::meta_progress::MetaBonuses;
 use crate::movement::{Player, PlayerAnimation, PlayerDash};
+use crate::palette::Palette;
+use crate::sfx::{PlaySfx, Sfx};
+use crate::screen_shake::ScreenShake;
 use crate::skills_pool::{SkillId, SkillPool};
 use crate::state::GameState;
 
 const MAX_SKILL_CARDS: usize = 3;
 const SKILL_CARD_SIZE: f32 = 64.0;
+/// 护盾技能每次给的护盾量
+const BARRIER_SHIELD: f32 = 30.0;
+/// 斩击落空时返还的冷却比例
+const SLASH_WHIFF_COOLDOWN_REFUND: f32 = 0.5;
+const HP_BAR_WIDTH: f32 = 180.0;
+const HP_BAR_HEIGHT: f32 = 10.0;
 
 #[derive(Component)]
 struct SkillUiRoot;
@@ -27,6 +46,14 @@ struct SkillCooldownText {
 #[derive(Component)]
 struct HpText;
 
+/// 血条的红色部分
+#[derive(Component)]
+struct HpBarFill;
+
+/// 叠在血条上的蓝色护盾层
+#[derive(Component)]
+struct ShieldBarFill;
+
 #[derive(Resource)]
 struct SkillSpawnTimer(pub Timer);
 
@@ -50,6 +77,8 @@ impl Plugin for SkillPlugin {
                     use_number_key_skills,
                     use_dash_skill_with_ctrl,
                     update_hp_text,
+                    update_hp_bar,
+                    apply_palette_to_hp_bar,
                     update_skill_cooldowns,
                 )
                     .in_set(CombatSet),
@@ -57,7 +86,7 @@ impl Plugin for SkillPlugin {
     }
 }
 
-fn setup_skill_ui(mut commands: Commands) {
+fn setup_skill_ui(mut commands: Commands, palette: Res<Palette>) {
     let root = commands
         .spawn((
             SkillUiRoot,
@@ -86,6 +115,41 @@ fn setup_skill_ui(mut commands: Commands) {
             },
         ));
 
+        parent
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(16.0),
+                    top: Val::Px(42.0),
+                    width: Val::Px(HP_BAR_WIDTH),
+                    height: Val::Px(HP_BAR_HEIGHT),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+            ))
+            .with_children(|bar| {
+                bar.spawn((
+                    HpBarFill,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(palette.hp_fill),
+                ));
+                bar.spawn((
+                    ShieldBarFill,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(palette.shield_fill),
+                ));
+            });
+
         for i in 0..MAX_SKILL_CARDS {
             parent.spawn((
                 SkillCard { slot_index: i, skill: SkillId::Slash },
@@ -136,7 +200,7 @@ fn cleanup_skill_ui(mut commands: Commands, root_q: Query<Entity, With<SkillUiRo
 }
 
 fn spawn_other_skills(
-    time: Res<Time>,
+    time: Res<Time<Virtual>>,
     mut timer: ResMut<SkillSpawnTimer>,
     mut pool: ResMut<SkillPool>,
     cards_q: Query<&SkillCard>,
@@ -156,27 +220,37 @@ fn spawn_other_skills(
 
     for (i, occupied) in used.iter().enumerate() {
         if !*occupied {
-            let skill = pool.next_non_dash();
+            let skill = pool.next_player_skill();
             commands.spawn((SkillCard { slot_index: i, skill },));
         }
     }
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn use_number_key_skills(
     keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
     mut cooldowns: ResMut<SkillCooldowns>,
     mut cards_q: Query<(Entity, &SkillCard)>,
-    mut player_q: Query<(&Transform, &mut PlayerAnimation), With<Player>>,
-    mut enemies_q: Query<(Entity, &Transform, &mut Health), With<Enemy>>,
+    mut player_q: Query<
+        (Entity, &Transform, &mut PlayerAnimation, Option<&mut Shield>, Option<&MetaBonuses>, Option<&Buffs>),
+        (With<Player>, Without<Downed>),
+    >,
+    enemies_q: Query<(Entity, &Transform), With<Enemy>>,
+    mut dealt_tx: MessageWriter<DamageDealt>,
     mut commands: Commands,
     pool: Res<SkillPool>,
     mut vfx_pool: ResMut<VfxPool>,
+    mut shake: ResMut<ScreenShake>,
+    mut hit_stop: ResMut<HitStop>,
+    walls: Res<WallColliders>,
 ) {
-    let Ok((player_tf, anim)) = player_q.single_mut() else { return; };
+    let Ok((player, player_tf, anim, mut shield, bonuses, buffs)) = player_q.single_mut() else { return; };
+    let damage_mult = bonuses.map_or(1.0, |b| b.damage_factor) * buffs.map_or(1.0, Buffs::damage_factor);
     let origin = player_tf.translation.truncate();
     let dir = anim.direction.as_vec2().normalize_or_zero();
 
-    let keys = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3];
+    let keys = bindings.skill_keys();
 
     for (slot, key) in keys.iter().enumerate() {
         if !keyboard.just_pressed(*key) {
@@ -201,11 +275,24 @@ fn use_number_key_skills(
 
         match skill {
             SkillId::Slash => {
-                spawn_slash_vfx(&mut commands, Some(&mut vfx_pool), origin, dir);
-                skill_slash(origin, dir, &mut enemies_q);
-                cooldowns.slot[slot] = pool.def(SkillId::Slash).cooldown;
+                let hits = skill_slash(origin, dir, SKILL_SLASH_DAMAGE * damage_mult, &enemies_q, &walls, &mut dealt_tx);
+                let cooldown = pool.def(SkillId::Slash).cooldown;
+                if hits > 0 {
+                    // 命中：亮色特效 + 震屏 + 顿帧，打中的越多震得越狠
+                    spawn_slash_vfx(&mut commands, Some(&mut vfx_pool), origin, dir, SLASH_VFX_COLOR);
+                    shake.add(0.25 + 0.05 * (hits - 1).min(5) as f32);
+                    hit_stop.trigger(SLASH_HIT_STOP_SECS);
+                    cooldowns.slot[slot] = cooldown;
+                } else {
+                    // 落空：暗淡的特效，不震屏不顿帧，返还一部分冷却
+                    spawn_slash_vfx(&mut commands, Some(&mut vfx_pool), origin, dir, SLASH_WHIFF_VFX_COLOR);
+                    cooldowns.slot[slot] = cooldown * (1.0 - SLASH_WHIFF_COOLDOWN_REFUND);
+                }
+            }
+            SkillId::Barrier => {
+                grant_shield(&mut commands, player, shield.as_deref_mut(), BARRIER_SHIELD);
+                cooldowns.slot[slot] = pool.def(SkillId::Barrier).cooldown;
             }
-            SkillId::Dash => {}
         }
 
         if let Some(e) = used_entity {
@@ -214,23 +301,32 @@ fn use_number_key_skills(
     }
 }
 
+/// 冲刺方向优先取当前按住的移动方向（可以斜向），没按方向键时才沿最后的朝向
 fn use_dash_skill_with_ctrl(
     keyboard: Res<ButtonInput<KeyCode>>,
-    time: Res<Time>,
+    bindings: Res<KeyBindings>,
+    movement: Res<MovementInput>,
+    time: Res<Time<Virtual>>,
     mut player_q: Query<(Entity, &mut PlayerDash, &mut PlayerAnimation), With<Player>>,
+    mut sfx_tx: MessageWriter<PlaySfx>,
 ) {
     let Ok((_e, mut dash, anim)) = player_q.single_mut() else { return; };
 
     dash.cooldown = (dash.cooldown - time.delta_secs()).max(0.0);
 
-    if keyboard.just_pressed(KeyCode::ControlLeft) && dash.cooldown <= 0.0 {
-        let dir_vec = anim.direction.as_vec2();
-        let dir = if dir_vec == Vec2::ZERO { Vec2::Y } else { dir_vec };
+    if keyboard.just_pressed(bindings.dash) && dash.cooldown <= 0.0 {
+        let facing = anim.direction.as_vec2();
+        let dir = movement
+            .0
+            .try_normalize()
+            .unwrap_or(if facing == Vec2::ZERO { Vec2::Y } else { facing });
 
         dash.is_dashing = true;
         dash.remaining = crate::movement::DASH_DURATION;
         dash.direction = dir;
         dash.cooldown = crate::movement::DASH_COOLDOWN;
+        let pitch = rand::thread_rng().gen_range(0.95..1.05);
+        sfx_tx.write(PlaySfx { sfx: Sfx::Dash, pitch });
     }
 }
 
@@ -241,8 +337,50 @@ fn update_hp_text(mut q: Query<&mut Text, With<HpText>>, player_q: Query<&Health
     }
 }
 
+/// 血条按当前血量缩放，护盾层按护盾量相对最大血量缩放
+#[allow(clippy::type_complexity)]
+fn update_hp_bar(
+    player_q: Query<(&Health, Option<&Shield>), With<Player>>,
+    mut fills: ParamSet<(
+        Query<&mut Node, With<HpBarFill>>,
+        Query<&mut Node, With<ShieldBarFill>>,
+    )>,
+) {
+    let Ok((hp, shield)) = player_q.single() else { return; };
+    let max = hp.max.max(1.0);
+    let hp_ratio = (hp.current / max).clamp(0.0, 1.0);
+    let shield_ratio = (shield.map(|s| s.amount).unwrap_or(0.0) / max).clamp(0.0, 1.0);
+
+    for mut node in &mut fills.p0() {
+        node.width = Val::Percent(hp_ratio * 100.0);
+    }
+    for mut node in &mut fills.p1() {
+        node.width = Val::Percent(shield_ratio * 100.0);
+    }
+}
+
+/// 切换色盲模式时血条跟着换色
+#[allow(clippy::type_complexity)]
+fn apply_palette_to_hp_bar(
+    palette: Res<Palette>,
+    mut fills: ParamSet<(
+        Query<&mut BackgroundColor, With<HpBarFill>>,
+        Query<&mut BackgroundColor, With<ShieldBarFill>>,
+    )>,
+) {
+    if !palette.is_changed() {
+        return;
+    }
+    for mut bg in &mut fills.p0() {
+        bg.0 = palette.hp_fill;
+    }
+    for mut bg in &mut fills.p1() {
+        bg.0 = palette.shield_fill;
+    }
+}
+
 fn update_skill_cooldowns(
-    time: Res<Time>,
+    time: Res<Time<Virtual>>,
     mut cooldowns: ResMut<SkillCooldowns>,
     cards_q: Query<&SkillCard>,
     mut cd_text_q: Query<(&SkillCooldownText, &mut Text)>,
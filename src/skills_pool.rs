@@ -2,42 +2,46 @@ use bevy::prelude::*;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum SkillId {
-    Dash,
     Slash,
+    /// 护盾：获得一层会随时间衰减的护盾
+    Barrier,
 }
 
 #[derive(Clone, Copy, Debug)]
 pub struct SkillDef {
-    pub id: SkillId,
     pub name: &'static str,
     pub cooldown: f32,
 }
 
-#[derive(Resource, Debug)]
+#[derive(Resource, Debug, Default)]
 pub struct SkillPool {
     next_other: usize,
-}
-
-impl Default for SkillPool {
-    fn default() -> Self {
-        Self { next_other: 0 }
-    }
+    next_player: usize,
 }
 
 impl SkillPool {
     pub fn def(&self, id: SkillId) -> SkillDef {
         match id {
-            SkillId::Dash => SkillDef { id, name: "Dash", cooldown: 3.0 },
-            SkillId::Slash => SkillDef { id, name: "Slash", cooldown: 6.0 },
+            SkillId::Slash => SkillDef { name: "Slash", cooldown: 6.0 },
+            SkillId::Barrier => SkillDef { name: "Barrier", cooldown: 10.0 },
         }
     }
 
-    pub fn next_non_dash(&mut self) -> SkillId {
+    /// 敌人施法轮换
+    pub fn next_enemy_skill(&mut self) -> SkillId {
         let list = [SkillId::Slash];
         let id = list[self.next_other % list.len()];
         self.next_other = self.next_other.wrapping_add(1);
         id
     }
+
+    /// 玩家技能卡轮换，和敌人的施法顺序分开计数
+    pub fn next_player_skill(&mut self) -> SkillId {
+        let list = [SkillId::Slash, SkillId::Barrier];
+        let id = list[self.next_player % list.len()];
+        self.next_player = self.next_player.wrapping_add(1);
+        id
+    }
 }
 
 pub struct SkillPoolPlugin;
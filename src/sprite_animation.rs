@@ -0,0 +1,186 @@
+// src/sprite_animation.rs
+//! 行走图动画的公共部分：切帧配置、按贴图尺寸解析布局，以及行走循环的逐帧步进。
+//! 玩家和敌人各自决定用哪一行（方向）和是否在走，帧的推进和 `Sprite::rect` 的更新都交给 `WalkCycle`。
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+/// 行走图默认布局：4 列动画帧 × 4 行方向
+const DEFAULT_SHEET_COLUMNS: usize = 4;
+const DEFAULT_SHEET_ROWS: usize = 4;
+
+/// 行走图的切帧配置。没指定的项按贴图尺寸自动推断：
+/// 给了帧尺寸就按尺寸数出列数，否则按列数 / 行数均分贴图
+#[derive(Component, Clone, Debug, Default)]
+pub struct SpriteSheetConfig {
+    pub columns: Option<usize>,
+    pub rows: Option<usize>,
+    pub frame_size: Option<Vec2>,
+    /// 相邻两帧之间的间隔（像素）
+    pub padding: Vec2,
+    /// 第一帧左上角相对贴图左上角的偏移（像素）
+    pub offset: Vec2,
+}
+
+impl SpriteSheetConfig {
+    /// 结合贴图实际尺寸得出最终布局
+    pub fn resolve(&self, texture_size: Vec2) -> SheetLayout {
+        let usable = texture_size - self.offset;
+        let fit = |len: f32, frame: f32, gap: f32| (((len + gap) / (frame + gap)).floor() as usize).max(1);
+
+        let (columns, rows, frame_size) = match self.frame_size {
+            Some(frame) => (
+                self.columns.unwrap_or_else(|| fit(usable.x, frame.x, self.padding.x)),
+                self.rows.unwrap_or_else(|| fit(usable.y, frame.y, self.padding.y)),
+                frame,
+            ),
+            None => {
+                let columns = self.columns.unwrap_or(DEFAULT_SHEET_COLUMNS).max(1);
+                let rows = self.rows.unwrap_or(DEFAULT_SHEET_ROWS).max(1);
+                let cells = Vec2::new(columns as f32, rows as f32);
+                let frame = (usable - self.padding * (cells - Vec2::ONE)) / cells;
+                (columns, rows, frame)
+            }
+        };
+
+        SheetLayout { columns, rows, frame_size, padding: self.padding, offset: self.offset }
+    }
+}
+
+/// 解析后的行走图布局
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SheetLayout {
+    pub columns: usize,
+    pub rows: usize,
+    pub frame_size: Vec2,
+    pub padding: Vec2,
+    pub offset: Vec2,
+}
+
+impl SheetLayout {
+    /// 第 row 行第 col 列那一帧在贴图上的像素范围
+    pub fn frame_rect(&self, col: usize, row: usize) -> Rect {
+        let step = self.frame_size + self.padding;
+        let min = self.offset + Vec2::new(col as f32, row as f32) * step;
+        Rect { min, max: min + self.frame_size }
+    }
+}
+
+/// 行走循环：贴图加载后解析布局，移动时按固定间隔推进帧，停下时回到第一帧
+#[derive(Debug)]
+pub struct WalkCycle {
+    pub frame: usize,
+    /// 贴图加载完成后才有
+    pub layout: Option<SheetLayout>,
+    timer: Timer,
+}
+
+impl WalkCycle {
+    pub fn new(frame_secs: f32) -> Self {
+        Self {
+            frame: 0,
+            layout: None,
+            timer: Timer::from_seconds(frame_secs, TimerMode::Repeating),
+        }
+    }
+
+    /// 还没解析布局且贴图已加载时解析；返回本次是否完成了初始化
+    pub fn init(&mut self, images: &Assets<Image>, sprite: &Sprite, config: Option<&SpriteSheetConfig>) -> bool {
+        if self.layout.is_some() {
+            return false;
+        }
+        let Some(image) = images.get(&sprite.image) else {
+            return false;
+        };
+        self.layout = Some(config.cloned().unwrap_or_default().resolve(image.size().as_vec2()));
+        true
+    }
+
+    /// 推进行走动画；不在移动时停在第一帧。返回这次是否换到了下一帧
+    pub fn tick(&mut self, delta: Duration, moving: bool) -> bool {
+        let Some(layout) = self.layout else { return false; };
+        self.timer.tick(delta);
+        if moving {
+            if self.timer.just_finished() {
+                self.frame = (self.frame + 1) % layout.columns.max(1);
+                return true;
+            }
+        } else {
+            self.frame = 0;
+        }
+        false
+    }
+
+    /// 从第一帧重新开始
+    pub fn restart(&mut self) {
+        self.frame = 0;
+        self.timer.reset();
+    }
+
+    /// 把当前帧写进 sprite；row 超出贴图行数时退回第一行
+    pub fn apply(&self, sprite: &mut Sprite, row: usize) {
+        let Some(layout) = self.layout else { return; };
+        if layout.frame_size.x <= 0.0 || layout.frame_size.y <= 0.0 {
+            return;
+        }
+        let row = if row < layout.rows { row } else { 0 };
+        let col = self.frame % layout.columns.max(1);
+        sprite.rect = Some(layout.frame_rect(col, row));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_layout_splits_non_square_sheet_evenly() {
+        // 与 player.png 相同的尺寸：每帧 632 x 424
+        let layout = SpriteSheetConfig::default().resolve(Vec2::new(2528.0, 1696.0));
+        assert_eq!((layout.columns, layout.rows), (4, 4));
+        assert_eq!(layout.frame_size, Vec2::new(632.0, 424.0));
+
+        let rect = layout.frame_rect(3, 2);
+        assert_eq!(rect.min, Vec2::new(1896.0, 848.0));
+        assert_eq!(rect.max, Vec2::new(2528.0, 1272.0));
+    }
+
+    #[test]
+    fn explicit_frame_size_with_padding_and_offset() {
+        // 4 px 外边距，帧 30 x 40，帧间隔 2 px，右侧和底部还有多余的空白
+        let config = SpriteSheetConfig {
+            frame_size: Some(Vec2::new(30.0, 40.0)),
+            padding: Vec2::splat(2.0),
+            offset: Vec2::splat(4.0),
+            ..default()
+        };
+        let layout = config.resolve(Vec2::new(140.0, 190.0));
+        // (140 - 4 + 2) / 32 = 4.3 列，(190 - 4 + 2) / 42 = 4.4 行
+        assert_eq!((layout.columns, layout.rows), (4, 4));
+
+        let first = layout.frame_rect(0, 0);
+        assert_eq!(first.min, Vec2::new(4.0, 4.0));
+        assert_eq!(first.max, Vec2::new(34.0, 44.0));
+
+        let last = layout.frame_rect(3, 3);
+        assert_eq!(last.min, Vec2::new(4.0 + 3.0 * 32.0, 4.0 + 3.0 * 42.0));
+        assert_eq!(last.size(), Vec2::new(30.0, 40.0));
+        assert!(last.max.x <= 140.0 && last.max.y <= 190.0);
+    }
+
+    #[test]
+    fn explicit_grid_with_padding_excludes_gaps_from_frames() {
+        let config = SpriteSheetConfig {
+            columns: Some(3),
+            rows: Some(2),
+            padding: Vec2::new(6.0, 4.0),
+            ..default()
+        };
+        let layout = config.resolve(Vec2::new(108.0, 84.0));
+        // (108 - 2 * 6) / 3 = 32，(84 - 1 * 4) / 2 = 40
+        assert_eq!(layout.frame_size, Vec2::new(32.0, 40.0));
+        assert_eq!(layout.frame_rect(2, 1).min, Vec2::new(76.0, 44.0));
+        assert_eq!(layout.frame_rect(2, 1).max, Vec2::new(108.0, 84.0));
+    }
+}
@@ -1,10 +1,27 @@
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 
 #[derive(States, Debug, Clone, Eq, PartialEq, Hash, Default)]
 pub enum GameState {
     #[default]
     MainMenu,
     InGame,
+    /// 暂停：所有模拟系统（移动、敌人 AI、战斗、投射物、特效计时）只在 InGame 运行，
+    /// 同时暂停 Time<Virtual>。Paused 中照常运行的只有菜单/设置/存档面板 UI、
+    /// 手动保存与读档（save.rs）以及提示消息（用 Time<Real> 计时）。
+    /// 玩法计时（刷怪、敌人施法、技能冷却）显式用 Time<Virtual>，跟着暂停、顿帧和慢动作走；
+    /// 自动存档、过渡、提示这类按真实时间走的用 Time<Real>。
     Paused,
     GameOver,
 }
+
+/// 运行条件：主窗口有焦点（没有窗口时视为有焦点）
+pub fn window_has_focus(window_q: Query<&Window, With<PrimaryWindow>>) -> bool {
+    window_q.single().map(|w| w.focused).unwrap_or(true)
+}
+
+/// 运行条件：这一局结束了（用在 OnExit(InGame) / OnExit(Paused) 上）。
+/// 退出回调执行时 State 已经是新状态，InGame 和 Paused 之间切换不算离开
+pub fn leaving_run(state: Res<State<GameState>>) -> bool {
+    !matches!(state.get(), GameState::InGame | GameState::Paused)
+}
@@ -0,0 +1,226 @@
+// src/test_support.rs
+//! 无渲染的测试环境：MinimalPlugins + 状态机 + 逻辑插件（生命、战斗、敌人、精英、存档），
+//! 不开窗口、不渲染，直接进入 InGame。时间按固定步长手动推进，方便断言几帧之后的结果。
+//! 需要贴图和 Gizmos 的系统靠 `AssetPlugin` / `GizmoPlugin` 照常运行，只是不会画出任何东西。
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+
+use crate::aim_assist::LockedTarget;
+use crate::balance::BalancePlugin;
+use crate::checkpoint::{Checkpoint, ReviveCharges};
+use crate::combat::CombatPlugin;
+use crate::combat_core::CombatCorePlugin;
+use crate::difficulty_scaling::DifficultyScaling;
+use crate::elite::ElitePlugin;
+use crate::enemy::{Enemy, EnemyPlugin};
+use crate::equipment::{EquipmentSet, ItemDatabase};
+use crate::health::{Health, HealthPlugin};
+use crate::input::{KeyBindings, MovementInput};
+use crate::inventory::InventoryConfig;
+use crate::ldtk_collision::WallColliders;
+use crate::movement::Player;
+use crate::notifications::Notifications;
+use crate::palette::Palette;
+use crate::run_stats::RunStats;
+use crate::save::{SaveDir, SavePlugin};
+use crate::sfx::PlaySfx;
+use crate::state::GameState;
+use crate::transition::TransitionRequest;
+use crate::ui::types::GameSettings;
+
+/// 每次 `app.update()` 推进的时间
+pub const STEP: Duration = Duration::from_millis(16);
+
+/// 搭一个只跑逻辑系统的 App，并切到 InGame
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        AssetPlugin::default(),
+        bevy::input::InputPlugin,
+        bevy::gizmos::GizmoPlugin,
+    ))
+    .init_asset::<Image>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(STEP))
+    .init_state::<GameState>()
+    // 下面这些平时由没加进来的插件（装备、输入、UI、复活点等）提供
+    .init_resource::<ItemDatabase>()
+    .init_resource::<InventoryConfig>()
+    .init_resource::<Notifications>()
+    .init_resource::<GameSettings>()
+    .init_resource::<Palette>()
+    .init_resource::<RunStats>()
+    .init_resource::<DifficultyScaling>()
+    .init_resource::<Checkpoint>()
+    .init_resource::<ReviveCharges>()
+    .init_resource::<MovementInput>()
+    .init_resource::<KeyBindings>()
+    .init_resource::<LockedTarget>()
+    .init_resource::<WallColliders>()
+    // 存档相关系统只在这个临时目录里读写，不碰玩家的真实存档
+    .insert_resource(SaveDir(std::env::temp_dir().join("oplus_test_saves")))
+    .add_message::<TransitionRequest>()
+    .add_message::<PlaySfx>()
+    .add_plugins((BalancePlugin, HealthPlugin, CombatCorePlugin, CombatPlugin, EnemyPlugin, ElitePlugin, SavePlugin));
+
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::InGame);
+    app.update();
+    app
+}
+
+/// 在 pos 放一个玩家：只带逻辑需要的组件，没有贴图
+pub fn spawn_player(app: &mut App, pos: Vec2) -> Entity {
+    app.world_mut()
+        .spawn((
+            Player,
+            Transform::from_translation(pos.extend(0.0)),
+            Health::new(100.0),
+            EquipmentSet::default(),
+        ))
+        .id()
+}
+
+/// 在 pos 放一个不会移动、没有贴图的敌人
+pub fn spawn_enemy(app: &mut App, pos: Vec2, hp: f32) -> Entity {
+    app.world_mut()
+        .spawn((Enemy, Transform::from_translation(pos.extend(0.0)), Health::new(hp)))
+        .id()
+}
+
+/// 连续推进 n 帧
+pub fn step(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    use crate::combat_core::{spawn_projectile, CollisionLayers, DamageDealt, EnemyDied, Projectile, ProjectilePool};
+
+    #[test]
+    fn projectile_damages_enemy_in_its_path() {
+        let mut app = headless_app();
+        spawn_player(&mut app, Vec2::new(-500.0, 0.0));
+        let enemy = spawn_enemy(&mut app, Vec2::new(60.0, 0.0), 100.0);
+
+        app.world_mut()
+            .run_system_once(|mut commands: Commands| {
+                spawn_projectile(&mut commands, None, Vec2::ZERO, Vec2::X, 600.0, 2.0, 10.0, CollisionLayers::player_projectile(false), None, None, 0.0);
+            })
+            .unwrap();
+        step(&mut app, 20);
+
+        let hp = app.world().get::<Health>(enemy).unwrap().current;
+        assert!(hp < 100.0, "敌人应该被子弹打中，当前血量 {hp}");
+    }
+
+    #[test]
+    fn interceptor_rounds_shoot_down_enemy_projectiles() {
+        let mut app = headless_app();
+        spawn_player(&mut app, Vec2::new(-500.0, 0.0));
+
+        app.world_mut()
+            .run_system_once(|mut commands: Commands| {
+                spawn_projectile(&mut commands, None, Vec2::ZERO, Vec2::X, 600.0, 2.0, 10.0, CollisionLayers::player_projectile(true), None, None, 0.0);
+                spawn_projectile(&mut commands, None, Vec2::new(80.0, 0.0), Vec2::NEG_X, 1.0, 2.0, 10.0, CollisionLayers::enemy_projectile(), None, None, 0.0);
+            })
+            .unwrap();
+        step(&mut app, 20);
+
+        let live = app.world_mut().query::<&Projectile>().iter(app.world()).count();
+        assert_eq!(live, 0, "两颗子弹应该互相抵消");
+    }
+
+    #[test]
+    fn pooled_projectiles_are_hidden_until_reused() {
+        let mut app = headless_app();
+        spawn_player(&mut app, Vec2::new(-500.0, 0.0));
+
+        let fire = |origin: Vec2| {
+            move |mut commands: Commands, mut pool: ResMut<ProjectilePool>| {
+                spawn_projectile(&mut commands, Some(&mut pool), origin, Vec2::Y, 100.0, 0.05, 1.0, CollisionLayers::player_projectile(false), None, None, 0.0);
+            }
+        };
+        app.world_mut().run_system_once(fire(Vec2::ZERO)).unwrap();
+        step(&mut app, 10);
+
+        let pooled = app.world().resource::<ProjectilePool>().free.clone();
+        assert_eq!(pooled.len(), 1, "过期的子弹应该回到池里");
+        let ent = pooled[0];
+        assert!(app.world().get::<Projectile>(ent).is_none());
+        assert_eq!(app.world().get::<Visibility>(ent), Some(&Visibility::Hidden));
+
+        let origin = Vec2::new(300.0, 40.0);
+        app.world_mut().run_system_once(fire(origin)).unwrap();
+        assert!(app.world().get::<Projectile>(ent).is_some(), "应该复用池里的实体");
+        assert_eq!(app.world().get::<Visibility>(ent), Some(&Visibility::Inherited));
+        assert_eq!(app.world().get::<Transform>(ent).unwrap().translation.truncate(), origin);
+    }
+
+    #[test]
+    fn combat_entities_survive_pause_but_not_leaving_the_run() {
+        let mut app = headless_app();
+        spawn_player(&mut app, Vec2::new(-500.0, 0.0));
+        let enemy = spawn_enemy(&mut app, Vec2::new(0.0, 300.0), 50.0);
+        app.world_mut()
+            .run_system_once(|mut commands: Commands| {
+                spawn_projectile(&mut commands, None, Vec2::ZERO, Vec2::X, 10.0, 5.0, 1.0, CollisionLayers::player_projectile(false), None, None, 0.0);
+            })
+            .unwrap();
+        step(&mut app, 1);
+
+        let go_to = |app: &mut App, state: GameState| {
+            app.world_mut().resource_mut::<NextState<GameState>>().set(state);
+            app.update();
+        };
+        go_to(&mut app, GameState::Paused);
+        go_to(&mut app, GameState::InGame);
+        assert!(app.world().get_entity(enemy).is_ok(), "暂停再继续不应该清场");
+        assert_eq!(app.world_mut().query::<&Projectile>().iter(app.world()).count(), 1);
+
+        go_to(&mut app, GameState::Paused);
+        go_to(&mut app, GameState::MainMenu);
+        assert!(app.world().get_entity(enemy).is_err());
+        assert_eq!(app.world_mut().query::<&Projectile>().iter(app.world()).count(), 0);
+    }
+
+    #[test]
+    fn lifesteal_comes_from_the_damage_source() {
+        let mut app = headless_app();
+        let player = spawn_player(&mut app, Vec2::new(-500.0, 0.0));
+        app.world_mut().get_mut::<Health>(player).unwrap().current = 50.0;
+        let a = spawn_enemy(&mut app, Vec2::new(0.0, 300.0), 100.0);
+        let b = spawn_enemy(&mut app, Vec2::new(0.0, -300.0), 100.0);
+
+        app.world_mut().write_message(DamageDealt { target: a, amount: 20.0, crit: false, lifesteal: 0.5 });
+        app.world_mut().write_message(DamageDealt { target: b, amount: 20.0, crit: false, lifesteal: 0.0 });
+        step(&mut app, 1);
+
+        let hp = app.world().get::<Health>(player).unwrap().current;
+        assert!((hp - 60.0).abs() < 1e-4, "只有带吸血的那一下回血，当前血量 {hp}");
+    }
+
+    #[test]
+    fn zero_hp_enemies_are_cleaned_up() {
+        let mut app = headless_app();
+        spawn_player(&mut app, Vec2::ZERO);
+        let dead = spawn_enemy(&mut app, Vec2::new(200.0, 0.0), 0.0);
+        let alive = spawn_enemy(&mut app, Vec2::new(-200.0, 0.0), 50.0);
+
+        step(&mut app, 1);
+
+        assert!(app.world().get_entity(dead).is_err());
+        assert!(app.world().get_entity(alive).is_ok());
+        let died = app.world().resource::<Messages<EnemyDied>>();
+        assert_eq!(died.iter_current_update_messages().filter(|m| m.entity == dead).count(), 1);
+    }
+}
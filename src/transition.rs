@@ -0,0 +1,111 @@
+// src/transition.rs
+//! 状态切换的黑屏过渡：发 `TransitionRequest` 后先把全屏黑幕淡入，完全变黑时才切换状态，再淡出。
+//! 计时用 Time<Real>，不受暂停、顿帧和慢动作影响；过渡期间再来的请求直接忽略，避免连点重复切换。
+//! 暂停 / 继续这类需要立即响应的切换仍然直接设置 `NextState`。
+
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::state::GameState;
+
+/// 淡入、淡出各自的时长（秒）
+const FADE_SECS: f32 = 0.25;
+
+/// 请求带过渡地切换到某个状态
+#[derive(Message, Clone, Debug)]
+pub struct TransitionRequest(pub GameState);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TransitionPhase {
+    /// 黑幕逐渐盖上，结束时切换状态
+    FadeOut,
+    /// 新状态已生效，黑幕逐渐揭开
+    FadeIn,
+}
+
+/// 正在进行的过渡；没有过渡时不存在这个资源
+#[derive(Resource, Debug)]
+pub struct TransitionState {
+    pub to: GameState,
+    pub phase: TransitionPhase,
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+struct TransitionOverlay;
+
+pub struct TransitionPlugin;
+
+impl Plugin for TransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<TransitionRequest>()
+            .add_systems(Update, (begin_transition, update_transition).chain());
+    }
+}
+
+fn begin_transition(
+    mut commands: Commands,
+    mut requests: MessageReader<TransitionRequest>,
+    active: Option<Res<TransitionState>>,
+) {
+    let Some(TransitionRequest(to)) = requests.read().next().cloned() else { return; };
+    if active.is_some() {
+        return;
+    }
+
+    commands.insert_resource(TransitionState {
+        to,
+        phase: TransitionPhase::FadeOut,
+        timer: Timer::from_seconds(FADE_SECS, TimerMode::Once),
+    });
+    // 盖在所有 UI 之上，过渡期间挡住点击
+    commands.spawn((
+        TransitionOverlay,
+        GlobalZIndex(1000),
+        FocusPolicy::Block,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
+    ));
+}
+
+fn update_transition(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    transition: Option<ResMut<TransitionState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut overlay_q: Query<(Entity, &mut BackgroundColor), With<TransitionOverlay>>,
+) {
+    let Some(mut transition) = transition else { return; };
+    transition.timer.tick(time.delta());
+
+    let alpha = match transition.phase {
+        TransitionPhase::FadeOut => transition.timer.fraction(),
+        TransitionPhase::FadeIn => transition.timer.fraction_remaining(),
+    };
+    for (_, mut bg) in &mut overlay_q {
+        bg.0.set_alpha(alpha);
+    }
+
+    if !transition.timer.is_finished() {
+        return;
+    }
+
+    match transition.phase {
+        TransitionPhase::FadeOut => {
+            next_state.set(transition.to.clone());
+            transition.phase = TransitionPhase::FadeIn;
+            transition.timer.reset();
+        }
+        TransitionPhase::FadeIn => {
+            commands.remove_resource::<TransitionState>();
+            for (entity, _) in &overlay_q {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+// src/tutorial.rs
+//! 新手操作说明：第一次进入游戏时弹出按键列表（按当前键位显示），期间暂停模拟。
+//! 点 "知道了" 或按回车 / 空格关闭，并在设置里记下 `seen_tutorial`（随 settings.json 保存）；
+//! 设置面板的 "新手提示" 可以把它重新打开。打开暂停菜单时面板先收起，回到游戏后再弹出。
+
+use bevy::prelude::*;
+
+use crate::i18n::{localized, tr, Language};
+use crate::input::{key_label, InputAction, KeyBindings};
+use crate::state::GameState;
+use crate::ui::types::GameSettings;
+use crate::utils::despawn_with_children;
+
+#[derive(Component)]
+struct TutorialRoot;
+
+#[derive(Component)]
+struct TutorialDismissButton;
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::InGame), show_tutorial_if_unseen)
+            .add_systems(OnExit(GameState::InGame), hide_tutorial)
+            .add_systems(Update, dismiss_tutorial.run_if(in_state(GameState::InGame)));
+    }
+}
+
+/// 操作说明的每一行：(动作名, 按键)
+fn tutorial_lines(lang: Language, bindings: &KeyBindings) -> Vec<(&'static str, String)> {
+    let keys = |actions: &[InputAction]| {
+        actions.iter().map(|a| key_label(bindings.get(*a))).collect::<Vec<_>>().join(" / ")
+    };
+    vec![
+        (
+            tr(lang, "tutorial.move"),
+            keys(&[InputAction::MoveUp, InputAction::MoveLeft, InputAction::MoveDown, InputAction::MoveRight]),
+        ),
+        (tr(lang, "action.sprint"), keys(&[InputAction::Sprint])),
+        (tr(lang, "action.dash"), keys(&[InputAction::Dash])),
+        (tr(lang, "tutorial.attack"), tr(lang, "tutorial.mouse_left").to_string()),
+        (tr(lang, "tutorial.secondary"), tr(lang, "tutorial.mouse_right").to_string()),
+        (tr(lang, "tutorial.skills"), keys(&[InputAction::Skill1, InputAction::Skill2, InputAction::Skill3])),
+        (tr(lang, "action.interact"), keys(&[InputAction::Interact])),
+        (tr(lang, "action.toggle_equipment"), keys(&[InputAction::ToggleEquipment])),
+        (tr(lang, "action.cycle_target"), keys(&[InputAction::CycleTarget])),
+        (tr(lang, "tutorial.pause"), "Esc".to_string()),
+    ]
+}
+
+fn show_tutorial_if_unseen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    bindings: Res<KeyBindings>,
+    existing: Query<(), With<TutorialRoot>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if settings.seen_tutorial || !existing.is_empty() {
+        return;
+    }
+    virtual_time.pause();
+
+    let font: Handle<Font> = asset_server.load("fonts/YuFanLixing.otf");
+    let lang = settings.language;
+    let text_font = |size: f32| TextFont { font: font.clone(), font_size: size, ..default() };
+
+    commands
+        .spawn((
+            TutorialRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            GlobalZIndex(5),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(480.0),
+                    padding: UiRect::all(Val::Px(26.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(8.0),
+                    align_items: AlignItems::Stretch,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.12, 0.12, 0.16, 0.95)),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    localized(lang, "tutorial.title"),
+                    text_font(32.0),
+                    TextColor(Color::WHITE),
+                    Node { align_self: AlignSelf::Center, margin: UiRect::bottom(Val::Px(8.0)), ..default() },
+                ));
+
+                for (action, keys) in tutorial_lines(lang, &bindings) {
+                    panel
+                        .spawn(Node { justify_content: JustifyContent::SpaceBetween, ..default() })
+                        .with_children(|row| {
+                            row.spawn((Text::new(action), text_font(18.0), TextColor(Color::srgba(1.0, 1.0, 1.0, 0.85))));
+                            row.spawn((Text::new(keys), text_font(18.0), TextColor(Color::srgb(1.0, 0.85, 0.4))));
+                        });
+                }
+
+                panel
+                    .spawn((
+                        Button,
+                        TutorialDismissButton,
+                        Node {
+                            width: Val::Px(260.0),
+                            height: Val::Px(46.0),
+                            align_self: AlignSelf::Center,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::top(Val::Px(12.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.20, 0.20, 0.40)),
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((localized(lang, "tutorial.dismiss"), text_font(20.0), TextColor(Color::WHITE)));
+                    });
+            });
+        });
+}
+
+/// 点按钮或按回车 / 空格关闭，并记下已看过
+#[allow(clippy::type_complexity)]
+fn dismiss_tutorial(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut interactions: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<TutorialDismissButton>)>,
+    roots: Query<Entity, With<TutorialRoot>>,
+    children_q: Query<&Children>,
+    mut settings: ResMut<GameSettings>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if roots.is_empty() {
+        return;
+    }
+
+    let mut dismissed = keyboard.any_just_pressed([KeyCode::Enter, KeyCode::Space]);
+    for (interaction, mut bg) in &mut interactions {
+        match *interaction {
+            Interaction::Pressed => {
+                bg.0 = Color::srgb(0.8, 0.8, 1.0);
+                dismissed = true;
+            }
+            Interaction::Hovered => bg.0 = Color::srgb(0.6, 0.6, 0.8),
+            Interaction::None => bg.0 = Color::srgb(0.20, 0.20, 0.40),
+        }
+    }
+    if !dismissed {
+        return;
+    }
+
+    settings.seen_tutorial = true;
+    virtual_time.unpause();
+    for root in &roots {
+        despawn_with_children(&mut commands, &children_q, root);
+    }
+}
+
+/// 离开 InGame（暂停、回标题）时先收起并恢复模拟，没看完的下次进入 InGame 再弹出；
+/// 进入暂停时暂停菜单会自己再冻结 Time<Virtual>
+fn hide_tutorial(
+    mut commands: Commands,
+    roots: Query<Entity, With<TutorialRoot>>,
+    children_q: Query<&Children>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if roots.is_empty() {
+        return;
+    }
+    virtual_time.unpause();
+    for root in &roots {
+        despawn_with_children(&mut commands, &children_q, root);
+    }
+}
@@ -0,0 +1,288 @@
+use bevy::prelude::*;
+use bevy::ui::{UiRect, Val};
+
+use crate::i18n::{localized, tr, Language};
+use crate::input::{key_label, InputAction, KeyBindings};
+use crate::notifications::Notifications;
+use crate::ui::types::GameSettings;
+use crate::utils::despawn_with_children;
+
+#[derive(Resource)]
+pub(super) struct KeyBindingsOpenRequest;
+
+#[derive(Component)]
+pub(super) struct KeyBindingsUiRoot;
+
+/// 显示某个操作当前按键的文本
+#[derive(Component)]
+pub(super) struct KeyBindingValue(InputAction);
+
+#[derive(Component, Clone, Copy)]
+pub(super) enum KeyBindingButton {
+    Rebind(InputAction),
+    Reset,
+    Close,
+}
+
+/// 正在等待玩家按键的操作（“请按下按键...”模式）
+#[derive(Resource, Default)]
+pub(super) struct RebindCapture(Option<InputAction>);
+
+const BUTTON_COLOR: Color = Color::srgb(0.25, 0.25, 0.35);
+
+pub(super) fn open_keybindings_panel(commands: &mut Commands) {
+    commands.insert_resource(KeyBindingsOpenRequest);
+}
+
+pub(super) fn spawn_keybindings_panel_if_requested(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    request: Option<Res<KeyBindingsOpenRequest>>,
+    existing: Query<Entity, With<KeyBindingsUiRoot>>,
+    bindings: Res<KeyBindings>,
+    settings: Res<GameSettings>,
+) {
+    if request.is_none() {
+        return;
+    }
+
+    commands.remove_resource::<KeyBindingsOpenRequest>();
+
+    if !existing.is_empty() {
+        return;
+    }
+
+    let font: Handle<Font> = asset_server.load("fonts/YuFanLixing.otf");
+    let lang = settings.language;
+
+    commands
+        .spawn((
+            KeyBindingsUiRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            GlobalZIndex(10),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Percent(90.0),
+                    max_width: Val::Px(620.0),
+                    max_height: Val::Percent(90.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Stretch,
+                    padding: UiRect::all(Val::Px(24.0)),
+                    row_gap: Val::Px(8.0),
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.12, 0.12, 0.16, 0.96)),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    localized(lang, "keys.title"),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 32.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+
+                for action in InputAction::ALL {
+                    panel
+                        .spawn(Node {
+                            width: Val::Percent(100.0),
+                            flex_direction: FlexDirection::Row,
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        })
+                        .with_children(|row| {
+                            row.spawn((
+                                Node {
+                                    width: Val::Px(200.0),
+                                    ..default()
+                                },
+                                localized(lang, action.label_key()),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 20.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+
+                            row.spawn((
+                                Text::new(key_label(bindings.get(action))),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 20.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(0.9, 0.85, 0.5)),
+                                KeyBindingValue(action),
+                            ));
+
+                            spawn_button(row, &font, lang, "keys.rebind", KeyBindingButton::Rebind(action));
+                        });
+                }
+
+                panel
+                    .spawn(Node {
+                        width: Val::Percent(100.0),
+                        justify_content: JustifyContent::Center,
+                        column_gap: Val::Px(14.0),
+                        padding: UiRect::top(Val::Px(12.0)),
+                        ..default()
+                    })
+                    .with_children(|buttons| {
+                        spawn_button(buttons, &font, lang, "keys.reset", KeyBindingButton::Reset);
+                        spawn_button(buttons, &font, lang, "common.back", KeyBindingButton::Close);
+                    });
+            });
+        });
+}
+
+pub(super) fn handle_keybinding_buttons(
+    mut interactions: Query<(&Interaction, &mut BackgroundColor, &KeyBindingButton), Changed<Interaction>>,
+    mut bindings: ResMut<KeyBindings>,
+    mut capture: ResMut<RebindCapture>,
+    root_q: Query<Entity, With<KeyBindingsUiRoot>>,
+    children_q: Query<&Children>,
+    mut commands: Commands,
+) {
+    for (interaction, mut bg, button) in &mut interactions {
+        match *interaction {
+            Interaction::Pressed => {
+                bg.0 = Color::srgb(0.85, 0.85, 0.95);
+
+                match *button {
+                    KeyBindingButton::Rebind(action) => capture.0 = Some(action),
+                    KeyBindingButton::Reset => {
+                        *bindings = KeyBindings::default();
+                        capture.0 = None;
+                    }
+                    KeyBindingButton::Close => {
+                        capture.0 = None;
+                        close_keybindings_ui(&mut commands, &root_q, &children_q);
+                    }
+                }
+            }
+            Interaction::Hovered => bg.0 = Color::srgb(0.55, 0.55, 0.7),
+            Interaction::None => bg.0 = BUTTON_COLOR,
+        }
+    }
+}
+
+/// 捕获模式下，把下一次按下的键绑定到对应操作；Esc 取消
+pub(super) fn capture_rebind_key(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut capture: ResMut<RebindCapture>,
+    mut bindings: ResMut<KeyBindings>,
+    mut notifications: ResMut<Notifications>,
+    settings: Res<GameSettings>,
+) {
+    let Some(action) = capture.0 else { return; };
+    let Some(key) = keyboard.get_just_pressed().next().copied() else { return; };
+
+    // 消耗掉这次按键，避免同一帧里又触发关闭面板等逻辑
+    keyboard.clear_just_pressed(key);
+    capture.0 = None;
+
+    if key == KeyCode::Escape {
+        return;
+    }
+
+    if let Some(swapped) = bindings.rebind(action, key) {
+        let lang = settings.language;
+        notifications.notify(format!(
+            "{}{} ⇄ {}",
+            tr(lang, "keys.conflict"),
+            tr(lang, action.label_key()),
+            tr(lang, swapped.label_key())
+        ));
+    }
+}
+
+pub(super) fn sync_keybinding_texts(
+    bindings: Res<KeyBindings>,
+    capture: Res<RebindCapture>,
+    settings: Res<GameSettings>,
+    mut q: Query<(&mut Text, &KeyBindingValue)>,
+) {
+    if !bindings.is_changed() && !capture.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    for (mut text, value) in &mut q {
+        text.0 = if capture.0 == Some(value.0) {
+            tr(settings.language, "keys.press_key").to_string()
+        } else {
+            key_label(bindings.get(value.0))
+        };
+    }
+}
+
+/// 键位面板打开时 Esc 只关闭键位面板，不影响下面的设置面板
+pub(super) fn close_keybindings_on_esc(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    root_q: Query<Entity, With<KeyBindingsUiRoot>>,
+    children_q: Query<&Children>,
+    mut commands: Commands,
+) {
+    if root_q.is_empty() || !keyboard.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    keyboard.clear_just_pressed(KeyCode::Escape);
+    close_keybindings_ui(&mut commands, &root_q, &children_q);
+}
+
+fn close_keybindings_ui(
+    commands: &mut Commands,
+    root_q: &Query<Entity, With<KeyBindingsUiRoot>>,
+    children_q: &Query<&Children>,
+) {
+    if let Ok(root) = root_q.single() {
+        despawn_with_children(commands, children_q, root);
+    }
+}
+
+fn spawn_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    font: &Handle<Font>,
+    lang: Language,
+    key: &'static str,
+    button: KeyBindingButton,
+) {
+    parent
+        .spawn((
+            Button,
+            button,
+            Node {
+                width: Val::Px(130.0),
+                height: Val::Px(36.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR),
+        ))
+        .with_children(|b| {
+            b.spawn((
+                localized(lang, key),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
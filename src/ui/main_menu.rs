@@ -1,7 +1,14 @@
 use bevy::prelude::*;
 use bevy::ui::Val;
 
+use crate::i18n::localized;
+use crate::layers;
+use crate::new_run::{start_new_run, NewRunPending};
+use crate::save::{LoadSlotEvent, SaveSlots};
 use crate::state::GameState;
+use crate::transition::TransitionRequest;
+use crate::ui::menu_focus::MenuActivated;
+use crate::ui::types::GameSettings;
 
 #[derive(Component)]
 pub struct MainMenuUI;
@@ -11,19 +18,32 @@ pub struct MainMenuBackground;
 
 #[derive(Component, Clone, Copy)]
 pub enum MainMenuAction {
+    /// 读取最近修改的存档（手动或自动）直接进入游戏
+    Continue,
     Start,
     Save,
+    /// 局外强化
+    Upgrades,
     Settings,
     Exit,
+    /// 暂停菜单专用：重新读取当前存档，见 `save::reload_target`
+    ReloadSave,
 }
 
-pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn spawn_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    slots: Res<SaveSlots>,
+) {
     let font = asset_server.load("fonts/YuFanLixing.otf");
+    let lang = settings.language;
+    let has_save = !slots.slots.is_empty();
 
     let bg_handle: Handle<Image> = asset_server.load("main_background.png");
     let mut bg_sprite = Sprite::from_image(bg_handle);
     bg_sprite.custom_size = Some(Vec2::new(1920.0, 1080.0));
-    commands.spawn((MainMenuBackground, bg_sprite, Transform::from_xyz(0.0, 0.0, -100.0)));
+    commands.spawn((MainMenuBackground, bg_sprite, Transform::from_xyz(0.0, 0.0, layers::BACKGROUND)));
 
     commands
         .spawn((
@@ -40,6 +60,34 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
             BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
         ))
         .with_children(|parent| {
+            // Continue：没有存档时置灰，也不挂 Button（不响应点击和键盘焦点）
+            let mut continue_btn = parent.spawn((
+                Node {
+                    width: Val::Px(200.0),
+                    height: Val::Px(50.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                MainMenuAction::Continue,
+            ));
+            if has_save {
+                continue_btn.insert((Button, BackgroundColor(Color::srgb(0.3, 0.6, 0.7))));
+            } else {
+                continue_btn.insert(BackgroundColor(Color::srgb(0.18, 0.18, 0.22)));
+            }
+            continue_btn.with_children(|button| {
+                button.spawn((
+                    localized(lang, "menu.continue"),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 28.0,
+                        ..default()
+                    },
+                    TextColor(if has_save { Color::WHITE } else { Color::srgba(1.0, 1.0, 1.0, 0.35) }),
+                ));
+            });
+
             // Start
             parent
                 .spawn((
@@ -56,7 +104,7 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                 ))
                 .with_children(|button| {
                     button.spawn((
-                        Text::new("开始游戏".to_string()),
+                        localized(lang, "menu.start"),
                         TextFont {
                             font: font.clone(),
                             font_size: 28.0,
@@ -82,7 +130,33 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                 ))
                 .with_children(|button| {
                     button.spawn((
-                        Text::new("存档".to_string()),
+                        localized(lang, "menu.saves"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
+            // Upgrades
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(200.0),
+                        height: Val::Px(50.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.6, 0.7)),
+                    MainMenuAction::Upgrades,
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        localized(lang, "menu.upgrades"),
                         TextFont {
                             font: font.clone(),
                             font_size: 28.0,
@@ -108,7 +182,7 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                 ))
                 .with_children(|button| {
                     button.spawn((
-                        Text::new("设置".to_string()),
+                        localized(lang, "menu.settings"),
                         TextFont {
                             font: font.clone(),
                             font_size: 28.0,
@@ -134,7 +208,7 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                 ))
                 .with_children(|button| {
                     button.spawn((
-                        Text::new("退出".to_string()),
+                        localized(lang, "menu.quit"),
                         TextFont {
                             font,
                             font_size: 28.0,
@@ -159,32 +233,26 @@ pub fn cleanup_main_menu(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handle_main_menu_buttons(
     mut interactions: Query<(&Interaction, &mut BackgroundColor, &MainMenuAction), Changed<Interaction>>,
-    mut next_state: ResMut<NextState<GameState>>,
+    mut activated_rx: MessageReader<MenuActivated>,
+    actions_q: Query<&MainMenuAction>,
+    mut transition_tx: MessageWriter<TransitionRequest>,
     mut exit_writer: MessageWriter<AppExit>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    slots: Res<SaveSlots>,
+    mut load_tx: MessageWriter<LoadSlotEvent>,
+    mut new_run: ResMut<NewRunPending>,
 ) {
+    let mut pressed = Vec::new();
     for (interaction, mut bg, action) in &mut interactions {
         match *interaction {
             Interaction::Pressed => {
                 bg.0 = Color::srgb(0.8, 0.8, 1.0);
-                match action {
-                    MainMenuAction::Start => {
-                        next_state.set(GameState::InGame);
-                    }
-                    MainMenuAction::Save => {
-                        crate::ui::save::open_save_panel(&mut commands, &asset_server);
-                    }
-                    MainMenuAction::Settings => {
-                        crate::ui::settings::open_settings_panel(&mut commands);
-                    }
-                    MainMenuAction::Exit => {
-                        // 发送退出消息
-                        exit_writer.write(AppExit::Success);
-                    }
-                }
+                pressed.push(*action);
             }
             Interaction::Hovered => {
                 bg.0 = Color::srgb(0.6, 0.6, 0.8);
@@ -194,4 +262,38 @@ pub fn handle_main_menu_buttons(
             }
         }
     }
+    // 键盘 / 手柄确认的按钮
+    pressed.extend(activated_rx.read().filter_map(|ev| actions_q.get(ev.0).ok().copied()));
+
+    for action in pressed {
+        match action {
+            MainMenuAction::Continue => {
+                // SaveSlots 已按修改时间从新到旧排好
+                if let Some(latest) = slots.slots.first() {
+                    new_run.0 = false;
+                    load_tx.write(LoadSlotEvent { file_name: latest.file_name.clone() });
+                    transition_tx.write(TransitionRequest(GameState::InGame));
+                }
+            }
+            MainMenuAction::Start => {
+                // 进入 InGame 时清掉读档目标和本局状态，见 new_run
+                start_new_run(&mut new_run, &mut transition_tx);
+            }
+            MainMenuAction::Save => {
+                crate::ui::save::open_save_panel(&mut commands, &asset_server, settings.language);
+            }
+            MainMenuAction::Upgrades => {
+                crate::ui::upgrades::open_upgrades_panel(&mut commands, &asset_server, settings.language);
+            }
+            MainMenuAction::Settings => {
+                crate::ui::settings::open_settings_panel(&mut commands);
+            }
+            // 主菜单没有“重新读档”按钮
+            MainMenuAction::ReloadSave => {}
+            MainMenuAction::Exit => {
+                // 发送退出消息
+                exit_writer.write(AppExit::Success);
+            }
+        }
+    }
 }
\ No newline at end of file
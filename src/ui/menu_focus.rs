@@ -0,0 +1,171 @@
+//! 菜单的键盘 / 手柄导航：上下键（或移动键、十字键）移动焦点，Enter / 手柄 A 键确认。
+//! 焦点按菜单分别记在 `MenuFocus` 里；确认时发 `MenuActivated`，由各菜单原有的按钮处理系统执行动作。
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::input::KeyBindings;
+use crate::ui::keybindings::KeyBindingsUiRoot;
+use crate::ui::main_menu::{MainMenuAction, MainMenuUI};
+use crate::ui::pause_menu::PauseMenuUI;
+use crate::ui::save::SavePanel;
+use crate::ui::settings::SettingsUiRoot;
+
+/// 焦点按钮的底色（鼠标悬停 / 按下时仍由按钮自己的交互颜色决定）
+const FOCUS_COLOR: Color = Color::srgb(0.45, 0.45, 0.70);
+/// 与按钮 `Interaction::None` 时的颜色一致
+const IDLE_COLOR: Color = Color::srgb(0.25, 0.25, 0.35);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MenuId {
+    Main,
+    Pause,
+}
+
+/// 每个菜单当前聚焦的按钮序号
+#[derive(Resource, Default)]
+pub struct MenuFocus {
+    indices: HashMap<MenuId, usize>,
+    /// 当前被高亮的按钮，焦点移走时把它的底色还原
+    highlighted: Option<Entity>,
+}
+
+impl MenuFocus {
+    pub fn index(&self, menu: MenuId) -> usize {
+        self.indices.get(&menu).copied().unwrap_or(0)
+    }
+}
+
+/// 键盘 / 手柄确认了某个菜单按钮
+#[derive(Message)]
+pub struct MenuActivated(pub Entity);
+
+#[allow(clippy::collapsible_if, clippy::too_many_arguments, clippy::type_complexity)]
+pub(super) fn navigate_menu_focus(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    gamepads: Query<&Gamepad>,
+    mut focus: ResMut<MenuFocus>,
+    mut activated_tx: MessageWriter<MenuActivated>,
+    main_q: Query<&Children, With<MainMenuUI>>,
+    pause_q: Query<&Children, With<PauseMenuUI>>,
+    mut buttons_q: Query<(&Interaction, &mut BackgroundColor), With<MainMenuAction>>,
+    overlays_q: Query<(), Or<(With<SettingsUiRoot>, With<SavePanel>, With<KeyBindingsUiRoot>)>>,
+) {
+    let (menu, children) = if let Ok(children) = main_q.single() {
+        (MenuId::Main, children)
+    } else if let Ok(children) = pause_q.single() {
+        (MenuId::Pause, children)
+    } else {
+        focus.highlighted = None;
+        return;
+    };
+
+    // 设置 / 存档 / 键位面板盖在菜单上时不抢按键
+    if !overlays_q.is_empty() {
+        return;
+    }
+
+    let buttons: Vec<Entity> = children.iter().filter(|c| buttons_q.contains(*c)).collect();
+    if buttons.is_empty() {
+        return;
+    }
+
+    let pad_pressed = |button: GamepadButton| gamepads.iter().any(|pad| pad.just_pressed(button));
+    let up = keys.just_pressed(KeyCode::ArrowUp) || keys.just_pressed(bindings.move_up) || pad_pressed(GamepadButton::DPadUp);
+    let down =
+        keys.just_pressed(KeyCode::ArrowDown) || keys.just_pressed(bindings.move_down) || pad_pressed(GamepadButton::DPadDown);
+    let confirm = keys.just_pressed(KeyCode::Enter)
+        || keys.just_pressed(KeyCode::NumpadEnter)
+        || pad_pressed(GamepadButton::South);
+
+    let len = buttons.len();
+    let mut index = focus.index(menu).min(len - 1);
+    // 鼠标悬停的按钮接管焦点，之后用键盘从这里继续
+    if let Some(hovered) = buttons
+        .iter()
+        .position(|b| buttons_q.get(*b).is_ok_and(|(i, _)| *i == Interaction::Hovered))
+    {
+        index = hovered;
+    }
+    if up {
+        index = (index + len - 1) % len;
+    }
+    if down {
+        index = (index + 1) % len;
+    }
+    focus.indices.insert(menu, index);
+
+    let target = buttons[index];
+    if focus.highlighted != Some(target) {
+        if let Some(prev) = focus.highlighted {
+            if let Ok((interaction, mut bg)) = buttons_q.get_mut(prev) {
+                if *interaction == Interaction::None {
+                    bg.0 = IDLE_COLOR;
+                }
+            }
+        }
+        focus.highlighted = Some(target);
+    }
+    // 鼠标移开后按钮会被交互系统刷回灰色，这里每帧补回高亮
+    if let Ok((interaction, mut bg)) = buttons_q.get_mut(target) {
+        if *interaction == Interaction::None && bg.0 != FOCUS_COLOR {
+            bg.0 = FOCUS_COLOR;
+        }
+    }
+
+    if confirm {
+        activated_tx.write(MenuActivated(target));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::keyboard::{Key, KeyboardInput};
+    use bevy::input::ButtonState;
+
+    use crate::new_run::NewRunPending;
+    use crate::state::GameState;
+    use crate::test_support::headless_app;
+    use crate::transition::TransitionRequest;
+    use crate::ui::main_menu::handle_main_menu_buttons;
+
+    #[test]
+    fn enter_on_focused_start_begins_exactly_one_new_run() {
+        let mut app = headless_app();
+        app.init_resource::<MenuFocus>()
+            .init_resource::<NewRunPending>()
+            .add_message::<MenuActivated>()
+            .add_systems(Update, navigate_menu_focus.after(handle_main_menu_buttons))
+            .add_systems(Update, handle_main_menu_buttons);
+        app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::MainMenu);
+        app.update();
+
+        let button = |action: MainMenuAction| (Interaction::None, BackgroundColor(IDLE_COLOR), action);
+        app.world_mut()
+            .spawn(MainMenuUI)
+            .with_children(|menu| {
+                menu.spawn(button(MainMenuAction::Start));
+                menu.spawn(button(MainMenuAction::Exit));
+            });
+        app.update();
+
+        app.world_mut().write_message(KeyboardInput {
+            key_code: KeyCode::Enter,
+            logical_key: Key::Enter,
+            state: ButtonState::Pressed,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+        // 确认在这一帧发出，按钮处理系统下一帧才读到
+        app.update();
+        app.update();
+
+        assert!(app.world().resource::<NewRunPending>().0);
+        let requests = app.world().resource::<Messages<TransitionRequest>>();
+        assert_eq!(requests.len(), 1, "一次 Enter 只能开一局");
+    }
+}
@@ -1,8 +1,11 @@
 pub mod types;
+pub mod keybindings;
 pub mod main_menu;
+pub mod menu_focus;
 pub mod pause_menu;
 pub mod settings;
 pub mod save;
+pub mod upgrades;
 
 use bevy::prelude::*;
 
@@ -15,23 +18,57 @@ impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         // 初始化公共资源
         app.init_resource::<GameSettings>()
-            .init_resource::<SelectedSlot>();
+            .init_resource::<SelectedSlot>()
+            .init_resource::<keybindings::RebindCapture>()
+            .init_resource::<menu_focus::MenuFocus>()
+            .add_message::<menu_focus::MenuActivated>();
 
         // main menu
-        app.add_systems(OnEnter(crate::state::GameState::MainMenu), main_menu::spawn_main_menu)
-            .add_systems(OnExit(crate::state::GameState::MainMenu), main_menu::cleanup_main_menu)
+        // “继续”按钮要看存档列表，所以等存档扫描完再生成主菜单
+        app.add_systems(
+            OnEnter(crate::state::GameState::MainMenu),
+            main_menu::spawn_main_menu.after(crate::save::load_save_slots_from_disk),
+        )
+            .add_systems(
+                OnExit(crate::state::GameState::MainMenu),
+                (main_menu::cleanup_main_menu, upgrades::cleanup_upgrades_panel),
+            )
             .add_systems(
                 Update,
-                main_menu::handle_main_menu_buttons.run_if(in_state(crate::state::GameState::MainMenu)),
+                (
+                    main_menu::handle_main_menu_buttons,
+                    upgrades::handle_upgrade_buttons,
+                    upgrades::sync_upgrade_texts,
+                    upgrades::close_upgrades_on_esc,
+                )
+                    .chain()
+                    .run_if(in_state(crate::state::GameState::MainMenu)),
             );
 
         // pause menu
-        app.add_systems(OnEnter(crate::state::GameState::Paused), pause_menu::spawn_pause_menu)
-            .add_systems(OnExit(crate::state::GameState::Paused), pause_menu::cleanup_pause_menu)
-            .add_systems(
-                Update,
-                pause_menu::handle_pause_menu_buttons.run_if(in_state(crate::state::GameState::Paused)),
-            );
+        app.add_systems(
+            OnEnter(crate::state::GameState::Paused),
+            (pause_menu::spawn_pause_menu, pause_menu::freeze_virtual_time),
+        )
+        .add_systems(
+            OnExit(crate::state::GameState::Paused),
+            (pause_menu::cleanup_pause_menu, pause_menu::unfreeze_virtual_time),
+        )
+        .add_systems(
+            Update,
+            pause_menu::handle_pause_menu_buttons.run_if(in_state(crate::state::GameState::Paused)),
+        );
+
+        // 菜单键盘 / 手柄导航：放在按钮处理之后，交互颜色刷新完再补焦点高亮
+        app.add_systems(
+            Update,
+            menu_focus::navigate_menu_focus
+                .after(main_menu::handle_main_menu_buttons)
+                .after(pause_menu::handle_pause_menu_buttons)
+                .run_if(
+                    in_state(crate::state::GameState::MainMenu).or(in_state(crate::state::GameState::Paused)),
+                ),
+        );
 
         // settings
         app.add_systems(
@@ -40,11 +77,32 @@ impl Plugin for MenuPlugin {
                 settings::spawn_settings_panel_if_requested,
                 settings::handle_settings_buttons,
                 settings::sync_settings_texts,
+                settings::sync_game_speed_text,
+                keybindings::spawn_keybindings_panel_if_requested,
+                keybindings::handle_keybinding_buttons,
+                keybindings::capture_rebind_key,
+                keybindings::sync_keybinding_texts,
+                keybindings::close_keybindings_on_esc,
                 settings::close_settings_on_esc,
+                settings::persist_settings_on_change,
             )
-                .chain(),
+                .chain()
+                // 面板里按 Esc 只关面板：先于暂停切换把 Esc 吃掉
+                .before(crate::input::toggle_pause),
         );
+        app.add_systems(Startup, settings::load_settings_from_disk);
+        app.add_systems(Update, (settings::track_windowed_geometry, settings::apply_ui_scale));
+        app.add_systems(Last, settings::limit_frame_rate);
         
-        app.add_systems(Update, (save::sync_save_slots_list, save::handle_save_slot_buttons));
+        app.add_systems(
+            Update,
+            (
+                save::sync_save_slots_list,
+                save::scroll_save_slots_list,
+                save::handle_save_slot_buttons,
+                save::handle_activate_button,
+                save::close_save_panel_on_esc.before(crate::input::toggle_pause),
+            ),
+        );
     }
 }
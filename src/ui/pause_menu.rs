@@ -1,14 +1,22 @@
 use bevy::prelude::*;
 use bevy::ui::Val;
 
+use crate::i18n::{localized, tr};
+use crate::new_run::ReloadPending;
+use crate::notifications::Notifications;
+use crate::save::{reload_target, CurrentSlot, LoadSlotEvent, SaveSlots};
 use crate::state::GameState;
+use crate::transition::TransitionRequest;
 use crate::ui::main_menu::MainMenuAction;
+use crate::ui::menu_focus::MenuActivated;
+use crate::ui::types::GameSettings;
 
 #[derive(Component)]
 pub struct PauseMenuUI;
 
-pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>, settings: Res<GameSettings>) {
     let font = asset_server.load("fonts/YuFanLixing.otf");
+    let lang = settings.language;
 
     commands
         .spawn((
@@ -22,9 +30,24 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                 row_gap: Val::Px(16.0),
                 ..default()
             },
-            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            // 压暗冻结的游戏画面
+            BackgroundColor(Color::srgba(0.02, 0.02, 0.05, 0.7)),
         ))
         .with_children(|parent| {
+            parent.spawn((
+                localized(lang, "pause.title"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 44.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(1.0, 1.0, 1.0, 0.9)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(12.0)),
+                    ..default()
+                },
+            ));
+
             // Resume
             parent
                 .spawn((
@@ -41,7 +64,7 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                 ))
                 .with_children(|button| {
                     button.spawn((
-                        Text::new("继续游戏".to_string()),
+                        localized(lang, "pause.resume"),
                         TextFont {
                             font: font.clone(),
                             font_size: 28.0,
@@ -67,7 +90,33 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                 ))
                 .with_children(|button| {
                     button.spawn((
-                        Text::new("存档".to_string()),
+                        localized(lang, "menu.saves"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
+            // Reload Save：回到当前存档里的局面
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(200.0),
+                        height: Val::Px(50.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.6, 0.7)),
+                    MainMenuAction::ReloadSave,
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        localized(lang, "pause.reload"),
                         TextFont {
                             font: font.clone(),
                             font_size: 28.0,
@@ -93,7 +142,7 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                 ))
                 .with_children(|button| {
                     button.spawn((
-                        Text::new("设置".to_string()),
+                        localized(lang, "menu.settings"),
                         TextFont {
                             font: font.clone(),
                             font_size: 28.0,
@@ -119,7 +168,7 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                 ))
                 .with_children(|button| {
                     button.spawn((
-                        Text::new("返回主菜单".to_string()),
+                        localized(lang, "pause.main_menu"),
                         TextFont {
                             font,
                             font_size: 28.0,
@@ -131,12 +180,22 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
         });
 }
 
+/// 进入暂停时冻结虚拟时间，残留的计时器/特效也不会继续走
+pub fn freeze_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+pub fn unfreeze_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
 pub fn cleanup_pause_menu(mut commands: Commands, q: Query<Entity, With<PauseMenuUI>>) {
     if let Ok(e) = q.single() {
         commands.entity(e).try_despawn();
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handle_pause_menu_buttons(
     mut interactions: Query<
         (
@@ -146,33 +205,64 @@ pub fn handle_pause_menu_buttons(
         ),
         Changed<Interaction>,
     >,
+    mut activated_rx: MessageReader<MenuActivated>,
+    actions_q: Query<&MainMenuAction>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut transition_tx: MessageWriter<TransitionRequest>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    current: Res<CurrentSlot>,
+    slots: Res<SaveSlots>,
+    mut load_tx: MessageWriter<LoadSlotEvent>,
+    mut reload: ResMut<ReloadPending>,
+    mut notifications: ResMut<Notifications>,
 ) {
+    let mut pressed = Vec::new();
     for (interaction, mut bg, action) in &mut interactions {
         match *interaction {
             Interaction::Pressed => {
                 bg.0 = Color::srgb(0.8, 0.8, 1.0);
-                match action {
-                    crate::ui::main_menu::MainMenuAction::Start => {
-                        // Resume
+                pressed.push(*action);
+            }
+            Interaction::Hovered => bg.0 = Color::srgb(0.6, 0.6, 0.8),
+            Interaction::None => bg.0 = Color::srgb(0.25, 0.25, 0.35),
+        }
+    }
+    // 键盘 / 手柄确认的按钮
+    pressed.extend(activated_rx.read().filter_map(|ev| actions_q.get(ev.0).ok().copied()));
+
+    for action in pressed {
+        match action {
+            crate::ui::main_menu::MainMenuAction::Start => {
+                // Resume：只是解除暂停，不碰 PendingLoad / CurrentSlot，不会读档
+                next_state.set(GameState::InGame);
+            }
+            crate::ui::main_menu::MainMenuAction::ReloadSave => {
+                // 暂停中也会应用读档，回到游戏时已经是存档里的局面；本局资源和敌人在进入 InGame 时清掉，见 new_run
+                match reload_target(&current, &slots) {
+                    Some(file_name) => {
+                        let name = file_name.trim_end_matches(".json").to_string();
+                        notifications.notify(format!("{}{name}", tr(settings.language, "notify.reloaded")));
+                        reload.0 = true;
+                        load_tx.write(LoadSlotEvent { file_name });
                         next_state.set(GameState::InGame);
                     }
-                    crate::ui::main_menu::MainMenuAction::Save => {
-                        crate::ui::save::open_save_panel(&mut commands, &asset_server);
-                    }
-                    crate::ui::main_menu::MainMenuAction::Settings => {
-                        crate::ui::settings::open_settings_panel(&mut commands);
-                    }
-                    crate::ui::main_menu::MainMenuAction::Exit => {
-                        // Return to main menu
-                        next_state.set(GameState::MainMenu);
-                    }
+                    None => notifications.notify(tr(settings.language, "notify.no_save")),
                 }
             }
-            Interaction::Hovered => bg.0 = Color::srgb(0.6, 0.6, 0.8),
-            Interaction::None => bg.0 = Color::srgb(0.25, 0.25, 0.35),
+            crate::ui::main_menu::MainMenuAction::Save => {
+                crate::ui::save::open_save_panel(&mut commands, &asset_server, settings.language);
+            }
+            crate::ui::main_menu::MainMenuAction::Settings => {
+                crate::ui::settings::open_settings_panel(&mut commands);
+            }
+            // 暂停菜单没有“继续存档”和“强化”按钮
+            crate::ui::main_menu::MainMenuAction::Continue | crate::ui::main_menu::MainMenuAction::Upgrades => {}
+            crate::ui::main_menu::MainMenuAction::Exit => {
+                // Return to main menu
+                transition_tx.write(TransitionRequest(GameState::MainMenu));
+            }
         }
     }
 }
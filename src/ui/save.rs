@@ -1,8 +1,13 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
-use bevy::ui::Val;
+use bevy::ui::{RelativeCursorPosition, Val};
 
-use crate::save::{LoadSlotEvent, ManualSaveEvent, SaveSlots};
-use crate::ui::types::SelectedSlot;
+use crate::i18n::{localized, tr, Language};
+use crate::new_run::{NewRunPending, ReloadPending};
+use crate::save::{LoadSlotEvent, ManualSaveEvent, SaveDir, SaveSlots};
+use crate::state::GameState;
+use crate::transition::TransitionRequest;
+use crate::ui::types::{GameSettings, SelectedSlot};
 use crate::utils::despawn_with_children;
 
 #[derive(Component)]
@@ -29,7 +34,10 @@ pub enum SaveSlotAction {
     Select, // 选择某个存档（不直接加载）
 }
 
-pub fn open_save_panel(commands: &mut Commands, asset_server: &AssetServer) {
+/// 滚轮一格对应的滚动距离（一行存档高度加间距）
+const SCROLL_LINE_PX: f32 = 46.0;
+
+pub fn open_save_panel(commands: &mut Commands, asset_server: &AssetServer, lang: Language) {
     let font = asset_server.load("fonts/YuFanLixing.otf");
 
     // 用 Overlay 作为唯一根节点，面板作为它的子节点（便于递归销毁）
@@ -69,7 +77,7 @@ pub fn open_save_panel(commands: &mut Commands, asset_server: &AssetServer) {
             .with_children(|panel| {
                 // 标题
                 panel.spawn((
-                    Text::new("存档"),
+                    localized(lang, "save.title"),
                     TextFont {
                         font: font.clone(),
                         font_size: 30.0,
@@ -81,6 +89,7 @@ pub fn open_save_panel(commands: &mut Commands, asset_server: &AssetServer) {
                 // 存档列表（滚动）
                 panel.spawn((
                     SaveSlotsList,
+                    RelativeCursorPosition::default(),
                     Node {
                         width: Val::Percent(100.0),
                         height: Val::Percent(65.0),
@@ -121,7 +130,7 @@ pub fn open_save_panel(commands: &mut Commands, asset_server: &AssetServer) {
                     ))
                     .with_children(|btn| {
                         btn.spawn((
-                            Text::new("手动保存"),
+                            localized(lang, "save.manual_save"),
                             TextFont {
                                 font: font.clone(),
                                 font_size: 20.0,
@@ -146,7 +155,7 @@ pub fn open_save_panel(commands: &mut Commands, asset_server: &AssetServer) {
                     ))
                     .with_children(|btn| {
                         btn.spawn((
-                            Text::new("载入选中存档"),
+                            localized(lang, "save.load_selected"),
                             TextFont {
                                 font,
                                 font_size: 20.0,
@@ -161,34 +170,29 @@ pub fn open_save_panel(commands: &mut Commands, asset_server: &AssetServer) {
 }
 
 /// 列表同步：
-/// - 面板打开时 / 面板存在时定期刷新（解决“保存后不更新”）
+/// - 面板打开时从磁盘刷新一次；之后保存会直接更新 SaveSlots，列表随之重建，不再定时扫盘
 /// - 只清空 list 的子节点，不要 despawn list 本体（否则就会出现你日志里的 ChildOf 无效关系）:contentReference[oaicite:4]{index=4}
+#[allow(clippy::too_many_arguments)]
 pub fn sync_save_slots_list(
     mut commands: Commands,
-    time: Res<Time>,
-    mut refresh_timer: Local<Option<Timer>>,
     panels_added: Query<Entity, Added<SavePanel>>,
     list_q: Query<Entity, With<SaveSlotsList>>,
     children_q: Query<&Children>,
     asset_server: Res<AssetServer>,
     mut slots: ResMut<SaveSlots>,
+    save_dir: Res<SaveDir>,
     selected: Res<SelectedSlot>,
+    settings: Res<GameSettings>,
 ) {
     let Some(list_e) = list_q.iter().next() else { return };
 
-    // 初始化定时器：面板打开后 0.5s 刷一次磁盘，保证“保存后立刻可见”
-    let timer = refresh_timer.get_or_insert_with(|| Timer::from_seconds(0.5, TimerMode::Repeating));
-    timer.tick(time.delta());
-
     let just_opened = !panels_added.is_empty();
-    let should_refresh_disk = just_opened || timer.just_finished();
-
-    if should_refresh_disk {
-        crate::save::refresh_save_slots_from_disk(&mut slots);
+    if just_opened {
+        crate::save::refresh_save_slots_from_disk(&mut slots, &save_dir);
     }
 
     // 只有在“内容可能变化”时重建
-    if !(just_opened || should_refresh_disk || slots.is_changed() || selected.is_changed()) {
+    if !(just_opened || slots.is_changed() || selected.is_changed() || settings.is_changed()) {
         return;
     }
 
@@ -203,11 +207,12 @@ pub fn sync_save_slots_list(
     // 2) 重新生成列表
     let font = asset_server.load("fonts/YuFanLixing.otf");
     let cur = selected.0.clone();
+    let lang = settings.language;
 
     commands.entity(list_e).with_children(|parent| {
         if slots.slots.is_empty() {
             parent.spawn((
-                Text::new("暂无存档（请先手动保存一次）"),
+                Text::new(tr(lang, "save.empty")),
                 TextFont {
                     font: font.clone(),
                     font_size: 18.0,
@@ -222,7 +227,7 @@ pub fn sync_save_slots_list(
             let is_selected = cur.as_deref() == Some(meta.file_name.as_str());
 
             let label = if meta.is_auto {
-                format!("{}  (自动)", meta.display_name)
+                format!("{}  {}", meta.display_name, tr(lang, "save.auto_suffix"))
             } else {
                 meta.display_name.clone()
             };
@@ -234,7 +239,7 @@ pub fn sync_save_slots_list(
                         width: Val::Percent(100.0),
                         height: Val::Px(40.0),
                         padding: UiRect::horizontal(Val::Px(10.0)),
-                        justify_content: JustifyContent::FlexStart,
+                        justify_content: JustifyContent::SpaceBetween,
                         align_items: AlignItems::Center,
                         ..default()
                     },
@@ -258,11 +263,43 @@ pub fn sync_save_slots_list(
                         },
                         TextColor(Color::WHITE),
                     ));
+                    row.spawn((
+                        Text::new(meta.created_at.clone()),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.75)),
+                    ));
                 });
         }
     });
 }
 
+/// 鼠标悬停在存档列表上时用滚轮滚动（超出范围由布局自动夹住）
+pub fn scroll_save_slots_list(
+    mut wheel_rx: MessageReader<MouseWheel>,
+    mut list_q: Query<(&mut ScrollPosition, &RelativeCursorPosition), With<SaveSlotsList>>,
+) {
+    let dy: f32 = wheel_rx
+        .read()
+        .map(|ev| match ev.unit {
+            MouseScrollUnit::Line => ev.y * SCROLL_LINE_PX,
+            MouseScrollUnit::Pixel => ev.y,
+        })
+        .sum();
+    if dy == 0.0 {
+        return;
+    }
+
+    for (mut scroll, cursor) in &mut list_q {
+        if cursor.cursor_over() {
+            scroll.y = (scroll.y - dy).max(0.0);
+        }
+    }
+}
+
 pub fn handle_save_slot_buttons(
     mut interactions: Query<(&Interaction, &mut BackgroundColor, &SaveSlotButton), Changed<Interaction>>,
     mut manual_save_tx: MessageWriter<ManualSaveEvent>,
@@ -279,10 +316,7 @@ pub fn handle_save_slot_buttons(
                 bg.0 = Color::srgb(0.8, 0.8, 1.0);
                 match btn.action {
                     SaveSlotAction::Save => {
-                        manual_save_tx.write(ManualSaveEvent {
-                            file_name: None,
-                            slot_index: None,
-                        });
+                        manual_save_tx.write(ManualSaveEvent { file_name: None });
                     }
                     SaveSlotAction::Select => {
                         selected_slot.0 = Some(btn.file_name.clone());
@@ -295,10 +329,17 @@ pub fn handle_save_slot_buttons(
     }
 }
 
+/// 加载选中的存档：主菜单里打开时直接进游戏，暂停中打开时回到游戏
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn handle_activate_button(
     mut interactions: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<ActivateButton>)>,
     selected_slot: Res<SelectedSlot>,
     mut load_tx: MessageWriter<LoadSlotEvent>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut transition_tx: MessageWriter<TransitionRequest>,
+    mut new_run: ResMut<NewRunPending>,
+    mut reload: ResMut<ReloadPending>,
     mut commands: Commands,
     q_overlay: Query<Entity, With<SavePanelOverlay>>,
     children_q: Query<&Children>,
@@ -308,8 +349,20 @@ pub fn handle_activate_button(
             Interaction::Pressed => {
                 bg.0 = Color::srgb(0.8, 0.8, 1.0);
 
-                if let Some(name) = selected_slot.0.clone() {
-                    load_tx.write(LoadSlotEvent { file_name: name });
+                let Some(name) = selected_slot.0.clone() else {
+                    continue;
+                };
+                load_tx.write(LoadSlotEvent { file_name: name });
+                match state.get() {
+                    GameState::MainMenu => {
+                        new_run.0 = false;
+                        transition_tx.write(TransitionRequest(GameState::InGame));
+                    }
+                    GameState::Paused => {
+                        reload.0 = true;
+                        next_state.set(GameState::InGame);
+                    }
+                    _ => {}
                 }
 
                 // 载入后关闭面板（递归删除，避免孤儿 UI）
@@ -323,15 +376,17 @@ pub fn handle_activate_button(
     }
 }
 
+/// 存档面板打开时 Esc 只关闭面板，不会顺带切换暂停
 pub fn close_save_panel_on_esc(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
     mut commands: Commands,
     q_overlay: Query<Entity, With<SavePanelOverlay>>,
     children_q: Query<&Children>,
 ) {
-    if !keyboard.just_pressed(KeyCode::Escape) {
+    if q_overlay.is_empty() || !keyboard.just_pressed(KeyCode::Escape) {
         return;
     }
+    keyboard.clear_just_pressed(KeyCode::Escape);
 
     // 递归删除 overlay（它是 UI 根）
     if let Some(root) = q_overlay.iter().next() {
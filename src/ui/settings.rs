@@ -1,13 +1,33 @@
 use bevy::prelude::*;
 use bevy::ui::{UiRect, Val};
-use bevy::window::{MonitorSelection, PrimaryWindow, WindowMode};
+use bevy::window::{
+    MonitorSelection, PresentMode, PrimaryWindow, VideoModeSelection, WindowMode, WindowMoved,
+    WindowPosition, WindowResized,
+};
+use std::time::{Duration, Instant};
 
-use crate::ui::types::{GameSettings, RESOLUTIONS};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{localized, tr, Language};
+use crate::input::KeyBindings;
+use crate::save::settings_file_path;
+use crate::ui::keybindings::open_keybindings_panel;
+use crate::ui::types::{
+    DisplayMode, GameSettings, FPS_CAPS, GAME_SPEED_MAX, GAME_SPEED_MIN, RESOLUTIONS, UI_SCALE_MAX, UI_SCALE_MIN,
+};
 use crate::utils::despawn_with_children;
 
 #[derive(Resource)]
 pub(super) struct SettingsOpenRequest;
 
+/// 打开设置面板时的设置和键位快照：面板里的改动（包括改键）即时预览，"应用" 时才提交（更新快照并写盘），
+/// "返回" / Esc 则恢复到快照。面板关闭后这个资源不存在
+#[derive(Resource)]
+pub(super) struct SettingsSnapshot {
+    settings: GameSettings,
+    key_bindings: KeyBindings,
+}
+
 #[derive(Component)]
 pub(super) struct SettingsUiRoot;
 
@@ -22,7 +42,46 @@ pub(super) struct ResolutionValue;
 pub(super) struct VolumeValue;
 
 #[derive(Component)]
-pub(super) struct FullscreenValue;
+pub(super) struct DisplayModeValue;
+
+#[derive(Component)]
+pub(super) struct DifficultyValue;
+
+#[derive(Component)]
+pub(super) struct VsyncValue;
+
+#[derive(Component)]
+pub(super) struct FpsCapValue;
+
+#[derive(Component)]
+pub(super) struct UiScaleValue;
+
+#[derive(Component)]
+pub(super) struct LanguageValue;
+
+#[derive(Component)]
+pub(super) struct ScreenShakeValue;
+
+#[derive(Component)]
+pub(super) struct AimAssistValue;
+
+#[derive(Component)]
+pub(super) struct FocusPauseValue;
+
+#[derive(Component)]
+pub(super) struct SlowMoValue;
+
+#[derive(Component)]
+pub(super) struct GameSpeedValue;
+
+#[derive(Component)]
+pub(super) struct FaceModeValue;
+
+#[derive(Component)]
+pub(super) struct ColorblindValue;
+
+#[derive(Component)]
+pub(super) struct TutorialValue;
 
 #[derive(Component, Clone, Copy)]
 pub(super) enum SettingsAction {
@@ -30,7 +89,26 @@ pub(super) enum SettingsAction {
     ResolutionNext,
     VolumeDown,
     VolumeUp,
-    ToggleFullscreen,
+    UiScaleDown,
+    UiScaleUp,
+    ScreenShakeDown,
+    ScreenShakeUp,
+    AimAssistDown,
+    AimAssistUp,
+    CycleDisplayMode,
+    ToggleVsync,
+    FpsCapPrev,
+    FpsCapNext,
+    CycleDifficulty,
+    CycleLanguage,
+    TogglePauseOnFocusLoss,
+    ToggleKillSlowMo,
+    GameSpeedDown,
+    GameSpeedUp,
+    CycleFaceMode,
+    ToggleColorblind,
+    ToggleTutorial,
+    OpenKeyBindings,
     Apply,
     Close,
 }
@@ -45,6 +123,7 @@ pub(super) fn spawn_settings_panel_if_requested(
     request: Option<Res<SettingsOpenRequest>>,
     existing: Query<Entity, With<SettingsUiRoot>>,
     settings: Res<GameSettings>,
+    bindings: Res<KeyBindings>,
 ) {
     if request.is_none() {
         return;
@@ -56,13 +135,29 @@ pub(super) fn spawn_settings_panel_if_requested(
         return;
     }
 
+    commands.insert_resource(SettingsSnapshot { settings: settings.clone(), key_bindings: bindings.clone() });
+
     let bg: Handle<Image> = asset_server.load("settings.png");
     let font: Handle<Font> = asset_server.load("fonts/YuFanLixing.otf");
 
     let (rw, rh) = current_resolution(&settings);
     let res_text = format!("{rw} x {rh}");
     let vol_text = format!("{:.0}%", (settings.volume * 100.0).clamp(0.0, 100.0));
-    let fs_text = if settings.fullscreen { "开" } else { "关" }.to_string();
+    let lang = settings.language;
+    let mode_text = tr(lang, settings.display_mode.label_key()).to_string();
+    let diff_text = tr(lang, settings.difficulty.label_key()).to_string();
+    let lang_text = lang.label().to_string();
+    let vsync_text = vsync_label(&settings);
+    let fps_text = fps_cap_label(&settings);
+    let scale_text = ui_scale_label(&settings);
+    let shake_text = screen_shake_label(&settings);
+    let aim_text = aim_assist_label(&settings);
+    let focus_text = focus_pause_label(&settings);
+    let slow_mo_text = slow_mo_label(&settings);
+    let speed_text = game_speed_label(&settings);
+    let face_text = tr(lang, settings.face_mode.label_key()).to_string();
+    let colorblind_text = colorblind_label(&settings);
+    let tutorial_text = tutorial_label(&settings);
 
     commands
         .spawn((
@@ -79,8 +174,12 @@ pub(super) fn spawn_settings_panel_if_requested(
         .with_children(|root| {
             root.spawn((
                 Node {
-                    width: Val::Px(820.0),
-                    height: Val::Px(560.0),
+                    // 小窗口按百分比收缩、大窗口不超过设计尺寸；设置项较多时内容可滚动
+                    width: Val::Percent(90.0),
+                    max_width: Val::Px(820.0),
+                    height: Val::Percent(90.0),
+                    max_height: Val::Px(640.0),
+                    overflow: Overflow::scroll_y(),
                     flex_direction: FlexDirection::Column,
                     justify_content: JustifyContent::FlexStart,
                     align_items: AlignItems::Center,
@@ -92,7 +191,7 @@ pub(super) fn spawn_settings_panel_if_requested(
             ))
             .with_children(|panel| {
                 panel.spawn((
-                    Text::new("设置"),
+                    localized(lang, "settings.title"),
                     TextFont {
                         font: font.clone(),
                         font_size: 40.0,
@@ -113,9 +212,22 @@ pub(super) fn spawn_settings_panel_if_requested(
                     },
                 ))
                 .with_children(|content| {
-                    spawn_row_resolution(content, &font, res_text);
-                    spawn_row_fullscreen(content, &font, fs_text);
-                    spawn_row_volume(content, &font, vol_text);
+                    spawn_row_resolution(content, &font, lang, res_text);
+                    spawn_row_display_mode(content, &font, lang, mode_text);
+                    spawn_row_vsync(content, &font, lang, vsync_text);
+                    spawn_row_fps_cap(content, &font, lang, fps_text);
+                    spawn_row_volume(content, &font, lang, vol_text);
+                    spawn_row_ui_scale(content, &font, lang, scale_text);
+                    spawn_row_screen_shake(content, &font, lang, shake_text);
+                    spawn_row_aim_assist(content, &font, lang, aim_text);
+                    spawn_row_slow_mo(content, &font, lang, slow_mo_text);
+                    spawn_row_game_speed(content, &font, lang, speed_text);
+                    spawn_row_face_mode(content, &font, lang, face_text);
+                    spawn_row_colorblind(content, &font, lang, colorblind_text);
+                    spawn_row_difficulty(content, &font, lang, diff_text);
+                    spawn_row_language(content, &font, lang, lang_text);
+                    spawn_row_focus_pause(content, &font, lang, focus_text);
+                    spawn_row_tutorial(content, &font, lang, tutorial_text);
 
                     content
                         .spawn((
@@ -131,20 +243,28 @@ pub(super) fn spawn_settings_panel_if_requested(
                             },
                         ))
                         .with_children(|buttons| {
-                            spawn_action_button(buttons, &font, "应用", SettingsAction::Apply);
-                            spawn_action_button(buttons, &font, "返回", SettingsAction::Close);
+                            for (key, action) in [
+                                ("settings.keybindings", SettingsAction::OpenKeyBindings),
+                                ("settings.apply", SettingsAction::Apply),
+                                ("common.back", SettingsAction::Close),
+                            ] {
+                                spawn_action_button(buttons, &font, lang, key, action);
+                            }
                         });
                 });
             });
         });
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub(super) fn handle_settings_buttons(
     mut interactions: Query<
         (&Interaction, &mut BackgroundColor, &SettingsAction),
         (Changed<Interaction>, With<Button>, With<SettingsButton>),
     >,
     mut settings: ResMut<GameSettings>,
+    mut bindings: ResMut<KeyBindings>,
+    mut snapshot: Option<ResMut<SettingsSnapshot>>,
     mut window_q: Query<&mut Window, With<PrimaryWindow>>,
     root_q: Query<Entity, With<SettingsUiRoot>>,
     children_q: Query<&Children>,
@@ -170,14 +290,73 @@ pub(super) fn handle_settings_buttons(
                     SettingsAction::VolumeUp => {
                         settings.volume = (settings.volume + 0.05).clamp(0.0, 1.0);
                     }
-                    SettingsAction::ToggleFullscreen => {
-                        settings.fullscreen = !settings.fullscreen;
+                    SettingsAction::CycleDisplayMode => {
+                        settings.display_mode = settings.display_mode.next();
+                        apply_window_settings(&settings, &mut window_q);
+                    }
+                    SettingsAction::UiScaleDown => {
+                        settings.ui_scale = (settings.ui_scale - 0.25).clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+                    }
+                    SettingsAction::UiScaleUp => {
+                        settings.ui_scale = (settings.ui_scale + 0.25).clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+                    }
+                    SettingsAction::ScreenShakeDown => {
+                        settings.screen_shake = (settings.screen_shake - 0.25).clamp(0.0, 1.0);
+                    }
+                    SettingsAction::ScreenShakeUp => {
+                        settings.screen_shake = (settings.screen_shake + 0.25).clamp(0.0, 1.0);
+                    }
+                    SettingsAction::AimAssistDown => {
+                        settings.aim_assist = (settings.aim_assist - 0.25).clamp(0.0, 1.0);
+                    }
+                    SettingsAction::AimAssistUp => {
+                        settings.aim_assist = (settings.aim_assist + 0.25).clamp(0.0, 1.0);
+                    }
+                    SettingsAction::ToggleVsync => {
+                        settings.vsync = !settings.vsync;
                         apply_window_settings(&settings, &mut window_q);
                     }
+                    SettingsAction::FpsCapPrev => step_fps_cap(&mut settings, -1),
+                    SettingsAction::FpsCapNext => step_fps_cap(&mut settings, 1),
+                    SettingsAction::CycleDifficulty => {
+                        settings.difficulty = settings.difficulty.next();
+                    }
+                    SettingsAction::CycleLanguage => {
+                        settings.language = settings.language.next();
+                    }
+                    SettingsAction::TogglePauseOnFocusLoss => {
+                        settings.pause_on_focus_loss = !settings.pause_on_focus_loss;
+                    }
+                    SettingsAction::ToggleKillSlowMo => {
+                        settings.kill_slow_mo = !settings.kill_slow_mo;
+                    }
+                    SettingsAction::GameSpeedDown => {
+                        settings.game_speed = (settings.game_speed - 0.1).clamp(GAME_SPEED_MIN, GAME_SPEED_MAX);
+                    }
+                    SettingsAction::GameSpeedUp => {
+                        settings.game_speed = (settings.game_speed + 0.1).clamp(GAME_SPEED_MIN, GAME_SPEED_MAX);
+                    }
+                    SettingsAction::CycleFaceMode => {
+                        settings.face_mode = settings.face_mode.next();
+                    }
+                    SettingsAction::ToggleColorblind => {
+                        settings.colorblind = !settings.colorblind;
+                    }
+                    SettingsAction::ToggleTutorial => {
+                        settings.seen_tutorial = !settings.seen_tutorial;
+                    }
+                    SettingsAction::OpenKeyBindings => {
+                        open_keybindings_panel(&mut commands);
+                    }
                     SettingsAction::Apply => {
                         apply_window_settings(&settings, &mut window_q);
+                        if let Some(snapshot) = snapshot.as_mut() {
+                            snapshot.settings = settings.clone();
+                            snapshot.key_bindings = bindings.clone();
+                        }
                     }
                     SettingsAction::Close => {
+                        revert_settings(&mut commands, &mut settings, &mut bindings, snapshot.as_deref(), &mut window_q);
                         close_settings_ui(&mut commands, &root_q, &children_q);
                     }
                 }
@@ -188,9 +367,29 @@ pub(super) fn handle_settings_buttons(
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub(super) fn sync_settings_texts(
     settings: Res<GameSettings>,
-    mut q: Query<(&mut Text, AnyOf<(&ResolutionValue, &VolumeValue, &FullscreenValue)>)>,
+    mut q: Query<(
+        &mut Text,
+        AnyOf<(
+            &ResolutionValue,
+            &VolumeValue,
+            &DisplayModeValue,
+            &DifficultyValue,
+            &VsyncValue,
+            &FpsCapValue,
+            &UiScaleValue,
+            &LanguageValue,
+            &ScreenShakeValue,
+            &AimAssistValue,
+            &FocusPauseValue,
+            &SlowMoValue,
+            &FaceModeValue,
+            &ColorblindValue,
+            &TutorialValue,
+        )>,
+    )>,
 ) {
     if !settings.is_changed() {
         return;
@@ -199,31 +398,172 @@ pub(super) fn sync_settings_texts(
     let (rw, rh) = current_resolution(&settings);
     let res_text = format!("{rw} x {rh}");
     let vol_text = format!("{:.0}%", (settings.volume * 100.0).clamp(0.0, 100.0));
-    let fs_text = if settings.fullscreen { "开" } else { "关" }.to_string();
+    let lang = settings.language;
+    let mode_text = tr(lang, settings.display_mode.label_key()).to_string();
+    let diff_text = tr(lang, settings.difficulty.label_key()).to_string();
+    let lang_text = lang.label().to_string();
+    let vsync_text = vsync_label(&settings);
+    let fps_text = fps_cap_label(&settings);
+    let scale_text = ui_scale_label(&settings);
+    let shake_text = screen_shake_label(&settings);
+    let aim_text = aim_assist_label(&settings);
+    let focus_text = focus_pause_label(&settings);
+    let slow_mo_text = slow_mo_label(&settings);
+    let face_text = tr(lang, settings.face_mode.label_key()).to_string();
+    let colorblind_text = colorblind_label(&settings);
+    let tutorial_text = tutorial_label(&settings);
 
-    for (mut text, (is_res, is_vol, is_fs)) in &mut q {
+    for (
+        mut text,
+        (
+            is_res,
+            is_vol,
+            is_fs,
+            is_diff,
+            is_vsync,
+            is_fps,
+            is_scale,
+            is_lang,
+            is_shake,
+            is_aim,
+            is_focus,
+            is_slow_mo,
+            is_face,
+            is_colorblind,
+            is_tutorial,
+        ),
+    ) in &mut q
+    {
         if is_res.is_some() {
             text.0 = res_text.clone();
         } else if is_vol.is_some() {
             text.0 = vol_text.clone();
         } else if is_fs.is_some() {
-            text.0 = fs_text.clone();
+            text.0 = mode_text.clone();
+        } else if is_diff.is_some() {
+            text.0 = diff_text.clone();
+        } else if is_vsync.is_some() {
+            text.0 = vsync_text.clone();
+        } else if is_fps.is_some() {
+            text.0 = fps_text.clone();
+        } else if is_scale.is_some() {
+            text.0 = scale_text.clone();
+        } else if is_lang.is_some() {
+            text.0 = lang_text.clone();
+        } else if is_shake.is_some() {
+            text.0 = shake_text.clone();
+        } else if is_aim.is_some() {
+            text.0 = aim_text.clone();
+        } else if is_focus.is_some() {
+            text.0 = focus_text.clone();
+        } else if is_slow_mo.is_some() {
+            text.0 = slow_mo_text.clone();
+        } else if is_face.is_some() {
+            text.0 = face_text.clone();
+        } else if is_colorblind.is_some() {
+            text.0 = colorblind_text.clone();
+        } else if is_tutorial.is_some() {
+            text.0 = tutorial_text.clone();
         }
     }
 }
 
+/// Esc 等同于 "返回"：放弃未应用的改动并关闭面板。Esc 在这里吃掉，不会顺带取消暂停
+#[allow(clippy::too_many_arguments)]
 pub(super) fn close_settings_on_esc(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut settings: ResMut<GameSettings>,
+    mut bindings: ResMut<KeyBindings>,
+    snapshot: Option<Res<SettingsSnapshot>>,
+    mut window_q: Query<&mut Window, With<PrimaryWindow>>,
     root_q: Query<Entity, With<SettingsUiRoot>>,
     children_q: Query<&Children>,
     mut commands: Commands,
 ) {
-    if !keyboard.just_pressed(KeyCode::Escape) {
+    if root_q.is_empty() || !keyboard.just_pressed(KeyCode::Escape) {
         return;
     }
+    keyboard.clear_just_pressed(KeyCode::Escape);
+    revert_settings(&mut commands, &mut settings, &mut bindings, snapshot.as_deref(), &mut window_q);
     close_settings_ui(&mut commands, &root_q, &children_q);
 }
 
+/// 恢复到打开面板时的设置（包括窗口模式和分辨率）和键位，并丢掉快照
+fn revert_settings(
+    commands: &mut Commands,
+    settings: &mut ResMut<GameSettings>,
+    bindings: &mut ResMut<KeyBindings>,
+    snapshot: Option<&SettingsSnapshot>,
+    window_q: &mut Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Some(snapshot) = snapshot else { return; };
+    if **settings != snapshot.settings {
+        **settings = snapshot.settings.clone();
+        apply_window_settings(settings, window_q);
+    }
+    **bindings = snapshot.key_bindings.clone();
+    commands.remove_resource::<SettingsSnapshot>();
+}
+
+/// settings.json 的内容：通用设置 + 键位
+#[derive(Serialize, Deserialize, Default)]
+struct SettingsFile {
+    #[serde(flatten)]
+    settings: GameSettings,
+    #[serde(default)]
+    key_bindings: KeyBindings,
+}
+
+/// 启动时读取 settings.json（不存在或解析失败就用默认值），并立即应用窗口设置
+pub(super) fn load_settings_from_disk(
+    mut settings: ResMut<GameSettings>,
+    mut bindings: ResMut<KeyBindings>,
+    mut window_q: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let path = settings_file_path();
+    let Ok(text) = std::fs::read_to_string(&path) else { return; };
+
+    match serde_json::from_str::<SettingsFile>(&text) {
+        Ok(loaded) => {
+            *settings = loaded.settings;
+            *bindings = loaded.key_bindings;
+            apply_window_settings(&settings, &mut window_q);
+        }
+        Err(e) => warn!("读取设置失败 {:?}: {}", path, e),
+    }
+}
+
+/// 设置或键位有变化就写回 settings.json；设置面板打开期间只写已应用的快照，未应用的预览和改键不落盘
+pub(super) fn persist_settings_on_change(
+    settings: Res<GameSettings>,
+    bindings: Res<KeyBindings>,
+    snapshot: Option<Res<SettingsSnapshot>>,
+) {
+    let settings_changed = settings.is_changed() && !settings.is_added();
+    let bindings_changed = bindings.is_changed() && !bindings.is_added();
+    let applied = snapshot.as_ref().is_some_and(|s| s.is_changed() && !s.is_added());
+    if !settings_changed && !bindings_changed && !applied {
+        return;
+    }
+
+    let (committed, committed_bindings) = snapshot
+        .as_ref()
+        .map_or((&*settings, &*bindings), |s| (&s.settings, &s.key_bindings));
+    let file = SettingsFile {
+        settings: committed.clone(),
+        key_bindings: committed_bindings.clone(),
+    };
+    let path = settings_file_path();
+    match serde_json::to_string_pretty(&file) {
+        Ok(text) => {
+            if let Err(e) = std::fs::write(&path, text) {
+                warn!("写入设置失败 {:?}: {}", path, e);
+            }
+        }
+        Err(e) => warn!("序列化设置失败: {}", e),
+    }
+}
+
 fn close_settings_ui(commands: &mut Commands, root_q: &Query<Entity, With<SettingsUiRoot>>, children_q: &Query<&Children>) {
     if let Ok(root) = root_q.single() {
         despawn_with_children(commands, children_q, root);
@@ -252,25 +592,217 @@ fn step_resolution(settings: &mut GameSettings, dir: i32) {
         (cur + len - 1) % len
     };
     settings.resolution_index = next;
+    // 主动选了预设分辨率，就不再沿用手动拖出来的窗口大小
+    settings.window_size = None;
+}
+
+/// 读取 settings.json 里的通用设置（键位等其它字段会被忽略），给 main.rs 创建窗口时用
+pub fn read_saved_settings() -> Option<GameSettings> {
+    let text = std::fs::read_to_string(settings_file_path()).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+/// 窗口模式下正在变化、还没写进设置的大小/位置
+#[derive(Default)]
+pub(super) struct PendingWindowGeometry {
+    size: Option<(u32, u32)>,
+    position: Option<(i32, i32)>,
+    idle: f32,
+}
+
+/// 记录窗口模式下玩家手动调整的大小和位置；停止变化 0.5 秒后再写入设置，避免拖动时频繁写盘
+#[allow(clippy::collapsible_if)]
+pub(super) fn track_windowed_geometry(
+    time: Res<Time<Real>>,
+    mut resized: MessageReader<WindowResized>,
+    mut moved: MessageReader<WindowMoved>,
+    primary_q: Query<Entity, With<PrimaryWindow>>,
+    mut settings: ResMut<GameSettings>,
+    mut pending: Local<PendingWindowGeometry>,
+) {
+    let primary = primary_q.single().ok();
+    let windowed = settings.display_mode == DisplayMode::Windowed;
+
+    for ev in resized.read() {
+        if windowed && Some(ev.window) == primary {
+            pending.size = Some((ev.width.round() as u32, ev.height.round() as u32));
+            pending.idle = 0.0;
+        }
+    }
+    for ev in moved.read() {
+        if windowed && Some(ev.window) == primary {
+            pending.position = Some((ev.position.x, ev.position.y));
+            pending.idle = 0.0;
+        }
+    }
+
+    if pending.size.is_none() && pending.position.is_none() {
+        return;
+    }
+
+    pending.idle += time.delta_secs();
+    if pending.idle < 0.5 {
+        return;
+    }
+
+    if let Some(size) = pending.size.take() {
+        if settings.window_size != Some(size) {
+            settings.window_size = Some(size);
+        }
+    }
+    if let Some(pos) = pending.position.take() {
+        if settings.window_position != Some(pos) {
+            settings.window_position = Some(pos);
+        }
+    }
+}
+
+fn ui_scale_label(settings: &GameSettings) -> String {
+    format!("{:.0}%", settings.ui_scale * 100.0)
+}
+
+/// 把设置里的界面缩放同步到 Bevy 的 UiScale
+pub(super) fn apply_ui_scale(settings: Res<GameSettings>, mut ui_scale: ResMut<UiScale>) {
+    if !settings.is_changed() {
+        return;
+    }
+    let scale = settings.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+    if ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+}
+
+fn screen_shake_label(settings: &GameSettings) -> String {
+    if settings.screen_shake <= 0.0 {
+        tr(settings.language, "common.off").to_string()
+    } else {
+        format!("{:.0}%", settings.screen_shake * 100.0)
+    }
+}
+
+fn aim_assist_label(settings: &GameSettings) -> String {
+    if settings.aim_assist <= 0.0 {
+        tr(settings.language, "common.off").to_string()
+    } else {
+        format!("{:.0}%", settings.aim_assist * 100.0)
+    }
+}
+
+fn vsync_label(settings: &GameSettings) -> String {
+    let key = if settings.vsync { "common.on" } else { "common.off" };
+    tr(settings.language, key).to_string()
+}
+
+fn focus_pause_label(settings: &GameSettings) -> String {
+    let key = if settings.pause_on_focus_loss { "common.on" } else { "common.off" };
+    tr(settings.language, key).to_string()
+}
+
+fn slow_mo_label(settings: &GameSettings) -> String {
+    let key = if settings.kill_slow_mo { "common.on" } else { "common.off" };
+    tr(settings.language, key).to_string()
+}
+
+fn game_speed_label(settings: &GameSettings) -> String {
+    format!("{:.1}x", settings.game_speed)
+}
+
+/// 游戏速度单独同步：sync_settings_texts 的 AnyOf 已经放满 15 项
+pub(super) fn sync_game_speed_text(settings: Res<GameSettings>, mut q: Query<&mut Text, With<GameSpeedValue>>) {
+    if !settings.is_changed() {
+        return;
+    }
+    for mut text in &mut q {
+        text.0 = game_speed_label(&settings);
+    }
+}
+
+fn colorblind_label(settings: &GameSettings) -> String {
+    let key = if settings.colorblind { "common.on" } else { "common.off" };
+    tr(settings.language, key).to_string()
+}
+
+/// 开 = 下次进入游戏时显示操作说明
+fn tutorial_label(settings: &GameSettings) -> String {
+    let key = if settings.seen_tutorial { "common.off" } else { "common.on" };
+    tr(settings.language, key).to_string()
+}
+
+fn fps_cap_label(settings: &GameSettings) -> String {
+    if settings.max_fps == 0 {
+        tr(settings.language, "settings.fps_unlimited").to_string()
+    } else {
+        format!("{} FPS", settings.max_fps)
+    }
+}
+
+fn step_fps_cap(settings: &mut GameSettings, dir: i32) {
+    let len = FPS_CAPS.len();
+    if len == 0 {
+        settings.max_fps = 0;
+        return;
+    }
+
+    let cur = FPS_CAPS.iter().position(|&f| f == settings.max_fps).unwrap_or(0);
+    let next = if dir >= 0 {
+        (cur + 1) % len
+    } else {
+        (cur + len - 1) % len
+    };
+    settings.max_fps = FPS_CAPS[next];
+}
+
+/// 帧率上限：在帧末尾 sleep 补足剩余时间（垂直同步开启时通常不需要）
+#[allow(clippy::collapsible_if)]
+pub(super) fn limit_frame_rate(settings: Res<GameSettings>, mut last_frame: Local<Option<Instant>>) {
+    if settings.max_fps > 0 {
+        if let Some(prev) = *last_frame {
+            let target = Duration::from_secs_f64(1.0 / settings.max_fps as f64);
+            let elapsed = prev.elapsed();
+            if elapsed < target {
+                std::thread::sleep(target - elapsed);
+            }
+        }
+    }
+    *last_frame = Some(Instant::now());
 }
 
 fn apply_window_settings(settings: &GameSettings, window_q: &mut Query<&mut Window, With<PrimaryWindow>>) {
     let Ok(mut window) = window_q.single_mut() else { return; };
 
-    if settings.fullscreen {
-        window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Current);
+    window.present_mode = if settings.vsync {
+        PresentMode::AutoVsync
     } else {
-        window.mode = WindowMode::Windowed;
-        let (w, h) = current_resolution(settings);
-        window.resolution.set(w as f32, h as f32);
+        PresentMode::AutoNoVsync
+    };
+
+    let (w, h) = current_resolution(settings);
+    match settings.display_mode {
+        DisplayMode::Windowed => {
+            window.mode = WindowMode::Windowed;
+            let (w, h) = settings.window_size.unwrap_or((w, h));
+            window.resolution.set(w as f32, h as f32);
+            if let Some((x, y)) = settings.window_position {
+                window.position = WindowPosition::At(IVec2::new(x, y));
+            }
+        }
+        DisplayMode::Borderless => {
+            window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Current);
+        }
+        DisplayMode::Fullscreen => {
+            // 独占全屏：沿用显示器当前的视频模式。指定分辨率 + 写死的刷新率 / 色深在多数显示器上
+            // 对不上任何一个模式，切换会直接失败，所以这里不用选中的分辨率（它只对窗口模式生效）
+            window.mode = WindowMode::Fullscreen(MonitorSelection::Current, VideoModeSelection::Current);
+        }
     }
 }
 
-fn spawn_row_resolution(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, value: String) {
+fn spawn_row_resolution(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
     spawn_row(
         parent,
         font,
-        "分辨率",
+        lang,
+        "settings.resolution",
         value,
         ResolutionValue,
         Some((SettingsAction::ResolutionPrev, "←")),
@@ -279,24 +811,180 @@ fn spawn_row_resolution(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Fon
     );
 }
 
-fn spawn_row_fullscreen(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, value: String) {
+fn spawn_row_display_mode(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.display_mode",
+        value,
+        DisplayModeValue,
+        Some((SettingsAction::CycleDisplayMode, "common.toggle")),
+        None,
+        None,
+    );
+}
+
+fn spawn_row_ui_scale(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.ui_scale",
+        value,
+        UiScaleValue,
+        Some((SettingsAction::UiScaleDown, "-")),
+        Some((SettingsAction::UiScaleUp, "+")),
+        None,
+    );
+}
+
+fn spawn_row_screen_shake(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.screen_shake",
+        value,
+        ScreenShakeValue,
+        Some((SettingsAction::ScreenShakeDown, "-")),
+        Some((SettingsAction::ScreenShakeUp, "+")),
+        None,
+    );
+}
+
+fn spawn_row_aim_assist(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.aim_assist",
+        value,
+        AimAssistValue,
+        Some((SettingsAction::AimAssistDown, "-")),
+        Some((SettingsAction::AimAssistUp, "+")),
+        None,
+    );
+}
+
+fn spawn_row_vsync(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.vsync",
+        value,
+        VsyncValue,
+        Some((SettingsAction::ToggleVsync, "common.toggle")),
+        None,
+        None,
+    );
+}
+
+fn spawn_row_focus_pause(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.pause_on_focus_loss",
+        value,
+        FocusPauseValue,
+        Some((SettingsAction::TogglePauseOnFocusLoss, "common.toggle")),
+        None,
+        None,
+    );
+}
+
+fn spawn_row_slow_mo(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.kill_slow_mo",
+        value,
+        SlowMoValue,
+        Some((SettingsAction::ToggleKillSlowMo, "common.toggle")),
+        None,
+        None,
+    );
+}
+
+fn spawn_row_game_speed(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.game_speed",
+        value,
+        GameSpeedValue,
+        Some((SettingsAction::GameSpeedDown, "-")),
+        Some((SettingsAction::GameSpeedUp, "+")),
+        None,
+    );
+}
+
+fn spawn_row_face_mode(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.face_mode",
+        value,
+        FaceModeValue,
+        Some((SettingsAction::CycleFaceMode, "common.toggle")),
+        None,
+        None,
+    );
+}
+
+fn spawn_row_colorblind(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.colorblind",
+        value,
+        ColorblindValue,
+        Some((SettingsAction::ToggleColorblind, "common.toggle")),
+        None,
+        None,
+    );
+}
+
+fn spawn_row_tutorial(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
     spawn_row(
         parent,
         font,
-        "全屏",
+        lang,
+        "settings.tutorial",
         value,
-        FullscreenValue,
-        Some((SettingsAction::ToggleFullscreen, "切换")),
+        TutorialValue,
+        Some((SettingsAction::ToggleTutorial, "common.toggle")),
         None,
         None,
     );
 }
 
-fn spawn_row_volume(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, value: String) {
+fn spawn_row_fps_cap(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
     spawn_row(
         parent,
         font,
-        "音量",
+        lang,
+        "settings.fps_cap",
+        value,
+        FpsCapValue,
+        Some((SettingsAction::FpsCapPrev, "←")),
+        Some((SettingsAction::FpsCapNext, "→")),
+        None,
+    );
+}
+
+fn spawn_row_volume(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.volume",
         value,
         VolumeValue,
         Some((SettingsAction::VolumeDown, "-")),
@@ -305,15 +993,45 @@ fn spawn_row_volume(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>,
     );
 }
 
+fn spawn_row_difficulty(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.difficulty",
+        value,
+        DifficultyValue,
+        Some((SettingsAction::CycleDifficulty, "common.toggle")),
+        None,
+        None,
+    );
+}
+
+fn spawn_row_language(parent: &mut ChildSpawnerCommands<'_>, font: &Handle<Font>, lang: Language, value: String) {
+    spawn_row(
+        parent,
+        font,
+        lang,
+        "settings.language",
+        value,
+        LanguageValue,
+        Some((SettingsAction::CycleLanguage, "common.toggle")),
+        None,
+        None,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
 fn spawn_row<M: Component>(
     parent: &mut ChildSpawnerCommands<'_>,
     font: &Handle<Font>,
-    label: &str,
+    lang: Language,
+    label: &'static str,
     value: String,
     marker: M,
-    left: Option<(SettingsAction, &str)>,
-    right: Option<(SettingsAction, &str)>,
-    extra: Option<(SettingsAction, &str)>,
+    left: Option<(SettingsAction, &'static str)>,
+    right: Option<(SettingsAction, &'static str)>,
+    extra: Option<(SettingsAction, &'static str)>,
 ) {
     parent
         .spawn(Node {
@@ -326,7 +1044,7 @@ fn spawn_row<M: Component>(
         })
         .with_children(|row| {
             row.spawn((
-                Text::new(label),
+                localized(lang, label),
                 TextFont {
                     font: font.clone(),
                     font_size: 24.0,
@@ -355,13 +1073,13 @@ fn spawn_row<M: Component>(
             })
             .with_children(|btns| {
                 if let Some((a, t)) = left {
-                    spawn_action_button(btns, font, t, a);
+                    spawn_action_button(btns, font, lang, t, a);
                 }
                 if let Some((a, t)) = right {
-                    spawn_action_button(btns, font, t, a);
+                    spawn_action_button(btns, font, lang, t, a);
                 }
                 if let Some((a, t)) = extra {
-                    spawn_action_button(btns, font, t, a);
+                    spawn_action_button(btns, font, lang, t, a);
                 }
             });
         });
@@ -370,7 +1088,8 @@ fn spawn_row<M: Component>(
 fn spawn_action_button(
     parent: &mut ChildSpawnerCommands<'_>,
     font: &Handle<Font>,
-    text: &str,
+    lang: Language,
+    key: &'static str,
     action: SettingsAction,
 ) {
     parent
@@ -389,7 +1108,7 @@ fn spawn_action_button(
         ))
         .with_children(|b| {
             b.spawn((
-                Text::new(text),
+                localized(lang, key),
                 TextFont {
                     font: font.clone(),
                     font_size: 20.0,
@@ -1,14 +1,165 @@
 // src/ui/types.rs
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Language;
 
 pub const RESOLUTIONS: &[(u32, u32)] = &[(1280, 720), (1600, 900), (1920, 1080)];
 
-#[derive(Resource)]
+/// 界面缩放范围
+pub const UI_SCALE_MIN: f32 = 0.75;
+pub const UI_SCALE_MAX: f32 = 2.0;
+
+/// 游戏速度范围：辅助功能，整体放慢战斗（只影响 Time<Virtual>）
+pub const GAME_SPEED_MIN: f32 = 0.5;
+pub const GAME_SPEED_MAX: f32 = 1.0;
+
+/// 可选帧率上限，0 表示不限制
+pub const FPS_CAPS: &[u32] = &[0, 30, 60, 120, 144];
+
+/// 游戏难度：影响敌人血量、接触伤害和刷怪间隔
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// 文本表 key
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "difficulty.easy",
+            Difficulty::Normal => "difficulty.normal",
+            Difficulty::Hard => "difficulty.hard",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    /// 敌人血量倍率
+    pub fn enemy_hp_factor(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.7,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.4,
+        }
+    }
+
+    /// 敌人接触伤害倍率
+    pub fn enemy_damage_factor(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.6,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+        }
+    }
+
+    /// 刷怪间隔倍率（越小刷得越快）
+    pub fn spawn_interval_factor(self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.4,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.7,
+        }
+    }
+}
+
+/// 角色朝向：跟随移动方向，或始终朝向鼠标
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FaceMode {
+    #[default]
+    Movement,
+    Cursor,
+}
+
+impl FaceMode {
+    /// 文本表 key
+    pub fn label_key(self) -> &'static str {
+        match self {
+            FaceMode::Movement => "face_mode.movement",
+            FaceMode::Cursor => "face_mode.cursor",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            FaceMode::Movement => FaceMode::Cursor,
+            FaceMode::Cursor => FaceMode::Movement,
+        }
+    }
+}
+
+/// 显示模式：窗口 / 无边框全屏 / 独占全屏
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    /// 文本表 key
+    pub fn label_key(self) -> &'static str {
+        match self {
+            DisplayMode::Windowed => "display.windowed",
+            DisplayMode::Borderless => "display.borderless",
+            DisplayMode::Fullscreen => "display.fullscreen",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            DisplayMode::Windowed => DisplayMode::Borderless,
+            DisplayMode::Borderless => DisplayMode::Fullscreen,
+            DisplayMode::Fullscreen => DisplayMode::Windowed,
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GameSettings {
     pub resolution_index: usize,
     /// 0.0 ~ 1.0
     pub volume: f32,
-    pub fullscreen: bool,
+    pub display_mode: DisplayMode,
+    pub difficulty: Difficulty,
+    pub language: Language,
+    /// 垂直同步：开 = AutoVsync，关 = AutoNoVsync
+    pub vsync: bool,
+    /// 帧率上限，0 表示不限制
+    pub max_fps: u32,
+    /// 界面缩放（高分屏下调大），通过 UiScale 作用于全部 UI
+    pub ui_scale: f32,
+    /// 屏幕震动强度 0.0 ~ 1.0，0 为关闭
+    pub screen_shake: f32,
+    /// 辅助瞄准强度 0.0 ~ 1.0，0 为关闭吸附（Tab 手动锁定始终可用）
+    pub aim_assist: f32,
+    /// 场上最后一个敌人被击杀时的慢动作
+    pub kill_slow_mo: bool,
+    /// 游戏速度倍率 GAME_SPEED_MIN ~ GAME_SPEED_MAX，顿帧和慢动作在此基础上再相乘
+    pub game_speed: f32,
+    /// 角色朝向跟随移动还是鼠标；朝向鼠标时近战也朝鼠标方向出手
+    pub face_mode: FaceMode,
+    /// 色盲模式：血条、伤害数字等改用蓝 / 橙配色（见 palette.rs）
+    pub colorblind: bool,
+    /// 已经看过新手操作说明；在设置里关掉 "新手提示" 会重置为 false
+    pub seen_tutorial: bool,
+    /// 窗口失去焦点时自动暂停；关闭时游戏照常运行，但失焦期间不刷怪、不自动存档
+    pub pause_on_focus_loss: bool,
+    /// 窗口模式下最后一次的窗口大小（玩家手动拖动后记录），None 表示用预设分辨率
+    pub window_size: Option<(u32, u32)>,
+    /// 窗口模式下最后一次的窗口位置
+    pub window_position: Option<(i32, i32)>,
 }
 
 impl Default for GameSettings {
@@ -16,7 +167,22 @@ impl Default for GameSettings {
         Self {
             resolution_index: 0,
             volume: 0.8,
-            fullscreen: false,
+            display_mode: DisplayMode::Windowed,
+            difficulty: Difficulty::Normal,
+            language: Language::Chinese,
+            vsync: true,
+            max_fps: 0,
+            ui_scale: 1.0,
+            screen_shake: 1.0,
+            aim_assist: 0.0,
+            kill_slow_mo: true,
+            game_speed: 1.0,
+            face_mode: FaceMode::Movement,
+            colorblind: false,
+            seen_tutorial: false,
+            pause_on_focus_loss: true,
+            window_size: None,
+            window_position: None,
         }
     }
 }
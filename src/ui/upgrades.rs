@@ -0,0 +1,213 @@
+use bevy::prelude::*;
+use bevy::ui::Val;
+
+use crate::i18n::{localized, tr, Language};
+use crate::meta_progress::{MetaProgress, MetaUpgrade, MAX_UPGRADE_LEVEL};
+use crate::ui::types::GameSettings;
+use crate::utils::despawn_with_children;
+
+/// 强化面板根节点（全屏遮罩）
+#[derive(Component)]
+pub struct UpgradesPanelRoot;
+
+#[derive(Component)]
+pub(super) struct ShardsText;
+
+/// 某项强化的 "名称 等级" 文本
+#[derive(Component)]
+pub(super) struct UpgradeLevelText(MetaUpgrade);
+
+/// 某项强化购买按钮上的价格文本
+#[derive(Component)]
+pub(super) struct UpgradeCostText(MetaUpgrade);
+
+#[derive(Component, Clone, Copy)]
+pub(super) enum UpgradesAction {
+    Buy(MetaUpgrade),
+    Close,
+}
+
+pub fn open_upgrades_panel(commands: &mut Commands, asset_server: &AssetServer, lang: Language) {
+    let font: Handle<Font> = asset_server.load("fonts/YuFanLixing.otf");
+    let text_font = |size: f32| TextFont { font: font.clone(), font_size: size, ..default() };
+
+    commands
+        .spawn((
+            UpgradesPanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.55)),
+            GlobalZIndex(5),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Percent(90.0),
+                    max_width: Val::Px(560.0),
+                    max_height: Val::Percent(90.0),
+                    overflow: Overflow::scroll_y(),
+                    padding: UiRect::all(Val::Px(26.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(12.0),
+                    align_items: AlignItems::Stretch,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.12, 0.12, 0.16, 0.95)),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    localized(lang, "upgrades.title"),
+                    text_font(32.0),
+                    TextColor(Color::WHITE),
+                    Node { align_self: AlignSelf::Center, ..default() },
+                ));
+                panel.spawn((
+                    ShardsText,
+                    Text::new(""),
+                    text_font(20.0),
+                    TextColor(Color::srgb(0.6, 0.85, 1.0)),
+                    Node { align_self: AlignSelf::Center, margin: UiRect::bottom(Val::Px(8.0)), ..default() },
+                ));
+
+                for upgrade in MetaUpgrade::ALL {
+                    panel
+                        .spawn(Node {
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        })
+                        .with_children(|row| {
+                            row.spawn((UpgradeLevelText(upgrade), Text::new(""), text_font(20.0), TextColor(Color::WHITE)));
+                            row.spawn((
+                                Button,
+                                UpgradesAction::Buy(upgrade),
+                                Node {
+                                    width: Val::Px(160.0),
+                                    height: Val::Px(40.0),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                BackgroundColor(Color::srgb(0.20, 0.20, 0.40)),
+                            ))
+                            .with_children(|btn| {
+                                btn.spawn((UpgradeCostText(upgrade), Text::new(""), text_font(18.0), TextColor(Color::WHITE)));
+                            });
+                        });
+                }
+
+                panel
+                    .spawn((
+                        Button,
+                        UpgradesAction::Close,
+                        Node {
+                            width: Val::Px(260.0),
+                            height: Val::Px(46.0),
+                            align_self: AlignSelf::Center,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::top(Val::Px(12.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.20, 0.20, 0.40)),
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((localized(lang, "common.back"), text_font(20.0), TextColor(Color::WHITE)));
+                    });
+            });
+        });
+}
+
+pub(super) fn handle_upgrade_buttons(
+    mut commands: Commands,
+    mut interactions: Query<(&Interaction, &UpgradesAction, &mut BackgroundColor), Changed<Interaction>>,
+    roots: Query<Entity, With<UpgradesPanelRoot>>,
+    children_q: Query<&Children>,
+    mut meta: ResMut<MetaProgress>,
+) {
+    for (interaction, action, mut bg) in &mut interactions {
+        match *interaction {
+            Interaction::Pressed => {
+                bg.0 = Color::srgb(0.8, 0.8, 1.0);
+                match *action {
+                    UpgradesAction::Buy(upgrade) => {
+                        meta.try_purchase(upgrade);
+                    }
+                    UpgradesAction::Close => close_upgrades_panel(&mut commands, &roots, &children_q),
+                }
+            }
+            Interaction::Hovered => bg.0 = Color::srgb(0.6, 0.6, 0.8),
+            Interaction::None => bg.0 = Color::srgb(0.20, 0.20, 0.40),
+        }
+    }
+}
+
+/// 面板刚打开或进度 / 语言变化时刷新文本
+#[allow(clippy::type_complexity)]
+pub(super) fn sync_upgrade_texts(
+    meta: Res<MetaProgress>,
+    settings: Res<GameSettings>,
+    added: Query<(), Added<UpgradesPanelRoot>>,
+    mut texts: Query<(&mut Text, AnyOf<(&ShardsText, &UpgradeLevelText, &UpgradeCostText)>)>,
+) {
+    if added.is_empty() && !meta.is_changed() && !settings.is_changed() {
+        return;
+    }
+    let lang = settings.language;
+
+    for (mut text, (shards, level, cost)) in &mut texts {
+        if shards.is_some() {
+            text.0 = format!("{}: {}", tr(lang, "upgrades.shards"), meta.shards);
+        } else if let Some(UpgradeLevelText(upgrade)) = level {
+            text.0 = format!(
+                "{}  Lv {}/{}",
+                tr(lang, upgrade.label_key()),
+                meta.level(*upgrade),
+                MAX_UPGRADE_LEVEL
+            );
+        } else if let Some(UpgradeCostText(upgrade)) = cost {
+            let level = meta.level(*upgrade);
+            text.0 = if level >= MAX_UPGRADE_LEVEL {
+                tr(lang, "upgrades.maxed").to_string()
+            } else {
+                format!("{} {}", tr(lang, "upgrades.buy"), upgrade.cost(level))
+            };
+        }
+    }
+}
+
+pub(super) fn close_upgrades_on_esc(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+    roots: Query<Entity, With<UpgradesPanelRoot>>,
+    children_q: Query<&Children>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        close_upgrades_panel(&mut commands, &roots, &children_q);
+    }
+}
+
+/// 离开主菜单时一并收起
+pub(super) fn cleanup_upgrades_panel(
+    mut commands: Commands,
+    roots: Query<Entity, With<UpgradesPanelRoot>>,
+    children_q: Query<&Children>,
+) {
+    close_upgrades_panel(&mut commands, &roots, &children_q);
+}
+
+fn close_upgrades_panel(
+    commands: &mut Commands,
+    roots: &Query<Entity, With<UpgradesPanelRoot>>,
+    children_q: &Query<&Children>,
+) {
+    for root in roots {
+        despawn_with_children(commands, children_q, root);
+    }
+}
@@ -11,4 +11,29 @@ pub fn despawn_with_children(
         }
     }
     commands.entity(entity).despawn();
-}
\ No newline at end of file
+}
+
+/// 按钮按下沿检测：只有从非 Pressed 进入 Pressed 的那一次算点击，
+/// 按住不放或交互组件被重复标记为 Changed 时都不会再次触发
+#[derive(Component, Default)]
+pub struct PressLatch {
+    was_pressed: bool,
+}
+
+impl PressLatch {
+    /// 用本帧的交互状态更新，返回是否刚刚按下
+    pub fn just_pressed(&mut self, interaction: Interaction) -> bool {
+        let pressed = interaction == Interaction::Pressed;
+        let edge = pressed && !self.was_pressed;
+        self.was_pressed = pressed;
+        edge
+    }
+
+    /// 用本帧的交互状态更新，返回是否在按钮上松开（完成一次点击）；
+    /// 拖到别处再松开不算
+    pub fn released_over(&mut self, interaction: Interaction) -> bool {
+        let released = self.was_pressed && interaction == Interaction::Hovered;
+        self.was_pressed = interaction == Interaction::Pressed;
+        released
+    }
+}
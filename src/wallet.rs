@@ -0,0 +1,115 @@
+// src/wallet.rs
+//! 金币：击杀敌人获得（精英给得更多），在商店里买卖物品。
+//! 属于单局资源，回到标题时清零；余额显示在左上角任务下方。
+
+use bevy::prelude::*;
+
+use crate::combat_core::EnemyDied;
+use crate::i18n::tr;
+use crate::state::GameState;
+use crate::ui::types::GameSettings;
+
+const GOLD_PER_KILL: u32 = 3;
+const GOLD_PER_ELITE_KILL: u32 = 15;
+
+#[derive(Resource, Default, Clone, Debug)]
+pub struct Wallet {
+    pub gold: u32,
+}
+
+impl Wallet {
+    pub fn earn(&mut self, amount: u32) {
+        self.gold = self.gold.saturating_add(amount);
+    }
+
+    /// 余额够就扣款并返回 true
+    pub fn try_spend(&mut self, amount: u32) -> bool {
+        if self.gold < amount {
+            return false;
+        }
+        self.gold -= amount;
+        true
+    }
+}
+
+#[derive(Component)]
+struct GoldText;
+
+pub struct WalletPlugin;
+
+impl Plugin for WalletPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Wallet>()
+            .add_systems(OnEnter(GameState::InGame), setup_gold_hud)
+            .add_systems(OnExit(GameState::InGame), cleanup_gold_hud)
+            .add_systems(
+                Update,
+                (earn_gold_from_kills, update_gold_hud).chain().run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnEnter(GameState::MainMenu), reset_wallet);
+    }
+}
+
+fn earn_gold_from_kills(mut wallet: ResMut<Wallet>, mut died_rx: MessageReader<EnemyDied>) {
+    let earned: u32 = died_rx
+        .read()
+        .map(|ev| if ev.elite { GOLD_PER_ELITE_KILL } else { GOLD_PER_KILL })
+        .sum();
+    if earned > 0 {
+        wallet.earn(earned);
+    }
+}
+
+fn reset_wallet(mut wallet: ResMut<Wallet>) {
+    *wallet = Wallet::default();
+}
+
+/// 放在任务下方
+fn setup_gold_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        GoldText,
+        Text::new(""),
+        TextFont {
+            font: asset_server.load("fonts/YuFanLixing.otf"),
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.85, 0.3)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(16.0),
+            top: Val::Px(108.0),
+            ..default()
+        },
+    ));
+}
+
+fn update_gold_hud(wallet: Res<Wallet>, settings: Res<GameSettings>, mut q: Query<&mut Text, With<GoldText>>) {
+    for mut t in &mut q {
+        let label = format!("{}: {}", tr(settings.language, "hud.gold"), wallet.gold);
+        if t.0 != label {
+            t.0 = label;
+        }
+    }
+}
+
+fn cleanup_gold_hud(mut commands: Commands, q: Query<Entity, With<GoldText>>) {
+    for e in &q {
+        commands.entity(e).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_spend_only_succeeds_with_enough_gold() {
+        let mut wallet = Wallet { gold: 10 };
+        assert!(!wallet.try_spend(11));
+        assert_eq!(wallet.gold, 10);
+        assert!(wallet.try_spend(10));
+        assert_eq!(wallet.gold, 0);
+        assert!(wallet.try_spend(0));
+    }
+}